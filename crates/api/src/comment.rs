@@ -5,24 +5,34 @@ use actix_web::web::Data;
 use lemmy_api_common::{
   blocking,
   check_community_ban,
+  check_community_deleted_or_removed,
   check_downvotes_enabled,
+  check_edit_history_visible,
+  check_private_instance,
   comment::*,
   get_local_user_view_from_jwt,
+  get_local_user_view_from_jwt_opt,
+  get_post,
+  reaction_limit_reached,
 };
 use lemmy_apub::{
   fetcher::post_or_comment::PostOrComment,
-  protocol::activities::voting::{
-    undo_vote::UndoVote,
-    vote::{Vote, VoteType},
+  objects::person::ApubPerson,
+  protocol::activities::{
+    reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+    voting::{
+      undo_vote::UndoVote,
+      vote::{Vote, VoteType},
+    },
   },
 };
 use lemmy_db_schema::{
   newtypes::LocalUserId,
   source::comment::*,
-  traits::{Likeable, Saveable},
+  traits::{Crud, Likeable, Reactable, Saveable},
 };
 use lemmy_db_views::{comment_view::CommentView, local_user_view::LocalUserView};
-use lemmy_utils::{ConnectionId, LemmyError};
+use lemmy_utils::{utils::is_valid_reaction, ConnectionId, LemmyError};
 use lemmy_websocket::{send::send_comment_ws_message, LemmyContext, UserOperation};
 
 use crate::Perform;
@@ -124,6 +134,82 @@ impl Perform for SaveComment {
   }
 }
 
+#[async_trait::async_trait(?Send)]
+impl Perform for CreateCommentReaction {
+  type Response = CommentResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<CommentResponse, LemmyError> {
+    let data: &CreateCommentReaction = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    if !is_valid_reaction(&data.emoji) {
+      return Err(LemmyError::from_message("invalid_reaction"));
+    }
+
+    let comment_id = data.comment_id;
+    let orig_comment = blocking(context.pool(), move |conn| {
+      CommentView::read(conn, comment_id, None)
+    })
+    .await??;
+
+    check_community_ban(
+      local_user_view.person.id,
+      orig_comment.community.id,
+      context.pool(),
+    )
+    .await?;
+    check_community_deleted_or_removed(orig_comment.community.id, context.pool()).await?;
+
+    let reaction_form = CommentReactionForm {
+      comment_id,
+      person_id: local_user_view.person.id,
+      emoji: data.emoji.to_owned(),
+    };
+    let react = data.react;
+    if react
+      && reaction_limit_reached::<CommentReaction>(reaction_form.clone(), context.pool()).await?
+    {
+      return Err(LemmyError::from_message("too_many_reactions"));
+    }
+    blocking(context.pool(), move |conn| {
+      if react {
+        CommentReaction::react(conn, &reaction_form).map(|_| ())
+      } else {
+        CommentReaction::unreact(conn, &reaction_form).map(|_| ())
+      }
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_react_to_comment"))?;
+
+    let object = PostOrComment::Comment(Box::new(orig_comment.comment.into()));
+    let actor: ApubPerson = local_user_view.person.clone().into();
+    let community_id = orig_comment.community.id;
+    let emoji = data.emoji.to_owned();
+    if react {
+      EmojiReact::send(&object, &actor, community_id, emoji, context).await?;
+    } else {
+      UndoEmojiReact::send(&object, &actor, community_id, emoji, context).await?;
+    }
+
+    send_comment_ws_message(
+      data.comment_id,
+      UserOperation::CreateCommentReaction,
+      websocket_id,
+      None,
+      Some(local_user_view.person.id),
+      vec![],
+      context,
+    )
+    .await
+  }
+}
+
 #[async_trait::async_trait(?Send)]
 impl Perform for CreateCommentLike {
   type Response = CommentResponse;
@@ -223,3 +309,36 @@ impl Perform for CreateCommentLike {
     .await
   }
 }
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListCommentRevisions {
+  type Response = ListCommentRevisionsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListCommentRevisionsResponse, LemmyError> {
+    let data: &ListCommentRevisions = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let comment_id = data.comment_id;
+    let comment = blocking(context.pool(), move |conn| Comment::read(conn, comment_id))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_comment"))?;
+    let post = get_post(comment.post_id, context.pool()).await?;
+    let hidden = comment.deleted || comment.removed;
+    check_edit_history_visible(&local_user_view, post.community_id, hidden, context.pool()).await?;
+
+    let revisions = blocking(context.pool(), move |conn| {
+      CommentRevision::list_for_comment(conn, comment_id)
+    })
+    .await??;
+
+    Ok(ListCommentRevisionsResponse { revisions })
+  }
+}
@@ -7,15 +7,19 @@ use lemmy_api_common::{
   get_local_user_view_from_jwt,
   is_mod_or_admin,
 };
-use lemmy_apub::protocol::activities::community::report::Report;
+use lemmy_apub::protocol::activities::community::{report::Report, resolve_report::ResolveReport};
 use lemmy_apub_lib::object_id::ObjectId;
-use lemmy_db_schema::{source::comment_report::*, traits::Reportable};
+use lemmy_db_schema::{
+  source::{comment_report::*, person::Person},
+  traits::{Crud, Reportable},
+};
 use lemmy_db_views::{
   comment_report_view::{CommentReportQueryBuilder, CommentReportView},
   comment_view::CommentView,
 };
 use lemmy_utils::{ConnectionId, LemmyError};
 use lemmy_websocket::{messages::SendModRoomMessage, LemmyContext, UserOperation};
+use tracing::warn;
 
 /// Creates a comment report and notifies the moderators of the community
 #[async_trait::async_trait(?Send)]
@@ -51,10 +55,11 @@ impl Perform for CreateCommentReport {
     check_community_ban(person_id, comment_view.community.id, context.pool()).await?;
 
     let report_form = CommentReportForm {
-      creator_id: person_id,
+      creator_id: Some(person_id),
       comment_id,
       original_comment_text: comment_view.comment.content,
       reason: data.reason.to_owned(),
+      forwarded_by_site_id: None,
     };
 
     let report = blocking(context.pool(), move |conn| {
@@ -130,6 +135,24 @@ impl Perform for ResolveCommentReport {
       .await?
       .map_err(|e| LemmyError::from_error_message(e, "couldnt_resolve_report"))?;
 
+    // Forwarded reports have no creator, and are about remote communities which resolve them
+    if let (true, Some(creator_id)) = (resolved, report.comment_report.creator_id) {
+      let creator = blocking(context.pool(), move |conn| Person::read(conn, creator_id)).await??;
+      // The report is resolved already, so this request shouldnt fail if federation does
+      if let Err(e) = ResolveReport::send(
+        ObjectId::new(report.comment.ap_id.clone()),
+        &creator.into(),
+        context,
+      )
+      .await
+      {
+        warn!(
+          "Failed to federate resolution of comment report {}: {}",
+          report_id, e
+        );
+      }
+    }
+
     let report_id = data.report_id;
     let comment_report_view = blocking(context.pool(), move |conn| {
       CommentReportView::read(conn, report_id, person_id)
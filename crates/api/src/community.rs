@@ -12,13 +12,16 @@ use lemmy_api_common::{
 };
 use lemmy_apub::{
   activities::block::SiteOrCommunity,
+  fetcher::resolve_actor_identifier,
   objects::{community::ApubCommunity, person::ApubPerson},
   protocol::activities::{
     block::{block_user::BlockUser, undo_block_user::UndoBlockUser},
     community::{add_mod::AddMod, remove_mod::RemoveMod},
     following::{follow::FollowCommunity as FollowCommunityApub, undo_follow::UndoFollowCommunity},
+    migration::move_community::MoveCommunity as MoveCommunityApub,
   },
 };
+use lemmy_apub_lib::object_id::ObjectId;
 use lemmy_db_schema::{
   source::{
     community::{
@@ -50,6 +53,7 @@ use lemmy_db_views_actor::{
 };
 use lemmy_utils::{location_info, utils::naive_from_unix, ConnectionId, LemmyError};
 use lemmy_websocket::{messages::SendCommunityRoomMessage, LemmyContext, UserOperation};
+use url::Url;
 
 #[async_trait::async_trait(?Send)]
 impl Perform for FollowCommunity {
@@ -510,3 +514,50 @@ impl Perform for TransferCommunity {
     })
   }
 }
+
+#[async_trait::async_trait(?Send)]
+impl Perform for MoveCommunity {
+  type Response = CommunityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<CommunityResponse, LemmyError> {
+    let data: &MoveCommunity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let community_id = data.community_id;
+    is_mod_or_admin(context.pool(), local_user_view.person.id, community_id).await?;
+
+    let target = data.target.trim();
+    let target: ObjectId<ApubCommunity> = match Url::parse(target) {
+      Ok(url) => ObjectId::new(url),
+      Err(_) => {
+        let community = resolve_actor_identifier::<ApubCommunity, Community>(target, context)
+          .await
+          .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
+        ObjectId::new(community.actor_id)
+      }
+    };
+
+    let community: ApubCommunity = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??
+    .into();
+    MoveCommunityApub::send(&community, target, &local_user_view.person.into(), context).await?;
+
+    // Other clients are informed through the websocket once the community is marked as moved
+    let person_id = local_user_view.person.id;
+    let community_view = blocking(context.pool(), move |conn| {
+      CommunityView::read(conn, community_id, Some(person_id))
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
+
+    Ok(CommunityResponse { community_view })
+  }
+}
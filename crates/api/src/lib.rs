@@ -47,6 +47,28 @@ pub async fn match_websocket_operation(
     UserOperation::ApproveRegistrationApplication => {
       do_websocket_operation::<ApproveRegistrationApplication>(context, id, op, data).await
     }
+    UserOperation::GetActivityQueueStats => {
+      do_websocket_operation::<GetActivityQueueStats>(context, id, op, data).await
+    }
+    UserOperation::ListInstanceDeliveries => {
+      do_websocket_operation::<ListInstanceDeliveries>(context, id, op, data).await
+    }
+    UserOperation::GetInboxQueueStats => {
+      do_websocket_operation::<GetInboxQueueStats>(context, id, op, data).await
+    }
+    UserOperation::GetFederationList => {
+      do_websocket_operation::<GetFederationList>(context, id, op, data).await
+    }
+    UserOperation::AddFederationListEntry => {
+      do_websocket_operation::<AddFederationListEntry>(context, id, op, data).await
+    }
+    UserOperation::RemoveFederationListEntry => {
+      do_websocket_operation::<RemoveFederationListEntry>(context, id, op, data).await
+    }
+    UserOperation::DebugActivity => {
+      do_websocket_operation::<DebugActivity>(context, id, op, data).await
+    }
+    UserOperation::RotateKeys => do_websocket_operation::<RotateKeys>(context, id, op, data).await,
     UserOperation::BanPerson => do_websocket_operation::<BanPerson>(context, id, op, data).await,
     UserOperation::GetBannedPersons => {
       do_websocket_operation::<GetBannedPersons>(context, id, op, data).await
@@ -78,6 +100,9 @@ pub async fn match_websocket_operation(
     UserOperation::SaveUserSettings => {
       do_websocket_operation::<SaveUserSettings>(context, id, op, data).await
     }
+    UserOperation::MoveAccount => {
+      do_websocket_operation::<MoveAccount>(context, id, op, data).await
+    }
     UserOperation::ChangePassword => {
       do_websocket_operation::<ChangePassword>(context, id, op, data).await
     }
@@ -111,6 +136,9 @@ pub async fn match_websocket_operation(
     UserOperation::TransferCommunity => {
       do_websocket_operation::<TransferCommunity>(context, id, op, data).await
     }
+    UserOperation::MoveCommunity => {
+      do_websocket_operation::<MoveCommunity>(context, id, op, data).await
+    }
     UserOperation::LeaveAdmin => do_websocket_operation::<LeaveAdmin>(context, id, op, data).await,
 
     // Community ops
@@ -133,6 +161,12 @@ pub async fn match_websocket_operation(
     UserOperation::CreatePostLike => {
       do_websocket_operation::<CreatePostLike>(context, id, op, data).await
     }
+    UserOperation::CreatePostReaction => {
+      do_websocket_operation::<CreatePostReaction>(context, id, op, data).await
+    }
+    UserOperation::CreatePollVote => {
+      do_websocket_operation::<CreatePollVote>(context, id, op, data).await
+    }
     UserOperation::MarkPostAsRead => {
       do_websocket_operation::<MarkPostAsRead>(context, id, op, data).await
     }
@@ -149,6 +183,9 @@ pub async fn match_websocket_operation(
     UserOperation::GetSiteMetadata => {
       do_websocket_operation::<GetSiteMetadata>(context, id, op, data).await
     }
+    UserOperation::ListPostRevisions => {
+      do_websocket_operation::<ListPostRevisions>(context, id, op, data).await
+    }
 
     // Comment ops
     UserOperation::MarkCommentAsRead => {
@@ -160,6 +197,9 @@ pub async fn match_websocket_operation(
     UserOperation::CreateCommentLike => {
       do_websocket_operation::<CreateCommentLike>(context, id, op, data).await
     }
+    UserOperation::CreateCommentReaction => {
+      do_websocket_operation::<CreateCommentReaction>(context, id, op, data).await
+    }
     UserOperation::CreateCommentReport => {
       do_websocket_operation::<CreateCommentReport>(context, id, op, data).await
     }
@@ -169,6 +209,9 @@ pub async fn match_websocket_operation(
     UserOperation::ResolveCommentReport => {
       do_websocket_operation::<ResolveCommentReport>(context, id, op, data).await
     }
+    UserOperation::ListCommentRevisions => {
+      do_websocket_operation::<ListCommentRevisions>(context, id, op, data).await
+    }
   }
 }
 
@@ -206,7 +249,7 @@ pub(crate) fn captcha_as_wav_base64(captcha: &Captcha) -> String {
 
 #[cfg(test)]
 mod tests {
-  use lemmy_api_common::check_validator_time;
+  use lemmy_api_common::{check_edit_history_access, check_validator_time};
   use lemmy_db_schema::{
     establish_unpooled_connection,
     source::{
@@ -258,4 +301,20 @@ mod tests {
     let num_deleted = Person::delete(&conn, inserted_person.id).unwrap();
     assert_eq!(1, num_deleted);
   }
+
+  #[test]
+  fn test_edit_history_access() {
+    // Public edit history is visible to everyone
+    assert!(check_edit_history_access(true, false, false).is_ok());
+    assert!(check_edit_history_access(true, false, true).is_ok());
+
+    // Otherwise only to mods and admins
+    assert!(check_edit_history_access(false, false, false).is_err());
+    assert!(check_edit_history_access(false, false, true).is_ok());
+
+    // Deleted or removed content keeps its history hidden, even on sites with public history
+    assert!(check_edit_history_access(true, true, false).is_err());
+    assert!(check_edit_history_access(false, true, false).is_err());
+    assert!(check_edit_history_access(true, true, true).is_ok());
+  }
 }
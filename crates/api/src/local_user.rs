@@ -9,6 +9,7 @@ use lemmy_api_common::{
   check_registration_application,
   get_local_user_view_from_jwt,
   is_admin,
+  language_check,
   password_length_check,
   person::*,
   remove_user_data,
@@ -18,13 +19,20 @@ use lemmy_api_common::{
 };
 use lemmy_apub::{
   activities::block::SiteOrCommunity,
-  protocol::activities::block::{block_user::BlockUser, undo_block_user::UndoBlockUser},
+  fetcher::resolve_actor_identifier,
+  objects::person::ApubPerson,
+  protocol::activities::{
+    block::{block_user::BlockUser, undo_block_user::UndoBlockUser},
+    migration::move_person::MovePerson,
+  },
 };
+use lemmy_apub_lib::object_id::ObjectId;
 use lemmy_db_schema::{
   diesel_option_overwrite,
   diesel_option_overwrite_to_url,
   from_opt_str_to_opt_enum,
   naive_now,
+  newtypes::DbUrl,
   source::{
     comment::Comment,
     email_verification::EmailVerification,
@@ -62,6 +70,7 @@ use lemmy_websocket::{
   LemmyContext,
   UserOperation,
 };
+use url::Url;
 
 #[async_trait::async_trait(?Send)]
 impl Perform for Login {
@@ -184,9 +193,20 @@ impl Perform for SaveUserSettings {
     let bot_account = data.bot_account;
     let email_deref = data.email.as_deref().map(|e| e.to_owned());
     let email = diesel_option_overwrite(&email_deref);
-
-    check_image_has_local_domain(avatar.as_ref().unwrap_or(&None))?;
-    check_image_has_local_domain(banner.as_ref().unwrap_or(&None))?;
+    let also_known_as = data
+      .also_known_as
+      .as_ref()
+      .map(|aliases| {
+        aliases
+          .iter()
+          .map(|a| Url::parse(a.trim()).map(Into::into))
+          .collect::<Result<Vec<DbUrl>, _>>()
+      })
+      .transpose()
+      .map_err(|e| LemmyError::from_error_message(e, "invalid_url"))?;
+
+    check_image_has_local_domain(avatar.as_ref().unwrap_or(&None), &context.settings())?;
+    check_image_has_local_domain(banner.as_ref().unwrap_or(&None), &context.settings())?;
 
     if let Some(Some(email)) = &email {
       let previous_email = local_user_view.local_user.email.clone().unwrap_or_default();
@@ -220,6 +240,16 @@ impl Perform for SaveUserSettings {
       }
     }
 
+    let default_language = diesel_option_overwrite(&data.default_language);
+    if let Some(Some(language)) = &default_language {
+      language_check(language)?;
+    }
+    if let Some(languages) = &data.discussion_languages {
+      for language in languages {
+        language_check(language)?;
+      }
+    }
+
     if let Some(Some(matrix_user_id)) = &matrix_user_id {
       if !is_valid_matrix_id(matrix_user_id) {
         return Err(LemmyError::from_message("invalid_matrix_id"));
@@ -254,6 +284,8 @@ impl Perform for SaveUserSettings {
       matrix_user_id,
       bot_account,
       ban_expires: None,
+      moved_to: None,
+      also_known_as,
     };
 
     blocking(context.pool(), move |conn| {
@@ -279,6 +311,8 @@ impl Perform for SaveUserSettings {
       send_notifications_to_email: data.send_notifications_to_email,
       email_verified: None,
       accepted_application: None,
+      default_language,
+      discussion_languages: data.discussion_languages.to_owned(),
     };
 
     let local_user_res = blocking(context.pool(), move |conn| {
@@ -316,6 +350,44 @@ impl Perform for SaveUserSettings {
   }
 }
 
+#[async_trait::async_trait(?Send)]
+impl Perform for MoveAccount {
+  type Response = MoveAccountResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<MoveAccountResponse, LemmyError> {
+    let data: &MoveAccount = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let target = data.target.trim();
+    let target: ObjectId<ApubPerson> = match Url::parse(target) {
+      Ok(url) => ObjectId::new(url),
+      Err(_) => {
+        let person = resolve_actor_identifier::<ApubPerson, Person>(target, context)
+          .await
+          .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_person"))?;
+        ObjectId::new(person.actor_id)
+      }
+    };
+
+    let actor: ApubPerson = local_user_view.person.into();
+    MovePerson::send(&actor, target, context).await?;
+
+    let person_id = actor.id;
+    let person_view = blocking(context.pool(), move |conn| {
+      PersonViewSafe::read(conn, person_id)
+    })
+    .await??;
+
+    Ok(MoveAccountResponse { person_view })
+  }
+}
+
 #[async_trait::async_trait(?Send)]
 impl Perform for ChangePassword {
   type Response = LoginResponse;
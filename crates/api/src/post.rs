@@ -5,17 +5,25 @@ use lemmy_api_common::{
   check_community_ban,
   check_community_deleted_or_removed,
   check_downvotes_enabled,
+  check_edit_history_visible,
+  check_private_instance,
   get_local_user_view_from_jwt,
+  get_local_user_view_from_jwt_opt,
+  get_post,
   is_mod_or_admin,
   mark_post_as_read,
   mark_post_as_unread,
   post::*,
+  reaction_limit_reached,
 };
 use lemmy_apub::{
+  activities::poll::vote_in_poll,
   fetcher::post_or_comment::PostOrComment,
-  objects::post::ApubPost,
+  objects::{person::ApubPerson, post::ApubPost},
   protocol::activities::{
     create_or_update::post::CreateOrUpdatePost,
+    poll::create_poll_answer::CreatePollAnswer,
+    reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
     voting::{
       undo_vote::UndoVote,
       vote::{Vote, VoteType},
@@ -24,11 +32,20 @@ use lemmy_apub::{
   },
 };
 use lemmy_db_schema::{
-  source::{moderator::*, post::*},
-  traits::{Crud, Likeable, Saveable},
+  source::{
+    moderator::*,
+    poll::{Poll, PollOption},
+    post::*,
+  },
+  traits::{Crud, Likeable, Reactable, Saveable},
 };
 use lemmy_db_views::post_view::PostView;
-use lemmy_utils::{request::fetch_site_metadata, ConnectionId, LemmyError};
+use lemmy_utils::{
+  request::fetch_site_metadata,
+  utils::is_valid_reaction,
+  ConnectionId,
+  LemmyError,
+};
 use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperation};
 use std::convert::TryInto;
 
@@ -117,6 +134,133 @@ impl Perform for CreatePostLike {
   }
 }
 
+#[async_trait::async_trait(?Send)]
+impl Perform for CreatePostReaction {
+  type Response = PostResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<PostResponse, LemmyError> {
+    let data: &CreatePostReaction = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    if !is_valid_reaction(&data.emoji) {
+      return Err(LemmyError::from_message("invalid_reaction"));
+    }
+
+    let post_id = data.post_id;
+    let post: ApubPost = blocking(context.pool(), move |conn| Post::read(conn, post_id))
+      .await??
+      .into();
+
+    check_community_ban(local_user_view.person.id, post.community_id, context.pool()).await?;
+    check_community_deleted_or_removed(post.community_id, context.pool()).await?;
+
+    let reaction_form = PostReactionForm {
+      post_id,
+      person_id: local_user_view.person.id,
+      emoji: data.emoji.to_owned(),
+    };
+    let react = data.react;
+    if react
+      && reaction_limit_reached::<PostReaction>(reaction_form.clone(), context.pool()).await?
+    {
+      return Err(LemmyError::from_message("too_many_reactions"));
+    }
+    blocking(context.pool(), move |conn| {
+      if react {
+        PostReaction::react(conn, &reaction_form).map(|_| ())
+      } else {
+        PostReaction::unreact(conn, &reaction_form).map(|_| ())
+      }
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_react_to_post"))?;
+
+    let community_id = post.community_id;
+    let object = PostOrComment::Post(Box::new(post));
+    let actor: ApubPerson = local_user_view.person.clone().into();
+    let emoji = data.emoji.to_owned();
+    if react {
+      EmojiReact::send(&object, &actor, community_id, emoji, context).await?;
+    } else {
+      UndoEmojiReact::send(&object, &actor, community_id, emoji, context).await?;
+    }
+
+    send_post_ws_message(
+      data.post_id,
+      UserOperation::CreatePostReaction,
+      websocket_id,
+      Some(local_user_view.person.id),
+      context,
+    )
+    .await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl Perform for CreatePollVote {
+  type Response = PostResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<PostResponse, LemmyError> {
+    let data: &CreatePollVote = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let post_id = data.post_id;
+    let post: ApubPost = blocking(context.pool(), move |conn| Post::read(conn, post_id))
+      .await??
+      .into();
+
+    check_community_ban(local_user_view.person.id, post.community_id, context.pool()).await?;
+    check_community_deleted_or_removed(post.community_id, context.pool()).await?;
+
+    let person_id = local_user_view.person.id;
+    let option_ids = data.option_ids.clone();
+    let local = post.local;
+    let options = blocking(context.pool(), move |conn| -> Result<_, LemmyError> {
+      let poll = Poll::read_for_post(conn, post_id)?
+        .ok_or_else(|| LemmyError::from_message("post_has_no_poll"))?;
+      vote_in_poll(conn, &poll, &option_ids, person_id)?;
+      if local {
+        Poll::mark_results_pending(conn, poll.id)?;
+      }
+      Ok(
+        PollOption::list_for_poll(conn, poll.id)?
+          .into_iter()
+          .filter(|o| option_ids.contains(&o.id))
+          .collect::<Vec<PollOption>>(),
+      )
+    })
+    .await??;
+
+    // The poll author's instance counts the votes, and periodically sends the results to everyone
+    // else
+    if !post.local {
+      let actor: ApubPerson = local_user_view.person.clone().into();
+      CreatePollAnswer::send(&post, &actor, options, context).await?;
+    }
+
+    send_post_ws_message(
+      data.post_id,
+      UserOperation::CreatePollVote,
+      websocket_id,
+      Some(local_user_view.person.id),
+      context,
+    )
+    .await
+  }
+}
+
 #[async_trait::async_trait(?Send)]
 impl Perform for MarkPostAsRead {
   type Response = PostResponse;
@@ -359,3 +503,33 @@ impl Perform for GetSiteMetadata {
     Ok(GetSiteMetadataResponse { metadata })
   }
 }
+
+#[async_trait::async_trait(?Send)]
+impl Perform for ListPostRevisions {
+  type Response = ListPostRevisionsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListPostRevisionsResponse, LemmyError> {
+    let data: &ListPostRevisions = self;
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let post = get_post(data.post_id, context.pool()).await?;
+    let hidden = post.deleted || post.removed || post.scheduled_publish_time.is_some();
+    check_edit_history_visible(&local_user_view, post.community_id, hidden, context.pool()).await?;
+
+    let post_id = data.post_id;
+    let revisions = blocking(context.pool(), move |conn| {
+      PostRevision::list_for_post(conn, post_id)
+    })
+    .await??;
+
+    Ok(ListPostRevisionsResponse { revisions })
+  }
+}
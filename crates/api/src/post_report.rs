@@ -13,11 +13,14 @@ use lemmy_api_common::{
     ResolvePostReport,
   },
 };
-use lemmy_apub::protocol::activities::community::report::Report;
+use lemmy_apub::protocol::activities::community::{report::Report, resolve_report::ResolveReport};
 use lemmy_apub_lib::object_id::ObjectId;
 use lemmy_db_schema::{
-  source::post_report::{PostReport, PostReportForm},
-  traits::Reportable,
+  source::{
+    person::Person,
+    post_report::{PostReport, PostReportForm},
+  },
+  traits::{Crud, Reportable},
 };
 use lemmy_db_views::{
   post_report_view::{PostReportQueryBuilder, PostReportView},
@@ -25,6 +28,7 @@ use lemmy_db_views::{
 };
 use lemmy_utils::{ConnectionId, LemmyError};
 use lemmy_websocket::{messages::SendModRoomMessage, LemmyContext, UserOperation};
+use tracing::warn;
 
 /// Creates a post report and notifies the moderators of the community
 #[async_trait::async_trait(?Send)]
@@ -60,12 +64,13 @@ impl Perform for CreatePostReport {
     check_community_ban(person_id, post_view.community.id, context.pool()).await?;
 
     let report_form = PostReportForm {
-      creator_id: person_id,
+      creator_id: Some(person_id),
       post_id,
       original_post_name: post_view.post.name,
       original_post_url: post_view.post.url,
       original_post_body: post_view.post.body,
       reason: data.reason.to_owned(),
+      forwarded_by_site_id: None,
     };
 
     let report = blocking(context.pool(), move |conn| {
@@ -139,6 +144,24 @@ impl Perform for ResolvePostReport {
       .await?
       .map_err(|e| LemmyError::from_error_message(e, "couldnt_resolve_report"))?;
 
+    // Forwarded reports have no creator, and are about remote communities which resolve them
+    if let (true, Some(creator_id)) = (resolved, report.post_report.creator_id) {
+      let creator = blocking(context.pool(), move |conn| Person::read(conn, creator_id)).await??;
+      // The report is resolved already, so this request shouldnt fail if federation does
+      if let Err(e) = ResolveReport::send(
+        ObjectId::new(report.post.ap_id.clone()),
+        &creator.into(),
+        context,
+      )
+      .await
+      {
+        warn!(
+          "Failed to federate resolution of post report {}: {}",
+          report_id, e
+        );
+      }
+    }
+
     let post_report_view = blocking(context.pool(), move |conn| {
       PostReportView::read(conn, report_id, person_id)
     })
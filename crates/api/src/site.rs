@@ -1,6 +1,6 @@
 use crate::Perform;
 use actix_web::web::Data;
-use diesel::NotFound;
+use diesel::{Connection, NotFound, PgConnection};
 use lemmy_api_common::{
   blocking,
   build_federated_instances,
@@ -8,15 +8,23 @@ use lemmy_api_common::{
   get_local_user_view_from_jwt,
   get_local_user_view_from_jwt_opt,
   is_admin,
+  is_mod_or_admin,
   send_application_approved_email,
   site::*,
 };
 use lemmy_apub::{
+  debug::{debug_activity, InboxRequest},
+  federation_list::refresh_federation_lists,
   fetcher::{
     resolve_actor_identifier,
     search::{search_by_apub_id, SearchableObjects},
   },
-  objects::community::ApubCommunity,
+  objects::{
+    community::ApubCommunity,
+    instance::{init_authorized_fetch, ApubSite},
+    person::ApubPerson,
+  },
+  protocol::activities::{community::update::UpdateCommunity, instance::update::UpdateInstance},
 };
 use lemmy_db_schema::{
   diesel_option_overwrite,
@@ -24,6 +32,9 @@ use lemmy_db_schema::{
   newtypes::PersonId,
   source::{
     community::Community,
+    federation_list::{FederationListEntry, FederationListEntryForm},
+    inbox_queue::InboxQueueEntry,
+    instance_delivery::InstanceDelivery,
     local_user::{LocalUser, LocalUserForm},
     moderator::*,
     person::Person,
@@ -44,6 +55,7 @@ use lemmy_db_views::{
     RegistrationApplicationQueryBuilder,
     RegistrationApplicationView,
   },
+  search_snippet::{comment_search_snippets, post_search_snippets},
   site_view::SiteView,
 };
 use lemmy_db_views_actor::{
@@ -55,6 +67,7 @@ use lemmy_db_views_moderator::{
   mod_add_view::ModAddView,
   mod_ban_from_community_view::ModBanFromCommunityView,
   mod_ban_view::ModBanView,
+  mod_federation_list_view::ModFederationListView,
   mod_hide_community_view::ModHideCommunityView,
   mod_lock_post_view::ModLockPostView,
   mod_remove_comment_view::ModRemoveCommentView,
@@ -63,8 +76,16 @@ use lemmy_db_views_moderator::{
   mod_sticky_post_view::ModStickyPostView,
   mod_transfer_community_view::ModTransferCommunityView,
 };
-use lemmy_utils::{settings::structs::Settings, version, ConnectionId, LemmyError};
+use lemmy_utils::{
+  apub::generate_actor_keypair,
+  settings::structs::Settings,
+  version,
+  ConnectionId,
+  LemmyError,
+};
 use lemmy_websocket::LemmyContext;
+use tracing::warn;
+use url::Url;
 
 #[async_trait::async_trait(?Send)]
 impl Perform for GetModlog {
@@ -129,17 +150,18 @@ impl Perform for GetModlog {
     .await??;
 
     // These arrays are only for the full modlog, when a community isn't given
-    let (removed_communities, banned, added) = if data.community_id.is_none() {
+    let (removed_communities, banned, added, federation_list) = if data.community_id.is_none() {
       blocking(context.pool(), move |conn| {
         Ok((
           ModRemoveCommunityView::list(conn, mod_person_id, page, limit)?,
           ModBanView::list(conn, mod_person_id, page, limit)?,
           ModAddView::list(conn, mod_person_id, page, limit)?,
+          ModFederationListView::list(conn, mod_person_id, page, limit)?,
         )) as Result<_, LemmyError>
       })
       .await??
     } else {
-      (Vec::new(), Vec::new(), Vec::new())
+      (Vec::new(), Vec::new(), Vec::new(), Vec::new())
     };
 
     // Return the jwt
@@ -155,6 +177,7 @@ impl Perform for GetModlog {
       added,
       transferred_to_community,
       hidden_communities,
+      federation_list,
     })
   }
 }
@@ -185,6 +208,13 @@ impl Perform for Search {
       .as_ref()
       .map(|t| t.local_user.show_read_posts);
 
+    // Other forms of the search terms are found in the user's language, unless another is given
+    let search_language = data.language.to_owned().or_else(|| {
+      local_user_view
+        .as_ref()
+        .and_then(|l| l.local_user.default_language.to_owned())
+    });
+
     let person_id = local_user_view.map(|u| u.person.id);
 
     let mut posts = Vec::new();
@@ -212,6 +242,7 @@ impl Perform for Search {
     let creator_id = data.creator_id;
     match search_type {
       SearchType::Posts => {
+        let search_language = search_language.to_owned();
         posts = blocking(context.pool(), move |conn| {
           PostQueryBuilder::create(conn)
             .sort(sort)
@@ -224,6 +255,7 @@ impl Perform for Search {
             .creator_id(creator_id)
             .my_person_id(person_id)
             .search_term(q)
+            .search_language(search_language)
             .page(page)
             .limit(limit)
             .list()
@@ -231,11 +263,13 @@ impl Perform for Search {
         .await??;
       }
       SearchType::Comments => {
+        let search_language = search_language.to_owned();
         comments = blocking(context.pool(), move |conn| {
           CommentQueryBuilder::create(conn)
             .sort(sort)
             .listing_type(listing_type)
             .search_term(q)
+            .search_language(search_language)
             .show_bot_accounts(show_bot_accounts)
             .community_id(community_id)
             .community_actor_id(community_actor_id)
@@ -276,6 +310,7 @@ impl Perform for Search {
         let community_or_creator_included =
           data.community_id.is_some() || data.community_name.is_some() || data.creator_id.is_some();
         let community_actor_id_2 = community_actor_id.to_owned();
+        let search_language_2 = search_language.to_owned();
 
         posts = blocking(context.pool(), move |conn| {
           PostQueryBuilder::create(conn)
@@ -289,6 +324,7 @@ impl Perform for Search {
             .creator_id(creator_id)
             .my_person_id(person_id)
             .search_term(q)
+            .search_language(search_language_2)
             .page(page)
             .limit(limit)
             .list()
@@ -297,12 +333,14 @@ impl Perform for Search {
 
         let q = data.q.to_owned();
         let community_actor_id = community_actor_id.to_owned();
+        let search_language = search_language.to_owned();
 
         comments = blocking(context.pool(), move |conn| {
           CommentQueryBuilder::create(conn)
             .sort(sort)
             .listing_type(listing_type)
             .search_term(q)
+            .search_language(search_language)
             .show_bot_accounts(show_bot_accounts)
             .community_id(community_id)
             .community_actor_id(community_actor_id)
@@ -393,6 +431,37 @@ impl Perform for Search {
       }
     }
 
+    // Snippets aren't needed for url searches, empty searches which list everything, or for
+    // deleted and removed content
+    let (post_ids, comment_ids) =
+      if matches!(search_type, SearchType::Url) || data.q.trim().is_empty() {
+        (vec![], vec![])
+      } else {
+        (
+          posts
+            .iter()
+            .filter(|p| !p.post.deleted && !p.post.removed)
+            .map(|p| p.post.id)
+            .collect(),
+          comments
+            .iter()
+            .filter(|c| !c.comment.deleted && !c.comment.removed)
+            .map(|c| c.comment.id)
+            .collect(),
+        )
+      };
+    let q = data.q.to_owned();
+    let language = search_language.to_owned();
+    let post_snippets = blocking(context.pool(), move |conn| {
+      post_search_snippets(conn, post_ids, q, language)
+    })
+    .await??;
+    let q = data.q.to_owned();
+    let comment_snippets = blocking(context.pool(), move |conn| {
+      comment_search_snippets(conn, comment_ids, q, search_language)
+    })
+    .await??;
+
     // Return the jwt
     Ok(SearchResponse {
       type_: search_type.to_string(),
@@ -400,6 +469,8 @@ impl Perform for Search {
       posts,
       communities,
       users,
+      post_snippets,
+      comment_snippets,
     })
   }
 }
@@ -452,7 +523,16 @@ async fn convert_response(
         Some(blocking(pool, move |conn| CommunityView::read(conn, c.id, user_id)).await??)
     }
     Post(p) => {
-      removed_or_deleted = p.deleted || p.removed;
+      // Scheduled posts are only visible to their creator and the moderators
+      let scheduled_hidden = match user_id {
+        _ if p.scheduled_publish_time.is_none() => false,
+        Some(user_id) if user_id == p.creator_id => false,
+        Some(user_id) => is_mod_or_admin(pool, user_id, p.community_id)
+          .await
+          .is_err(),
+        None => true,
+      };
+      removed_or_deleted = p.deleted || p.removed || scheduled_hidden;
       res.post = Some(blocking(pool, move |conn| PostView::read(conn, p.id, user_id)).await??)
     }
     Comment(c) => {
@@ -565,6 +645,9 @@ impl Perform for SaveSiteConfig {
     // Make sure docker doesn't have :ro at the end of the volume, so its not a read-only filesystem
     let config_hjson = Settings::save_config_file(&data.config_hjson)
       .map_err(|e| e.with_message("couldnt_update_site"))?;
+    context
+      .reload_settings()
+      .map_err(|e| e.with_message("couldnt_update_site"))?;
 
     Ok(GetSiteConfigResponse { config_hjson })
   }
@@ -708,3 +791,356 @@ impl Perform for GetUnreadRegistrationApplicationCount {
     })
   }
 }
+
+/// Shows how many outgoing activities are waiting for delivery, and how many failed for good.
+#[async_trait::async_trait(?Send)]
+impl Perform for GetActivityQueueStats {
+  type Response = GetActivityQueueStatsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &GetActivityQueueStats = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let stats = context.activity_queue().get_stats().await?;
+
+    Ok(Self::Response {
+      pending: stats.pending,
+      running: stats.running,
+      dead: ActivityQueueJobCounts {
+        this_hour: stats.dead.this_hour(),
+        today: stats.dead.today(),
+        this_month: stats.dead.this_month(),
+        all_time: stats.dead.all_time(),
+      },
+      complete: ActivityQueueJobCounts {
+        this_hour: stats.complete.this_hour(),
+        today: stats.complete.today(),
+        this_month: stats.complete.this_month(),
+        all_time: stats.complete.all_time(),
+      },
+    })
+  }
+}
+
+/// Lists the delivery statistics of all remote instances, including whether they are suspended.
+#[async_trait::async_trait(?Send)]
+impl Perform for ListInstanceDeliveries {
+  type Response = ListInstanceDeliveriesResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &ListInstanceDeliveries = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let instances = blocking(context.pool(), InstanceDelivery::list).await??;
+
+    Ok(Self::Response { instances })
+  }
+}
+
+/// Shows how many incoming activities are waiting to be processed, and how long processing takes.
+#[async_trait::async_trait(?Send)]
+impl Perform for GetInboxQueueStats {
+  type Response = GetInboxQueueStatsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &GetInboxQueueStats = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let inbox_queue = blocking(context.pool(), InboxQueueEntry::stats).await??;
+
+    Ok(Self::Response { inbox_queue })
+  }
+}
+
+/// Lists the federation allowlist and blocklist, including who added each entry and why.
+#[async_trait::async_trait(?Send)]
+impl Perform for GetFederationList {
+  type Response = FederationListResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &GetFederationList = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let entries = blocking(context.pool(), FederationListEntry::list).await??;
+
+    Ok(Self::Response { entries })
+  }
+}
+
+/// Adds an instance to the federation allowlist or blocklist. Takes effect immediately in this
+/// process, and within a minute in other processes which share the database.
+#[async_trait::async_trait(?Send)]
+impl Perform for AddFederationListEntry {
+  type Response = FederationListResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &AddFederationListEntry = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let domain = parse_federation_list_domain(&data.domain)?;
+    let form = FederationListEntryForm {
+      domain: domain.clone(),
+      blocked: data.blocked,
+      admin_person_id: Some(local_user_view.person.id),
+      reason: data.reason.to_owned(),
+    };
+    let mod_form = ModFederationListForm {
+      mod_person_id: local_user_view.person.id,
+      domain,
+      blocked: data.blocked,
+      removed: false,
+      reason: data.reason.to_owned(),
+    };
+    blocking(context.pool(), move |conn| {
+      conn.transaction(|| {
+        FederationListEntry::upsert(conn, &form)?;
+        ModFederationList::create(conn, &mod_form)
+      })
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_federation_list"))?;
+
+    refresh_federation_lists(context).await?;
+
+    let entries = blocking(context.pool(), FederationListEntry::list).await??;
+    Ok(Self::Response { entries })
+  }
+}
+
+/// Removes an instance from the federation allowlist or blocklist. Takes effect immediately in this
+/// process, and within a minute in other processes which share the database.
+#[async_trait::async_trait(?Send)]
+impl Perform for RemoveFederationListEntry {
+  type Response = FederationListResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &RemoveFederationListEntry = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let domain = parse_federation_list_domain(&data.domain)?;
+    let blocked = data.blocked;
+    let mod_form = ModFederationListForm {
+      mod_person_id: local_user_view.person.id,
+      domain,
+      blocked,
+      removed: true,
+      reason: data.reason.to_owned(),
+    };
+    // The modlog entry is only written if there was something to remove
+    let removed = blocking(context.pool(), move |conn| {
+      conn.transaction(|| {
+        let removed = FederationListEntry::remove(conn, &mod_form.domain, blocked)?;
+        if removed > 0 {
+          ModFederationList::create(conn, &mod_form)?;
+        }
+        Ok(removed)
+      })
+    })
+    .await?
+    .map_err(|e: diesel::result::Error| {
+      LemmyError::from_error_message(e, "couldnt_update_federation_list")
+    })?;
+    if removed == 0 {
+      return Err(LemmyError::from_message("federation_list_entry_not_found"));
+    }
+
+    refresh_federation_lists(context).await?;
+
+    let entries = blocking(context.pool(), FederationListEntry::list).await??;
+    Ok(Self::Response { entries })
+  }
+}
+
+/// Runs an incoming activity, or an object fetched from a url, through parsing and the checks of
+/// the inbox without applying it, and reports the result of each step.
+#[async_trait::async_trait(?Send)]
+impl Perform for DebugActivity {
+  type Response = DebugActivityResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &DebugActivity = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+
+    let url = data
+      .url
+      .as_ref()
+      .map(|url| Url::parse(url))
+      .transpose()
+      .map_err(|e| LemmyError::from_error_message(e, "invalid_url"))?;
+    let inbox_request = data.headers.to_owned().map(|headers| InboxRequest {
+      path: data
+        .inbox_path
+        .to_owned()
+        .unwrap_or_else(|| "/inbox".to_string()),
+      headers,
+    });
+    let steps = debug_activity(data.activity.to_owned(), url, inbox_request, context).await?;
+    let success = steps.iter().all(|s| s.success);
+    Ok(Self::Response { success, steps })
+  }
+}
+
+/// Federation lists contain plain domains like `example.com`, as used in activitypub ids.
+fn parse_federation_list_domain(domain: &str) -> Result<String, LemmyError> {
+  let domain = domain.trim().to_lowercase();
+  if domain.is_empty() || domain.contains(|c: char| c == '/' || c == ':' || c.is_whitespace()) {
+    return Err(LemmyError::from_message("invalid_domain"));
+  }
+  Ok(domain)
+}
+
+/// Replaces the keypairs of the instance actor and of all local communities, and sends the new
+/// public keys to other instances. Those keep accepting the previous keys for a grace period, so
+/// that activities which were signed before the rotation can still be delivered.
+#[async_trait::async_trait(?Send)]
+impl Perform for RotateKeys {
+  type Response = RotateKeysResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<Self::Response, LemmyError> {
+    let data: &RotateKeys = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    // Only let admins do this
+    is_admin(&local_user_view)?;
+    let admin: ApubPerson = local_user_view.person.into();
+
+    let revoke_previous_keys = data.revoke_previous_keys.unwrap_or(false);
+    let (site, communities) = blocking(context.pool(), move |conn| {
+      rotate_local_keys(conn, revoke_previous_keys)
+    })
+    .await?
+    .map_err(|e| e.with_message("couldnt_rotate_keys"))?;
+    let count = communities.len() as i64;
+    // Outgoing fetches are signed with the instance actor
+    init_authorized_fetch(context).await?;
+
+    // The new keys are stored already, so a failed send shouldnt hold back the other updates
+    for community in communities {
+      let community_id = community.id;
+      let community: ApubCommunity = community.into();
+      if let Err(e) =
+        UpdateCommunity::send_key_rotation(community, &admin, revoke_previous_keys, context).await
+      {
+        warn!(
+          "Failed to send new key of community {}: {}",
+          community_id, e
+        );
+      }
+    }
+    let site: ApubSite = site.into();
+    if let Err(e) = UpdateInstance::send(site.clone(), &site, revoke_previous_keys, context).await {
+      warn!("Failed to send new key of site: {}", e);
+    }
+
+    Ok(RotateKeysResponse { communities: count })
+  }
+}
+
+/// Stores new keypairs for the local site and all local communities in one transaction, so that a
+/// failure can't leave only some of them rotated. The returned actors have the new public keys but
+/// still the previous private keys, so that the updates are signed with keys which other instances
+/// know about.
+fn rotate_local_keys(
+  conn: &PgConnection,
+  revoke_previous_keys: bool,
+) -> Result<(Site, Vec<Community>), LemmyError> {
+  conn.transaction(|| {
+    let mut communities = Community::list_local(conn)?;
+    for community in &mut communities {
+      let keypair = generate_actor_keypair()?;
+      Community::update_keypair(
+        conn,
+        community.id,
+        keypair.private_key,
+        keypair.public_key.clone(),
+      )?;
+      if revoke_previous_keys {
+        Community::revoke_previous_key(conn, community.id)?;
+      }
+      community.public_key = keypair.public_key;
+    }
+
+    let mut site = Site::read_local_site(conn)?;
+    let keypair = generate_actor_keypair()?;
+    Site::update_keypair(
+      conn,
+      site.id,
+      keypair.private_key,
+      keypair.public_key.clone(),
+    )?;
+    if revoke_previous_keys {
+      Site::revoke_previous_key(conn, site.id)?;
+    }
+    site.public_key = keypair.public_key;
+    Ok((site, communities))
+  })
+}
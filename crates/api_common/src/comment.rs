@@ -1,5 +1,13 @@
-use lemmy_db_schema::newtypes::{CommentId, CommentReportId, CommunityId, LocalUserId, PostId};
-use lemmy_db_views::{comment_report_view::CommentReportView, comment_view::CommentView};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommentReportId, CommunityId, LocalUserId, PostId},
+  source::comment::CommentRevision,
+};
+use lemmy_db_views::{
+  comment_report_view::CommentReportView,
+  comment_view::CommentView,
+  post_view::PostView,
+};
+use lemmy_db_views_actor::community_view::CommunityView;
 use lemmy_utils::Sensitive;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +17,10 @@ pub struct CreateComment {
   pub post_id: PostId,
   pub parent_id: Option<CommentId>,
   pub form_id: Option<String>,
+  /// Shown instead of the comment until the reader expands it
+  pub content_warning: Option<String>,
+  /// Defaults to the language in the user settings
+  pub language: Option<String>,
   pub auth: Sensitive<String>,
 }
 
@@ -18,14 +30,52 @@ pub struct GetComment {
   pub auth: Option<Sensitive<String>>,
 }
 
+/// A comment with the thread around it, for links to a single comment
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetCommentContext {
+  pub id: CommentId,
+  /// How many levels of parent comments to include, defaults to 3
+  pub ancestor_levels: Option<i32>,
+  /// How many levels of replies to include, defaults to 3
+  pub max_depth: Option<i32>,
+  /// Sort of the replies, one of Hot, Top, New or Old
+  pub comment_sort: Option<String>,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetCommentContextResponse {
+  /// The parent comments from the top down, then the comment itself, followed by its replies
+  pub comments: Vec<CommentView>,
+  pub post_view: PostView,
+  pub community_view: CommunityView,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EditComment {
   pub content: String,
   pub comment_id: CommentId,
   pub form_id: Option<String>,
+  /// An empty string removes the content warning
+  pub content_warning: Option<String>,
+  /// An empty string removes the language
+  pub language: Option<String>,
   pub auth: Sensitive<String>,
 }
 
+/// Lists the previous versions of an edited comment. Unless the site makes edit history public,
+/// only moderators and admins can see them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListCommentRevisions {
+  pub comment_id: CommentId,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListCommentRevisionsResponse {
+  pub revisions: Vec<CommentRevision>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteComment {
   pub comment_id: CommentId,
@@ -69,6 +119,15 @@ pub struct CreateCommentLike {
   pub auth: Sensitive<String>,
 }
 
+/// Adds or removes an emoji reaction, which is independent from the vote.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCommentReaction {
+  pub comment_id: CommentId,
+  pub emoji: String,
+  pub react: bool,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetComments {
   pub type_: Option<String>,
@@ -77,6 +136,17 @@ pub struct GetComments {
   pub limit: Option<i64>,
   pub community_id: Option<CommunityId>,
   pub community_name: Option<String>,
+  pub post_id: Option<PostId>,
+  /// Only list the replies below this comment, to load more of a tree
+  pub parent_id: Option<CommentId>,
+  /// Returns a comment tree with this many levels instead of a flat list, at least 1. The page and
+  /// limit apply to the first level only. At most 1000 replies are returned below them, split
+  /// evenly between the threads.
+  pub max_depth: Option<i32>,
+  /// The maximum number of replies below each comment of a tree
+  pub children_limit: Option<i64>,
+  /// Sort of a comment tree, one of Hot, Top, New or Old
+  pub comment_sort: Option<String>,
   pub saved_only: Option<bool>,
   pub auth: Option<Sensitive<String>>,
 }
@@ -90,6 +90,8 @@ pub struct EditCommunity {
   pub icon: Option<String>,
   pub banner: Option<String>,
   pub nsfw: Option<bool>,
+  /// Previous actors of this community, which are allowed to move here
+  pub also_known_as: Option<Vec<String>>,
   pub auth: Sensitive<String>,
 }
 
@@ -143,3 +145,12 @@ pub struct TransferCommunity {
   pub person_id: PersonId,
   pub auth: Sensitive<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveCommunity {
+  pub community_id: CommunityId,
+  /// The new community, either as url or as name@instance.tld. It needs to list this community as
+  /// alias.
+  pub target: String,
+  pub auth: Sensitive<String>,
+}
@@ -12,6 +12,7 @@ use lemmy_db_schema::{
     comment::Comment,
     community::Community,
     email_verification::{EmailVerification, EmailVerificationForm},
+    federation_list::FederationListEntry,
     password_reset_request::PasswordResetRequest,
     person::Person,
     person_block::PersonBlock,
@@ -20,7 +21,7 @@ use lemmy_db_schema::{
     secret::Secret,
     site::Site,
   },
-  traits::{Crud, Readable},
+  traits::{Crud, Reactable, Readable},
   DbPool,
 };
 use lemmy_db_views::{
@@ -36,7 +37,7 @@ use lemmy_utils::{
   claims::Claims,
   email::{send_email, translations::Lang},
   settings::structs::Settings,
-  utils::generate_random_string,
+  utils::{generate_random_string, is_valid_language},
   LemmyError,
   Sensitive,
 };
@@ -237,11 +238,28 @@ pub async fn check_community_deleted_or_removed(
     .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
   if community.deleted || community.removed {
     Err(LemmyError::from_message("deleted"))
+  } else if community.moved_to.is_some() {
+    Err(LemmyError::from_message("community_moved"))
   } else {
     Ok(())
   }
 }
 
+/// A person can react to the same post or comment with several different emojis, up to this many
+pub const MAX_REACTIONS_PER_PERSON: i64 = 10;
+
+/// Returns true if the person of the form can't add another emoji to the post or comment. Reacting
+/// again with an emoji which they already used doesn't count.
+#[tracing::instrument(skip_all)]
+pub async fn reaction_limit_reached<R>(form: R::Form, pool: &DbPool) -> Result<bool, LemmyError>
+where
+  R: Reactable + 'static,
+  R::Form: Send,
+{
+  let count = blocking(pool, move |conn| R::count_other_reactions(conn, &form)).await??;
+  Ok(count >= MAX_REACTIONS_PER_PERSON)
+}
+
 pub fn check_post_deleted_or_removed(post: &Post) -> Result<(), LemmyError> {
   if post.deleted || post.removed {
     Err(LemmyError::from_message("deleted"))
@@ -293,6 +311,49 @@ pub async fn check_private_instance(
   Ok(())
 }
 
+/// Checks that the edit history of a post or comment in the given community can be seen. Unless
+/// the site makes edit history public, only mods and admins can see it. The history of deleted
+/// or removed content is always limited to them.
+#[tracing::instrument(skip_all)]
+pub async fn check_edit_history_visible(
+  local_user_view: &Option<LocalUserView>,
+  community_id: CommunityId,
+  deleted_or_removed: bool,
+  pool: &DbPool,
+) -> Result<(), LemmyError> {
+  let public_edit_history = blocking(pool, Site::read_local_site)
+    .await?
+    .map(|site| site.public_edit_history)
+    .unwrap_or(false);
+
+  // Only look up the moderators if the history isn't visible to everyone anyway
+  let is_mod_or_admin = match local_user_view {
+    Some(local_user_view) if !public_edit_history || deleted_or_removed => {
+      let person_id = local_user_view.person.id;
+      blocking(pool, move |conn| {
+        CommunityView::is_mod_or_admin(conn, person_id, community_id)
+      })
+      .await?
+    }
+    _ => false,
+  };
+  check_edit_history_access(public_edit_history, deleted_or_removed, is_mod_or_admin)
+}
+
+/// The decision of `check_edit_history_visible`, once the site setting and the moderators are
+/// known.
+pub fn check_edit_history_access(
+  public_edit_history: bool,
+  deleted_or_removed: bool,
+  is_mod_or_admin: bool,
+) -> Result<(), LemmyError> {
+  if (public_edit_history && !deleted_or_removed) || is_mod_or_admin {
+    Ok(())
+  } else {
+    Err(LemmyError::from_message("not_a_mod_or_admin"))
+  }
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn build_federated_instances(
   pool: &DbPool,
@@ -302,13 +363,18 @@ pub async fn build_federated_instances(
   let hostname = &settings.hostname;
   let federation = federation_config.to_owned();
   if federation.enabled {
-    let distinct_communities = blocking(pool, move |conn| {
-      Community::distinct_federated_communities(conn)
+    let (distinct_communities, allowed, blocked) = blocking(pool, move |conn| {
+      Ok((
+        Community::distinct_federated_communities(conn)?,
+        FederationListEntry::list_domains(conn, false)?,
+        FederationListEntry::list_domains(conn, true)?,
+      )) as Result<_, diesel::result::Error>
     })
     .await??;
 
-    let allowed = federation.allowed_instances;
-    let blocked = federation.blocked_instances;
+    // An empty list means that it isn't used
+    let allowed = Some(allowed).filter(|a| !a.is_empty());
+    let blocked = Some(blocked).filter(|b| !b.is_empty());
 
     let mut linked = distinct_communities
       .iter()
@@ -364,6 +430,15 @@ pub fn honeypot_check(honeypot: &Option<String>) -> Result<(), LemmyError> {
   }
 }
 
+/// Checks that a language tag is valid, eg `en` or `pt-BR`
+pub fn language_check(language: &str) -> Result<(), LemmyError> {
+  if !is_valid_language(language) {
+    Err(LemmyError::from_message("invalid_language"))
+  } else {
+    Ok(())
+  }
+}
+
 pub fn send_email_to_user(
   local_user_view: &LocalUserView,
   subject: &str,
@@ -599,9 +674,11 @@ pub async fn delete_user_account(person_id: PersonId, pool: &DbPool) -> Result<(
   Ok(())
 }
 
-pub fn check_image_has_local_domain(url: &Option<DbUrl>) -> Result<(), LemmyError> {
+pub fn check_image_has_local_domain(
+  url: &Option<DbUrl>,
+  settings: &Settings,
+) -> Result<(), LemmyError> {
   if let Some(url) = url {
-    let settings = Settings::get();
     let domain = url.domain().expect("url has domain");
     if domain != settings.hostname {
       return Err(LemmyError::from_message("image_not_local"));
@@ -68,6 +68,12 @@ pub struct SaveUserSettings {
   pub show_bot_accounts: Option<bool>,
   pub show_read_posts: Option<bool>,
   pub show_new_post_notifs: Option<bool>,
+  /// Urls of your other accounts, which are then allowed to move to this account
+  pub also_known_as: Option<Vec<String>>,
+  /// Language for new posts and comments, an empty string removes it
+  pub default_language: Option<String>,
+  /// Only show posts and comments in these languages, an empty list shows all
+  pub discussion_languages: Option<Vec<String>>,
   pub auth: Sensitive<String>,
 }
 
@@ -106,6 +112,8 @@ pub struct GetPersonDetailsResponse {
   pub comments: Vec<CommentView>,
   pub posts: Vec<PostView>,
   pub moderates: Vec<CommunityModeratorView>,
+  /// The account which this person moved to, if any
+  pub moved_to: Option<PersonViewSafe>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -306,3 +314,16 @@ pub struct VerifyEmail {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VerifyEmailResponse {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoveAccount {
+  /// The new account, either as url or as name@instance.tld. It needs to list this account as
+  /// alias.
+  pub target: String,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MoveAccountResponse {
+  pub person_view: PersonViewSafe,
+}
@@ -1,4 +1,7 @@
-use lemmy_db_schema::newtypes::{CommunityId, PostId, PostReportId};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PollOptionId, PostId, PostReportId},
+  source::post::PostRevision,
+};
 use lemmy_db_views::{
   comment_view::CommentView,
   post_report_view::PostReportView,
@@ -20,6 +23,29 @@ pub struct CreatePost {
   pub body: Option<String>,
   pub honeypot: Option<String>,
   pub nsfw: Option<bool>,
+  /// Shown instead of the post body until the reader expands it
+  pub content_warning: Option<String>,
+  /// Defaults to the language in the user settings
+  pub language: Option<String>,
+  pub poll: Option<CreatePoll>,
+  /// Unix timestamp in the future, until which the post stays hidden
+  pub scheduled_publish_time: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreatePoll {
+  pub options: Vec<String>,
+  pub multiple_choice: Option<bool>,
+  /// Unix timestamp after which no more votes are accepted
+  pub end_time: Option<i64>,
+}
+
+/// Creates a copy of a post in another community, which links back to the original
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCrosspost {
+  pub post_id: PostId,
+  pub community_id: CommunityId,
   pub auth: Sensitive<String>,
 }
 
@@ -31,6 +57,8 @@ pub struct PostResponse {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetPost {
   pub id: PostId,
+  /// Sort of the comment tree, one of Hot, Top, New or Old
+  pub comment_sort: Option<String>,
   pub auth: Option<Sensitive<String>>,
 }
 
@@ -38,8 +66,17 @@ pub struct GetPost {
 pub struct GetPostResponse {
   pub post_view: PostView,
   pub community_view: CommunityView,
+  /// The first page of the comment tree: up to 50 top level comments, each followed by its replies
+  /// up to 8 levels deep, in depth first order. Each comment has at most 10 replies listed, and
+  /// there are at most 1000 replies in total. Before comment trees, this was a flat list of all
+  /// comments of the post. Clients should compare the replies with the `child_count` of each
+  /// comment, and load missing ones with `GetComments` using `parent_id`, or the next top level
+  /// comments with `post_id` and `page`.
   pub comments: Vec<CommentView>,
   pub moderators: Vec<CommunityModeratorView>,
+  /// Other posts with the same url, up to 50 of the newest ones. All of them can be listed with a
+  /// `Search` of type `Url`.
+  pub cross_posts: Vec<PostView>,
   pub online: usize,
 }
 
@@ -60,6 +97,25 @@ pub struct GetPostsResponse {
   pub posts: Vec<PostView>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListScheduledPosts {
+  pub page: Option<i64>,
+  pub limit: Option<i64>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListScheduledPostsResponse {
+  pub posts: Vec<PostView>,
+}
+
+/// Deletes a scheduled post permanently, before it was published
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelScheduledPost {
+  pub post_id: PostId,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreatePostLike {
   pub post_id: PostId,
@@ -67,6 +123,22 @@ pub struct CreatePostLike {
   pub auth: Sensitive<String>,
 }
 
+/// Adds or removes an emoji reaction, which is independent from the vote.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatePostReaction {
+  pub post_id: PostId,
+  pub emoji: String,
+  pub react: bool,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatePollVote {
+  pub post_id: PostId,
+  pub option_ids: Vec<PollOptionId>,
+  pub auth: Sensitive<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EditPost {
   pub post_id: PostId,
@@ -74,9 +146,28 @@ pub struct EditPost {
   pub url: Option<Url>,
   pub body: Option<String>,
   pub nsfw: Option<bool>,
+  /// An empty string removes the content warning
+  pub content_warning: Option<String>,
+  /// An empty string removes the language
+  pub language: Option<String>,
+  /// Only allowed for posts which are still scheduled
+  pub scheduled_publish_time: Option<i64>,
   pub auth: Sensitive<String>,
 }
 
+/// Lists the previous versions of an edited post. Unless the site makes edit history public,
+/// only moderators and admins can see them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListPostRevisions {
+  pub post_id: PostId,
+  pub auth: Option<Sensitive<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListPostRevisionsResponse {
+  pub revisions: Vec<PostRevision>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeletePost {
   pub post_id: PostId,
@@ -1,9 +1,17 @@
-use lemmy_db_schema::newtypes::{CommunityId, PersonId};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId},
+  source::{
+    federation_list::FederationListEntry,
+    inbox_queue::InboxQueueStats,
+    instance_delivery::InstanceDelivery,
+  },
+};
 use lemmy_db_views::{
   comment_view::CommentView,
   local_user_view::LocalUserSettingsView,
   post_view::PostView,
   registration_application_view::RegistrationApplicationView,
+  search_snippet::{CommentSnippet, PostSnippet},
   site_view::SiteView,
 };
 use lemmy_db_views_actor::{
@@ -19,6 +27,7 @@ use lemmy_db_views_moderator::{
   mod_add_view::ModAddView,
   mod_ban_from_community_view::ModBanFromCommunityView,
   mod_ban_view::ModBanView,
+  mod_federation_list_view::ModFederationListView,
   mod_hide_community_view::ModHideCommunityView,
   mod_lock_post_view::ModLockPostView,
   mod_remove_comment_view::ModRemoveCommentView,
@@ -29,6 +38,7 @@ use lemmy_db_views_moderator::{
 };
 use lemmy_utils::Sensitive;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Search {
@@ -39,6 +49,8 @@ pub struct Search {
   pub type_: Option<String>,
   pub sort: Option<String>,
   pub listing_type: Option<String>,
+  /// Language of the search terms, defaults to the user's default language
+  pub language: Option<String>,
   pub page: Option<i64>,
   pub limit: Option<i64>,
   pub auth: Option<Sensitive<String>>,
@@ -51,6 +63,8 @@ pub struct SearchResponse {
   pub posts: Vec<PostView>,
   pub communities: Vec<CommunityView>,
   pub users: Vec<PersonViewSafe>,
+  pub post_snippets: Vec<PostSnippet>,
+  pub comment_snippets: Vec<CommentSnippet>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -89,6 +103,7 @@ pub struct GetModlogResponse {
   pub transferred_to_community: Vec<ModTransferCommunityView>,
   pub added: Vec<ModAddView>,
   pub hidden_communities: Vec<ModHideCommunityView>,
+  pub federation_list: Vec<ModFederationListView>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -107,6 +122,8 @@ pub struct CreateSite {
   pub application_question: Option<String>,
   pub private_instance: Option<bool>,
   pub default_theme: Option<String>,
+  pub public_edit_history: Option<bool>,
+  pub federation_strict_allowlist: Option<bool>,
   pub auth: Sensitive<String>,
 }
 
@@ -126,6 +143,8 @@ pub struct EditSite {
   pub application_question: Option<String>,
   pub private_instance: Option<bool>,
   pub default_theme: Option<String>,
+  pub public_edit_history: Option<bool>,
+  pub federation_strict_allowlist: Option<bool>,
   pub auth: Sensitive<String>,
 }
 
@@ -222,3 +241,116 @@ pub struct GetUnreadRegistrationApplicationCount {
 pub struct GetUnreadRegistrationApplicationCountResponse {
   pub registration_applications: i64,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetActivityQueueStats {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetActivityQueueStatsResponse {
+  /// Deliveries waiting to be sent, including those waiting for a retry
+  pub pending: usize,
+  pub running: usize,
+  /// Deliveries which failed for good after exhausting all retries
+  pub dead: ActivityQueueJobCounts,
+  pub complete: ActivityQueueJobCounts,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ActivityQueueJobCounts {
+  pub this_hour: usize,
+  pub today: usize,
+  pub this_month: usize,
+  pub all_time: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListInstanceDeliveries {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListInstanceDeliveriesResponse {
+  pub instances: Vec<InstanceDelivery>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetInboxQueueStats {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetInboxQueueStatsResponse {
+  pub inbox_queue: InboxQueueStats,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetFederationList {
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddFederationListEntry {
+  pub domain: String,
+  /// Add to the blocklist if true, otherwise to the allowlist
+  pub blocked: bool,
+  pub reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveFederationListEntry {
+  pub domain: String,
+  pub blocked: bool,
+  pub reason: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FederationListResponse {
+  pub entries: Vec<FederationListEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebugActivity {
+  /// Raw json of an activity or object
+  pub activity: Option<String>,
+  /// Alternatively, the url of an activity or object to fetch
+  pub url: Option<String>,
+  /// Headers of the inbox request which delivered `activity`, to check its signature
+  pub headers: Option<HashMap<String, String>>,
+  /// Path of that inbox request, defaults to the shared inbox
+  pub inbox_path: Option<String>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DebugActivityResponse {
+  /// True if all steps succeeded, meaning the activity would be accepted
+  pub success: bool,
+  /// The steps which were run, ending with the first one which failed
+  pub steps: Vec<DebugActivityStep>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DebugActivityStep {
+  pub step: String,
+  pub success: bool,
+  /// The error if the step failed, or additional info
+  pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RotateKeys {
+  /// Stop accepting the previous keys right away instead of after the grace period, eg because they
+  /// were leaked. Activities which are still queued with the previous keys will then be rejected.
+  pub revoke_previous_keys: Option<bool>,
+  pub auth: Sensitive<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RotateKeysResponse {
+  /// Number of local communities which got a new keypair, in addition to the site
+  pub communities: i64,
+}
@@ -8,6 +8,7 @@ use lemmy_api_common::{
   comment::*,
   get_local_user_view_from_jwt,
   get_post,
+  language_check,
 };
 use lemmy_apub::{
   generate_local_apub_endpoint,
@@ -24,7 +25,7 @@ use lemmy_db_schema::{
 };
 use lemmy_db_views::comment_view::CommentView;
 use lemmy_utils::{
-  utils::{remove_slurs, scrape_text_for_mentions},
+  utils::{clean_optional_text, remove_slurs, scrape_text_for_mentions},
   ConnectionId,
   LemmyError,
 };
@@ -48,8 +49,14 @@ impl PerformCrud for CreateComment {
     let local_user_view =
       get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
 
-    let content_slurs_removed =
-      remove_slurs(&data.content.to_owned(), &context.settings().slur_regex());
+    let slur_regex = &context.settings().slur_regex();
+    let content_slurs_removed = remove_slurs(&data.content.to_owned(), slur_regex);
+    let content_warning =
+      clean_optional_text(&data.content_warning).map(|c| remove_slurs(&c, slur_regex));
+
+    if let Some(language) = &data.language {
+      language_check(language)?;
+    }
 
     // Check for a community ban
     let post_id = data.post_id;
@@ -60,6 +67,11 @@ impl PerformCrud for CreateComment {
     check_community_deleted_or_removed(community_id, context.pool()).await?;
     check_post_deleted_or_removed(&post)?;
 
+    // Scheduled posts cant be commented before they are published
+    if post.scheduled_publish_time.is_some() {
+      return Err(LemmyError::from_message("couldnt_find_post"));
+    }
+
     // Check if post is locked, no new comments
     if post.locked {
       return Err(LemmyError::from_message("locked"));
@@ -83,6 +95,13 @@ impl PerformCrud for CreateComment {
       parent_id: data.parent_id.to_owned(),
       post_id: data.post_id,
       creator_id: local_user_view.person.id,
+      content_warning: Some(content_warning),
+      language: Some(
+        data
+          .language
+          .to_owned()
+          .or_else(|| local_user_view.local_user.default_language.to_owned()),
+      ),
       ..CommentForm::default()
     };
 
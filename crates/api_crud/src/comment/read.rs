@@ -11,13 +11,21 @@ use lemmy_db_schema::{
   from_opt_str_to_opt_enum,
   source::community::Community,
   traits::DeleteableOrRemoveable,
+  CommentSortType,
   ListingType,
   SortType,
 };
-use lemmy_db_views::comment_view::{CommentQueryBuilder, CommentView};
+use lemmy_db_views::{
+  comment_view::{CommentQueryBuilder, CommentView},
+  post_view::PostView,
+};
+use lemmy_db_views_actor::community_view::CommunityView;
 use lemmy_utils::{ConnectionId, LemmyError};
 use lemmy_websocket::LemmyContext;
 
+/// The deepest comment tree which can be fetched at once
+const MAX_COMMENT_DEPTH: i32 = 20;
+
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for GetComment {
   type Response = CommentResponse;
@@ -51,6 +59,102 @@ impl PerformCrud for GetComment {
   }
 }
 
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for GetCommentContext {
+  type Response = GetCommentContextResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<GetCommentContextResponse, LemmyError> {
+    let data: &GetCommentContext = self;
+    let ancestor_levels = data.ancestor_levels.unwrap_or(3).max(0);
+    let max_depth = data.max_depth.unwrap_or(3);
+    if ancestor_levels > MAX_COMMENT_DEPTH || max_depth > MAX_COMMENT_DEPTH {
+      return Err(LemmyError::from_message("limit_too_high"));
+    }
+
+    let local_user_view =
+      get_local_user_view_from_jwt_opt(data.auth.as_ref(), context.pool(), context.secret())
+        .await?;
+
+    check_private_instance(&local_user_view, context.pool()).await?;
+
+    let show_bot_accounts = local_user_view
+      .as_ref()
+      .map(|t| t.local_user.show_bot_accounts);
+    let person_id = local_user_view.map(|u| u.person.id);
+
+    let id = data.id;
+    let comment_view = blocking(context.pool(), move |conn| {
+      CommentView::read(conn, id, person_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_comment"))?;
+
+    let ancestor_ids = comment_view.comment.ancestor_ids(ancestor_levels as usize);
+    let comment_sort = from_opt_str_to_opt_enum(&data.comment_sort).unwrap_or(CommentSortType::Hot);
+    let (ancestors, replies) = blocking(context.pool(), move |conn| -> Result<_, LemmyError> {
+      let ancestors = if ancestor_ids.is_empty() {
+        Vec::new()
+      } else {
+        CommentQueryBuilder::create(conn)
+          .my_person_id(person_id)
+          .comment_ids(ancestor_ids)
+          .list()?
+      };
+      let replies = if max_depth > 0 {
+        CommentQueryBuilder::create(conn)
+          .my_person_id(person_id)
+          .show_bot_accounts(show_bot_accounts)
+          .parent_id(id)
+          .comment_sort(comment_sort)
+          .max_depth(max_depth)
+          .list()?
+      } else {
+        Vec::new()
+      };
+      Ok((ancestors, replies))
+    })
+    .await?
+    .map_err(|e| e.with_message("couldnt_get_comments"))?;
+
+    let post_id = comment_view.post.id;
+    let post_view = blocking(context.pool(), move |conn| {
+      PostView::read(conn, post_id, person_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_post"))?;
+
+    let community_id = post_view.community.id;
+    let community_view = blocking(context.pool(), move |conn| {
+      CommunityView::read(conn, community_id, person_id)
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_community"))?;
+
+    let mut comments = ancestors;
+    comments.push(comment_view);
+    comments.extend(replies);
+
+    // Blank out deleted or removed info
+    for cv in comments
+      .iter_mut()
+      .filter(|cv| cv.comment.deleted || cv.comment.removed)
+    {
+      cv.comment = cv.to_owned().comment.blank_out_deleted_or_removed_info();
+    }
+
+    Ok(GetCommentContextResponse {
+      comments,
+      post_view,
+      community_view,
+    })
+  }
+}
+
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for GetComments {
   type Response = GetCommentsResponse;
@@ -63,6 +167,15 @@ impl PerformCrud for GetComments {
   ) -> Result<GetCommentsResponse, LemmyError> {
     let data: &GetComments = self;
 
+    if let Some(max_depth) = data.max_depth {
+      if max_depth < 1 {
+        return Err(LemmyError::from_message("invalid_max_depth"));
+      }
+      if max_depth > MAX_COMMENT_DEPTH {
+        return Err(LemmyError::from_message("limit_too_high"));
+      }
+    }
+
     // Check to make sure a community_id or community_name is given
     // if data.community_name.is_none() && data.community_id.is_none() {
     //   return Err(LemmyError::from_message("no_id_given"));
@@ -77,9 +190,13 @@ impl PerformCrud for GetComments {
     let show_bot_accounts = local_user_view
       .as_ref()
       .map(|t| t.local_user.show_bot_accounts);
+    let discussion_languages = local_user_view
+      .as_ref()
+      .map(|t| t.local_user.discussion_languages.to_owned());
     let person_id = local_user_view.map(|u| u.person.id);
 
     let sort: Option<SortType> = from_opt_str_to_opt_enum(&data.sort);
+    let comment_sort: Option<CommentSortType> = from_opt_str_to_opt_enum(&data.comment_sort);
     let listing_type: Option<ListingType> = from_opt_str_to_opt_enum(&data.type_);
 
     let community_id = data.community_id;
@@ -92,17 +209,27 @@ impl PerformCrud for GetComments {
       None
     };
     let saved_only = data.saved_only;
+    let post_id = data.post_id;
+    let parent_id = data.parent_id;
+    let max_depth = data.max_depth;
+    let children_limit = data.children_limit;
     let page = data.page;
     let limit = data.limit;
     let mut comments = blocking(context.pool(), move |conn| {
       CommentQueryBuilder::create(conn)
         .listing_type(listing_type)
         .sort(sort)
+        .comment_sort(comment_sort)
         .saved_only(saved_only)
         .community_id(community_id)
         .community_actor_id(community_actor_id)
+        .post_id(post_id)
+        .parent_id(parent_id)
+        .max_depth(max_depth)
+        .children_limit(children_limit)
         .my_person_id(person_id)
         .show_bot_accounts(show_bot_accounts)
+        .discussion_languages(discussion_languages)
         .page(page)
         .limit(limit)
         .list()
@@ -7,12 +7,13 @@ use lemmy_api_common::{
   check_post_deleted_or_removed,
   comment::*,
   get_local_user_view_from_jwt,
+  language_check,
 };
 use lemmy_apub::protocol::activities::{
   create_or_update::comment::CreateOrUpdateComment,
   CreateOrUpdateType,
 };
-use lemmy_db_schema::source::comment::Comment;
+use lemmy_db_schema::{diesel_option_overwrite, source::comment::Comment};
 use lemmy_db_views::comment_view::CommentView;
 use lemmy_utils::{
   utils::{remove_slurs, scrape_text_for_mentions},
@@ -62,11 +63,24 @@ impl PerformCrud for EditComment {
       return Err(LemmyError::from_message("no_comment_edit_allowed"));
     }
 
+    let language = diesel_option_overwrite(&data.language);
+    if let Some(Some(language)) = &language {
+      language_check(language)?;
+    }
+
     // Do the update
-    let content_slurs_removed =
-      remove_slurs(&data.content.to_owned(), &context.settings().slur_regex());
+    let slur_regex = &context.settings().slur_regex();
+    let content_slurs_removed = remove_slurs(&data.content.to_owned(), slur_regex);
+    let content_warning = diesel_option_overwrite(&data.content_warning)
+      .map(|c| c.map(|c| remove_slurs(&c, slur_regex)));
     let comment_id = data.comment_id;
     let updated_comment = blocking(context.pool(), move |conn| {
+      if let Some(content_warning) = content_warning {
+        Comment::update_content_warning(conn, comment_id, content_warning)?;
+      }
+      if let Some(language) = language {
+        Comment::update_language(conn, comment_id, language)?;
+      }
       Comment::update_content(conn, comment_id, &content_slurs_removed)
     })
     .await?
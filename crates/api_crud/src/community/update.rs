@@ -11,7 +11,7 @@ use lemmy_apub::protocol::activities::community::update::UpdateCommunity;
 use lemmy_db_schema::{
   diesel_option_overwrite_to_url,
   naive_now,
-  newtypes::PersonId,
+  newtypes::{DbUrl, PersonId},
   source::{
     community::{Community, CommunityForm},
     moderator::{ModHideCommunity, ModHideCommunityForm},
@@ -21,6 +21,7 @@ use lemmy_db_schema::{
 use lemmy_db_views_actor::community_moderator_view::CommunityModeratorView;
 use lemmy_utils::{utils::check_slurs_opt, ConnectionId, LemmyError};
 use lemmy_websocket::{send::send_community_ws_message, LemmyContext, UserOperationCrud};
+use url::Url;
 
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for EditCommunity {
@@ -38,11 +39,22 @@ impl PerformCrud for EditCommunity {
 
     let icon = diesel_option_overwrite_to_url(&data.icon)?;
     let banner = diesel_option_overwrite_to_url(&data.banner)?;
+    let also_known_as = data
+      .also_known_as
+      .as_ref()
+      .map(|aliases| {
+        aliases
+          .iter()
+          .map(|a| Url::parse(a.trim()).map(Into::into))
+          .collect::<Result<Vec<DbUrl>, _>>()
+      })
+      .transpose()
+      .map_err(|e| LemmyError::from_error_message(e, "invalid_url"))?;
 
     check_slurs_opt(&data.title, &context.settings().slur_regex())?;
     check_slurs_opt(&data.description, &context.settings().slur_regex())?;
-    check_image_has_local_domain(icon.as_ref().unwrap_or(&None))?;
-    check_image_has_local_domain(banner.as_ref().unwrap_or(&None))?;
+    check_image_has_local_domain(icon.as_ref().unwrap_or(&None), &context.settings())?;
+    check_image_has_local_domain(banner.as_ref().unwrap_or(&None), &context.settings())?;
 
     // Verify its a mod (only mods can edit it)
     let community_id = data.community_id;
@@ -71,6 +83,7 @@ impl PerformCrud for EditCommunity {
       nsfw: data.nsfw,
       hidden: Some(read_community.hidden),
       updated: Some(naive_now()),
+      also_known_as,
       ..CommunityForm::default()
     };
 
@@ -85,6 +85,9 @@ pub async fn match_websocket_operation_crud(
     UserOperationCrud::CreatePost => {
       do_websocket_operation::<CreatePost>(context, id, op, data).await
     }
+    UserOperationCrud::CreateCrosspost => {
+      do_websocket_operation::<CreateCrosspost>(context, id, op, data).await
+    }
     UserOperationCrud::GetPost => do_websocket_operation::<GetPost>(context, id, op, data).await,
     UserOperationCrud::GetPosts => do_websocket_operation::<GetPosts>(context, id, op, data).await,
     UserOperationCrud::EditPost => do_websocket_operation::<EditPost>(context, id, op, data).await,
@@ -94,6 +97,12 @@ pub async fn match_websocket_operation_crud(
     UserOperationCrud::RemovePost => {
       do_websocket_operation::<RemovePost>(context, id, op, data).await
     }
+    UserOperationCrud::ListScheduledPosts => {
+      do_websocket_operation::<ListScheduledPosts>(context, id, op, data).await
+    }
+    UserOperationCrud::CancelScheduledPost => {
+      do_websocket_operation::<CancelScheduledPost>(context, id, op, data).await
+    }
 
     // Comment ops
     UserOperationCrud::CreateComment => {
@@ -111,6 +120,9 @@ pub async fn match_websocket_operation_crud(
     UserOperationCrud::GetComment => {
       do_websocket_operation::<GetComment>(context, id, op, data).await
     }
+    UserOperationCrud::GetCommentContext => {
+      do_websocket_operation::<GetCommentContext>(context, id, op, data).await
+    }
     UserOperationCrud::GetComments => {
       do_websocket_operation::<GetComments>(context, id, op, data).await
     }
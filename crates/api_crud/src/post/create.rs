@@ -1,11 +1,15 @@
 use crate::PerformCrud;
 use actix_web::web::Data;
+use diesel::Connection;
 use lemmy_api_common::{
   blocking,
   check_community_ban,
   check_community_deleted_or_removed,
+  check_post_deleted_or_removed,
   get_local_user_view_from_jwt,
+  get_post,
   honeypot_check,
+  language_check,
   mark_post_as_read,
   post::*,
 };
@@ -16,9 +20,14 @@ use lemmy_apub::{
   EndpointType,
 };
 use lemmy_db_schema::{
-  source::post::{Post, PostForm, PostLike, PostLikeForm},
+  naive_now,
+  source::{
+    poll::{Poll, PollForm, PollOption, PollOptionForm},
+    post::{Post, PostForm, PostLike, PostLikeForm},
+  },
   traits::{Crud, Likeable},
 };
+use lemmy_db_views::post_view::PostView;
 use lemmy_utils::{
   request::fetch_site_data,
   utils::{
@@ -26,7 +35,9 @@ use lemmy_utils::{
     check_slurs_opt,
     clean_optional_text,
     clean_url_params,
+    is_valid_poll_option,
     is_valid_post_title,
+    naive_from_unix,
   },
   ConnectionId,
   LemmyError,
@@ -53,12 +64,30 @@ impl PerformCrud for CreatePost {
     let slur_regex = &context.settings().slur_regex();
     check_slurs(&data.name, slur_regex)?;
     check_slurs_opt(&data.body, slur_regex)?;
+    check_slurs_opt(&data.content_warning, slur_regex)?;
     honeypot_check(&data.honeypot)?;
+    if let Some(language) = &data.language {
+      language_check(language)?;
+    }
 
     if !is_valid_post_title(&data.name) {
       return Err(LemmyError::from_message("invalid_post_title"));
     }
 
+    if let Some(poll) = &data.poll {
+      check_poll(poll)?;
+      for option in &poll.options {
+        check_slurs(option, slur_regex)?;
+      }
+    }
+
+    let scheduled_publish_time = data.scheduled_publish_time.map(naive_from_unix);
+    if let Some(scheduled_publish_time) = scheduled_publish_time {
+      if scheduled_publish_time <= naive_now() {
+        return Err(LemmyError::from_message("invalid_scheduled_publish_time"));
+      }
+    }
+
     check_community_ban(local_user_view.person.id, data.community_id, context.pool()).await?;
     check_community_deleted_or_removed(data.community_id, context.pool()).await?;
 
@@ -77,43 +106,79 @@ impl PerformCrud for CreatePost {
       community_id: data.community_id,
       creator_id: local_user_view.person.id,
       nsfw: data.nsfw,
+      content_warning: Some(clean_optional_text(&data.content_warning)),
+      language: Some(
+        data
+          .language
+          .to_owned()
+          .or_else(|| local_user_view.local_user.default_language.to_owned()),
+      ),
       embed_title,
       embed_description,
       embed_html,
       thumbnail_url: pictrs_thumbnail.map(|u| u.into()),
+      published: scheduled_publish_time,
+      scheduled_publish_time: Some(scheduled_publish_time),
       ..PostForm::default()
     };
 
-    let inserted_post =
-      match blocking(context.pool(), move |conn| Post::create(conn, &post_form)).await? {
-        Ok(post) => post,
-        Err(e) => {
+    let poll = data.poll.as_ref().map(|poll| {
+      let options: Vec<String> = poll.options.iter().map(|o| o.trim().to_owned()).collect();
+      (
+        poll.multiple_choice,
+        poll.end_time.map(naive_from_unix),
+        options,
+      )
+    });
+    let protocol_and_hostname = context.settings().get_protocol_and_hostname();
+    // The post and its poll are created together, so that a failure can't leave a poll post without
+    // options
+    let updated_post = blocking(context.pool(), move |conn| {
+      conn.transaction::<_, LemmyError, _>(|| {
+        let inserted_post = Post::create(conn, &post_form).map_err(|e| {
           let err_type = if e.to_string() == "value too long for type character varying(200)" {
             "post_title_too_long"
           } else {
             "couldnt_create_post"
           };
+          LemmyError::from_error_message(e, err_type)
+        })?;
 
-          return Err(LemmyError::from_error_message(e, err_type));
-        }
-      };
+        let inserted_post_id = inserted_post.id;
+        let apub_id = generate_local_apub_endpoint(
+          EndpointType::Post,
+          &inserted_post_id.to_string(),
+          &protocol_and_hostname,
+        )?;
+        let updated_post = Post::update_ap_id(conn, inserted_post_id, apub_id)
+          .map_err(|e| LemmyError::from_error_message(e, "couldnt_create_post"))?;
 
-    let inserted_post_id = inserted_post.id;
-    let protocol_and_hostname = context.settings().get_protocol_and_hostname();
-    let updated_post = blocking(context.pool(), move |conn| -> Result<Post, LemmyError> {
-      let apub_id = generate_local_apub_endpoint(
-        EndpointType::Post,
-        &inserted_post_id.to_string(),
-        &protocol_and_hostname,
-      )?;
-      Ok(Post::update_ap_id(conn, inserted_post_id, apub_id)?)
+        if let Some((multiple_choice, end_time, options)) = poll {
+          let poll_form = PollForm {
+            post_id: inserted_post_id,
+            multiple_choice,
+            end_time: Some(end_time),
+          };
+          let poll = Poll::upsert(conn, &poll_form)
+            .map_err(|e| LemmyError::from_error_message(e, "couldnt_create_poll"))?;
+          for name in options {
+            let option_form = PollOptionForm {
+              poll_id: poll.id,
+              name,
+              vote_count: None,
+            };
+            PollOption::upsert(conn, &option_form)
+              .map_err(|e| LemmyError::from_error_message(e, "couldnt_create_poll"))?;
+          }
+        }
+        Ok(updated_post)
+      })
     })
-    .await?
-    .map_err(|e| e.with_message("couldnt_create_post"))?;
+    .await??;
 
     // They like their own post by default
     let person_id = local_user_view.person.id;
-    let post_id = inserted_post.id;
+    let post_id = updated_post.id;
     let like_form = PostLikeForm {
       post_id,
       person_id,
@@ -128,6 +193,15 @@ impl PerformCrud for CreatePost {
     // Mark the post as read
     mark_post_as_read(person_id, post_id, context.pool()).await?;
 
+    // Scheduled posts are federated and sent over websocket once they get published
+    if updated_post.scheduled_publish_time.is_some() {
+      let post_view = blocking(context.pool(), move |conn| {
+        PostView::read(conn, post_id, Some(person_id))
+      })
+      .await??;
+      return Ok(PostResponse { post_view });
+    }
+
     if let Some(url) = &updated_post.url {
       let mut webmention =
         Webmention::new::<Url>(updated_post.ap_id.clone().into(), url.clone().into())?;
@@ -153,7 +227,7 @@ impl PerformCrud for CreatePost {
     .await?;
 
     send_post_ws_message(
-      inserted_post.id,
+      post_id,
       UserOperationCrud::CreatePost,
       websocket_id,
       Some(local_user_view.person.id),
@@ -162,3 +236,69 @@ impl PerformCrud for CreatePost {
     .await
   }
 }
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for CreateCrosspost {
+  type Response = PostResponse;
+
+  #[tracing::instrument(skip(context, websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    websocket_id: Option<ConnectionId>,
+  ) -> Result<PostResponse, LemmyError> {
+    let data: &CreateCrosspost = self;
+    let orig_post = get_post(data.post_id, context.pool()).await?;
+    check_post_deleted_or_removed(&orig_post)?;
+    if orig_post.scheduled_publish_time.is_some() {
+      return Err(LemmyError::from_message("couldnt_find_post"));
+    }
+    if orig_post.community_id == data.community_id {
+      return Err(LemmyError::from_message("crosspost_to_same_community"));
+    }
+
+    let body = match &orig_post.body {
+      Some(body) => format!("cross-posted from: {}\n\n{}", orig_post.ap_id, body),
+      None => format!("cross-posted from: {}", orig_post.ap_id),
+    };
+
+    // The crosspost is a normal new post, with the same checks, federation and websocket messages
+    let create_post = CreatePost {
+      name: orig_post.name,
+      community_id: data.community_id,
+      url: orig_post.url.map(Into::into),
+      body: Some(body),
+      honeypot: None,
+      nsfw: Some(orig_post.nsfw),
+      content_warning: orig_post.content_warning,
+      language: orig_post.language,
+      poll: None,
+      scheduled_publish_time: None,
+      auth: data.auth.to_owned(),
+    };
+    create_post.perform(context, websocket_id).await
+  }
+}
+
+fn check_poll(poll: &CreatePoll) -> Result<(), LemmyError> {
+  if !(2..=20).contains(&poll.options.len()) {
+    return Err(LemmyError::from_message("invalid_poll_options"));
+  }
+  let mut names = Vec::new();
+  for option in &poll.options {
+    if !is_valid_poll_option(option) {
+      return Err(LemmyError::from_message("invalid_poll_options"));
+    }
+    let name = option.trim();
+    if names.contains(&name) {
+      return Err(LemmyError::from_message("duplicate_poll_option"));
+    }
+    names.push(name);
+  }
+  if let Some(end_time) = poll.end_time {
+    if naive_from_unix(end_time) <= naive_now() {
+      return Err(LemmyError::from_message("invalid_poll_end_time"));
+    }
+  }
+  Ok(())
+}
@@ -17,6 +17,7 @@ use lemmy_db_schema::{
   },
   traits::Crud,
 };
+use lemmy_db_views::post_view::PostView;
 use lemmy_utils::{ConnectionId, LemmyError};
 use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperationCrud};
 
@@ -42,6 +43,11 @@ impl PerformCrud for DeletePost {
       return Err(LemmyError::from_message("couldnt_update_post"));
     }
 
+    // Scheduled posts were never federated, they have to be cancelled instead
+    if orig_post.scheduled_publish_time.is_some() {
+      return Err(LemmyError::from_message("post_is_scheduled"));
+    }
+
     check_community_ban(
       local_user_view.person.id,
       orig_post.community_id,
@@ -91,6 +97,46 @@ impl PerformCrud for DeletePost {
   }
 }
 
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for CancelScheduledPost {
+  type Response = PostResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<PostResponse, LemmyError> {
+    let data: &CancelScheduledPost = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let post_id = data.post_id;
+    let person_id = local_user_view.person.id;
+    let post_view = blocking(context.pool(), move |conn| {
+      PostView::read(conn, post_id, Some(person_id))
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_post"))?;
+
+    // Verify that only the creator can cancel
+    if !Post::is_post_creator(person_id, post_view.post.creator_id) {
+      return Err(LemmyError::from_message("no_post_edit_allowed"));
+    }
+
+    if post_view.post.scheduled_publish_time.is_none() {
+      return Err(LemmyError::from_message("post_already_published"));
+    }
+
+    // The post was never published, so it can be deleted without a trace
+    blocking(context.pool(), move |conn| Post::delete(conn, post_id))
+      .await?
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_update_post"))?;
+
+    Ok(PostResponse { post_view })
+  }
+}
+
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for RemovePost {
   type Response = PostResponse;
@@ -3,6 +3,7 @@ use actix_web::web::Data;
 use lemmy_api_common::{
   blocking,
   check_private_instance,
+  get_local_user_view_from_jwt,
   get_local_user_view_from_jwt_opt,
   mark_post_as_read,
   post::*,
@@ -12,8 +13,10 @@ use lemmy_db_schema::{
   from_opt_str_to_opt_enum,
   source::community::Community,
   traits::DeleteableOrRemoveable,
+  CommentSortType,
   ListingType,
   SortType,
+  FETCH_LIMIT_MAX,
 };
 use lemmy_db_views::{
   comment_view::CommentQueryBuilder,
@@ -26,6 +29,14 @@ use lemmy_db_views_actor::{
 use lemmy_utils::{ConnectionId, LemmyError};
 use lemmy_websocket::{messages::GetPostUsersOnline, LemmyContext};
 
+/// Depth of the comment tree returned with a post, deeper replies are loaded with GetComments.
+/// All limits are documented on `GetPostResponse::comments`.
+const POST_COMMENTS_MAX_DEPTH: i32 = 8;
+/// Number of top level comments returned with a post
+const POST_COMMENTS_LIMIT: i64 = 50;
+/// Number of replies returned below each comment of the tree
+const POST_COMMENTS_CHILDREN_LIMIT: i64 = 10;
+
 #[async_trait::async_trait(?Send)]
 impl PerformCrud for GetPost {
   type Response = GetPostResponse;
@@ -46,6 +57,10 @@ impl PerformCrud for GetPost {
     let show_bot_accounts = local_user_view
       .as_ref()
       .map(|t| t.local_user.show_bot_accounts);
+    let show_nsfw = local_user_view.as_ref().map(|t| t.local_user.show_nsfw);
+    let discussion_languages = local_user_view
+      .as_ref()
+      .map(|t| t.local_user.discussion_languages.to_owned());
     let person_id = local_user_view.map(|u| u.person.id);
 
     let id = data.id;
@@ -55,18 +70,28 @@ impl PerformCrud for GetPost {
     .await?
     .map_err(|e| LemmyError::from_error_message(e, "couldnt_find_post"))?;
 
+    // Scheduled posts are only visible to their creator
+    if post_view.post.scheduled_publish_time.is_some() && Some(post_view.creator.id) != person_id {
+      return Err(LemmyError::from_message("couldnt_find_post"));
+    }
+
     // Mark the post as read
     if let Some(person_id) = person_id {
       mark_post_as_read(person_id, id, context.pool()).await?;
     }
 
     let id = data.id;
+    let comment_sort = from_opt_str_to_opt_enum(&data.comment_sort).unwrap_or(CommentSortType::Hot);
     let mut comments = blocking(context.pool(), move |conn| {
       CommentQueryBuilder::create(conn)
         .my_person_id(person_id)
         .show_bot_accounts(show_bot_accounts)
+        .discussion_languages(discussion_languages)
         .post_id(id)
-        .limit(9999)
+        .comment_sort(comment_sort)
+        .max_depth(POST_COMMENTS_MAX_DEPTH)
+        .children_limit(POST_COMMENTS_CHILDREN_LIMIT)
+        .limit(POST_COMMENTS_LIMIT)
         .list()
     })
     .await??;
@@ -101,6 +126,30 @@ impl PerformCrud for GetPost {
     })
     .await??;
 
+    // Posts in other communities which link to the same url. Urls are normalized when the post
+    // is created, so an exact match is enough. Only the newest ones are returned, the rest can be
+    // found with a url search.
+    let cross_posts = if let Some(url) = post_view.post.url.to_owned() {
+      let url = url.to_string();
+      let show_nsfw = post_view.post.nsfw || show_nsfw.unwrap_or(false);
+      let mut cross_posts = blocking(context.pool(), move |conn| {
+        PostQueryBuilder::create(conn)
+          .listing_type(ListingType::All)
+          .sort(SortType::New)
+          .url_search(url)
+          .show_nsfw(show_nsfw)
+          .show_bot_accounts(show_bot_accounts)
+          .my_person_id(person_id)
+          .limit(FETCH_LIMIT_MAX)
+          .list()
+      })
+      .await??;
+      cross_posts.retain(|pv| pv.post.id != id);
+      cross_posts
+    } else {
+      Vec::new()
+    };
+
     let online = context
       .chat_server()
       .send(GetPostUsersOnline { post_id: data.id })
@@ -113,6 +162,7 @@ impl PerformCrud for GetPost {
       community_view,
       comments,
       moderators,
+      cross_posts,
       online,
     })
   }
@@ -153,6 +203,9 @@ impl PerformCrud for GetPosts {
     let show_read_posts = local_user_view
       .as_ref()
       .map(|t| t.local_user.show_read_posts);
+    let discussion_languages = local_user_view
+      .as_ref()
+      .map(|t| t.local_user.discussion_languages.to_owned());
 
     let sort: Option<SortType> = from_opt_str_to_opt_enum(&data.sort);
     let listing_type: Option<ListingType> = from_opt_str_to_opt_enum(&data.type_);
@@ -177,6 +230,7 @@ impl PerformCrud for GetPosts {
         .show_nsfw(show_nsfw)
         .show_bot_accounts(show_bot_accounts)
         .show_read_posts(show_read_posts)
+        .discussion_languages(discussion_languages)
         .community_id(community_id)
         .community_actor_id(community_actor_id)
         .saved_only(saved_only)
@@ -208,3 +262,37 @@ impl PerformCrud for GetPosts {
     Ok(GetPostsResponse { posts })
   }
 }
+
+#[async_trait::async_trait(?Send)]
+impl PerformCrud for ListScheduledPosts {
+  type Response = ListScheduledPostsResponse;
+
+  #[tracing::instrument(skip(context, _websocket_id))]
+  async fn perform(
+    &self,
+    context: &Data<LemmyContext>,
+    _websocket_id: Option<ConnectionId>,
+  ) -> Result<ListScheduledPostsResponse, LemmyError> {
+    let data: &ListScheduledPosts = self;
+    let local_user_view =
+      get_local_user_view_from_jwt(&data.auth, context.pool(), context.secret()).await?;
+
+    let person_id = local_user_view.person.id;
+    let page = data.page;
+    let limit = data.limit;
+    let posts = blocking(context.pool(), move |conn| {
+      PostQueryBuilder::create(conn)
+        .sort(SortType::New)
+        .creator_id(person_id)
+        .my_person_id(person_id)
+        .scheduled_only(true)
+        .page(page)
+        .limit(limit)
+        .list()
+    })
+    .await?
+    .map_err(|e| LemmyError::from_error_message(e, "couldnt_get_posts"))?;
+
+    Ok(ListScheduledPostsResponse { posts })
+  }
+}
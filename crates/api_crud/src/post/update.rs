@@ -5,6 +5,7 @@ use lemmy_api_common::{
   check_community_ban,
   check_community_deleted_or_removed,
   get_local_user_view_from_jwt,
+  language_check,
   post::*,
 };
 use lemmy_apub::protocol::activities::{
@@ -12,13 +13,21 @@ use lemmy_apub::protocol::activities::{
   CreateOrUpdateType,
 };
 use lemmy_db_schema::{
+  diesel_option_overwrite,
   naive_now,
   source::post::{Post, PostForm},
   traits::Crud,
 };
+use lemmy_db_views::post_view::PostView;
 use lemmy_utils::{
   request::fetch_site_data,
-  utils::{check_slurs_opt, clean_optional_text, clean_url_params, is_valid_post_title},
+  utils::{
+    check_slurs_opt,
+    clean_optional_text,
+    clean_url_params,
+    is_valid_post_title,
+    naive_from_unix,
+  },
   ConnectionId,
   LemmyError,
 };
@@ -43,6 +52,12 @@ impl PerformCrud for EditPost {
     let slur_regex = &context.settings().slur_regex();
     check_slurs_opt(&data.name, slur_regex)?;
     check_slurs_opt(&data.body, slur_regex)?;
+    check_slurs_opt(&data.content_warning, slur_regex)?;
+
+    let language = diesel_option_overwrite(&data.language);
+    if let Some(Some(language)) = &language {
+      language_check(language)?;
+    }
 
     if let Some(name) = &data.name {
       if !is_valid_post_title(name) {
@@ -66,6 +81,17 @@ impl PerformCrud for EditPost {
       return Err(LemmyError::from_message("no_post_edit_allowed"));
     }
 
+    let is_scheduled = orig_post.scheduled_publish_time.is_some();
+    let scheduled_publish_time = data.scheduled_publish_time.map(naive_from_unix);
+    if let Some(scheduled_publish_time) = scheduled_publish_time {
+      if !is_scheduled {
+        return Err(LemmyError::from_message("post_already_published"));
+      }
+      if scheduled_publish_time <= naive_now() {
+        return Err(LemmyError::from_message("invalid_scheduled_publish_time"));
+      }
+    }
+
     // Fetch post links and Pictrs cached image
     let data_url = data.url.as_ref();
     let (metadata_res, pictrs_thumbnail) =
@@ -81,7 +107,12 @@ impl PerformCrud for EditPost {
       url: data_url.map(|u| clean_url_params(u.to_owned()).into()),
       body: clean_optional_text(&data.body),
       nsfw: data.nsfw,
-      updated: Some(naive_now()),
+      content_warning: diesel_option_overwrite(&data.content_warning),
+      language,
+      // Edits before publishing dont mark the post as edited
+      updated: (!is_scheduled).then(naive_now),
+      published: scheduled_publish_time,
+      scheduled_publish_time: scheduled_publish_time.map(Some),
       embed_title,
       embed_description,
       embed_html,
@@ -107,6 +138,16 @@ impl PerformCrud for EditPost {
       }
     };
 
+    // Scheduled posts are federated and sent over websocket once they get published
+    if updated_post.scheduled_publish_time.is_some() {
+      let person_id = local_user_view.person.id;
+      let post_view = blocking(context.pool(), move |conn| {
+        PostView::read(conn, post_id, Some(person_id))
+      })
+      .await??;
+      return Ok(PostResponse { post_view });
+    }
+
     // Send apub update
     CreateOrUpdatePost::send(
       updated_post.into(),
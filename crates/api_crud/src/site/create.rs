@@ -8,7 +8,11 @@ use lemmy_api_common::{
   site::*,
   site_description_length_check,
 };
-use lemmy_apub::generate_site_inbox_url;
+use lemmy_apub::{
+  federation_list::refresh_federation_lists,
+  generate_site_inbox_url,
+  objects::instance::init_authorized_fetch,
+};
 use lemmy_db_schema::{
   diesel_option_overwrite,
   diesel_option_overwrite_to_url,
@@ -20,7 +24,6 @@ use lemmy_db_schema::{
 use lemmy_db_views::site_view::SiteView;
 use lemmy_utils::{
   apub::generate_actor_keypair,
-  settings::structs::Settings,
   utils::{check_slurs, check_slurs_opt},
   ConnectionId,
   LemmyError,
@@ -55,8 +58,8 @@ impl PerformCrud for CreateSite {
 
     check_slurs(&data.name, &context.settings().slur_regex())?;
     check_slurs_opt(&data.description, &context.settings().slur_regex())?;
-    check_image_has_local_domain(icon.as_ref().unwrap_or(&None))?;
-    check_image_has_local_domain(banner.as_ref().unwrap_or(&None))?;
+    check_image_has_local_domain(icon.as_ref().unwrap_or(&None), &context.settings())?;
+    check_image_has_local_domain(banner.as_ref().unwrap_or(&None), &context.settings())?;
 
     // Make sure user is an admin
     is_admin(&local_user_view)?;
@@ -65,7 +68,7 @@ impl PerformCrud for CreateSite {
       site_description_length_check(desc)?;
     }
 
-    let actor_id: DbUrl = Url::parse(&Settings::get().get_protocol_and_hostname())?.into();
+    let actor_id: DbUrl = Url::parse(&context.settings().get_protocol_and_hostname())?.into();
     let inbox_url = Some(generate_site_inbox_url(&actor_id)?);
     let keypair = generate_actor_keypair()?;
     let site_form = SiteForm {
@@ -84,6 +87,11 @@ impl PerformCrud for CreateSite {
       private_key: Some(Some(keypair.private_key)),
       public_key: Some(keypair.public_key),
       default_theme: data.default_theme.clone(),
+      public_edit_history: data.public_edit_history,
+      // The config file only provides the initial value
+      federation_strict_allowlist: data
+        .federation_strict_allowlist
+        .or(Some(context.settings().federation.strict_allowlist)),
       ..SiteForm::default()
     };
 
@@ -92,6 +100,9 @@ impl PerformCrud for CreateSite {
       .await?
       .map_err(|e| LemmyError::from_error_message(e, "site_already_exists"))?;
 
+    init_authorized_fetch(context).await?;
+    refresh_federation_lists(context).await?;
+
     let site_view = blocking(context.pool(), SiteView::read_local).await??;
 
     Ok(SiteResponse { site_view })
@@ -69,6 +69,8 @@ impl PerformCrud for GetSite {
             application_question: setup.application_question.to_owned(),
             private_instance: setup.private_instance,
             default_theme: setup.default_theme.to_owned(),
+            public_edit_history: None,
+            federation_strict_allowlist: None,
             auth: admin_jwt,
           };
           create_site.perform(context, websocket_id).await?;
@@ -8,6 +8,7 @@ use lemmy_api_common::{
   site::{EditSite, SiteResponse},
   site_description_length_check,
 };
+use lemmy_apub::federation_list::refresh_federation_lists;
 use lemmy_db_schema::{
   diesel_option_overwrite,
   diesel_option_overwrite_to_url,
@@ -50,8 +51,8 @@ impl PerformCrud for EditSite {
 
     check_slurs_opt(&data.name, &context.settings().slur_regex())?;
     check_slurs_opt(&data.description, &context.settings().slur_regex())?;
-    check_image_has_local_domain(icon.as_ref().unwrap_or(&None))?;
-    check_image_has_local_domain(banner.as_ref().unwrap_or(&None))?;
+    check_image_has_local_domain(icon.as_ref().unwrap_or(&None), &context.settings())?;
+    check_image_has_local_domain(banner.as_ref().unwrap_or(&None), &context.settings())?;
 
     if let Some(Some(desc)) = &description {
       site_description_length_check(desc)?;
@@ -80,6 +81,8 @@ impl PerformCrud for EditSite {
       application_question,
       private_instance: data.private_instance,
       default_theme: data.default_theme.clone(),
+      public_edit_history: data.public_edit_history,
+      federation_strict_allowlist: data.federation_strict_allowlist,
       ..SiteForm::default()
     };
 
@@ -110,6 +113,10 @@ impl PerformCrud for EditSite {
       .map_err(|e| LemmyError::from_error_message(e, "couldnt_set_all_email_verified"))?;
     }
 
+    if local_site.federation_strict_allowlist != update_site.federation_strict_allowlist {
+      refresh_federation_lists(context).await?;
+    }
+
     let site_view = blocking(context.pool(), SiteView::read_local).await??;
 
     let res = SiteResponse { site_view };
@@ -7,7 +7,12 @@ use lemmy_api_common::{
   person::*,
 };
 use lemmy_apub::{fetcher::resolve_actor_identifier, objects::person::ApubPerson};
-use lemmy_db_schema::{from_opt_str_to_opt_enum, source::person::Person, SortType};
+use lemmy_db_schema::{
+  from_opt_str_to_opt_enum,
+  source::person::Person,
+  traits::ApubActor,
+  SortType,
+};
 use lemmy_db_views::{comment_view::CommentQueryBuilder, post_view::PostQueryBuilder};
 use lemmy_db_views_actor::{
   community_moderator_view::CommunityModeratorView,
@@ -73,6 +78,19 @@ impl PerformCrud for GetPersonDetails {
     })
     .await??;
 
+    // Point to the new account, if it is known to this instance
+    let moved_to = match person_view.person.moved_to.clone() {
+      Some(moved_to) => {
+        blocking(context.pool(), move |conn| {
+          Person::read_from_apub_id(conn, &moved_to)?
+            .map(|p| PersonViewSafe::read(conn, p.id))
+            .transpose()
+        })
+        .await??
+      }
+      None => None,
+    };
+
     let page = data.page;
     let limit = data.limit;
     let saved_only = data.saved_only;
@@ -124,6 +142,7 @@ impl PerformCrud for GetPersonDetails {
       moderates,
       comments,
       posts,
+      moved_to,
     })
   }
 }
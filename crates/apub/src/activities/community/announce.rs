@@ -95,8 +95,14 @@ impl ActivityHandler for AnnounceActivity {
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     verify_is_public(&self.to, &self.cc)?;
-    verify_activity(&self.id, self.actor.inner(), &context.settings())?;
-    self.object.verify(context, request_counter).await?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    match &self.object {
+      AnnouncableActivities::MoveCommunity(m) => {
+        m.verify_announced(&self.actor, context, request_counter)
+          .await?
+      }
+      _ => self.object.verify(context, request_counter).await?,
+    }
     Ok(())
   }
 
@@ -0,0 +1,111 @@
+use crate::{
+  activities::{
+    community::report::{create_report, reported_author_and_community},
+    generate_activity_id,
+    send_lemmy_activity,
+    verify_activity,
+  },
+  objects::instance::ApubSite,
+  protocol::activities::community::forward_report::ForwardReport,
+  PostOrComment,
+};
+use activitystreams_kinds::activity::FlagType;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType},
+  verify::{verify_domains_match, verify_urls_match},
+};
+use lemmy_db_schema::{source::site::Site, ReportCreator};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl ForwardReport {
+  /// Informs the home instance of the author about a report in a local community. Nothing is sent
+  /// for local authors, or if the reporter is from the same instance as the author.
+  #[tracing::instrument(skip_all)]
+  pub(crate) async fn send(
+    object: &PostOrComment,
+    reporter: &Url,
+    reason: String,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let (author, _) = reported_author_and_community(object, context).await?;
+    if author.local || author.actor_id.domain() == reporter.domain() {
+      return Ok(());
+    }
+
+    let site: ApubSite = blocking(context.pool(), Site::read_local_site)
+      .await??
+      .into();
+    let kind = FlagType::Flag;
+    let id = generate_activity_id(
+      kind.clone(),
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let forward = ForwardReport {
+      actor: ObjectId::new(site.actor_id()),
+      to: [ObjectId::new(author.actor_id())],
+      object: (
+        ObjectId::new(author.actor_id()),
+        ObjectId::new(object.ap_id()),
+      ),
+      summary: reason,
+      kind,
+      id: id.clone(),
+      unparsed: Default::default(),
+    };
+    let inboxes = vec![author.shared_inbox_or_inbox_url()];
+    send_lemmy_activity(context, &forward, &id, &site, inboxes, false).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for ForwardReport {
+  type DataType = LemmyContext;
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    let object = self
+      .object
+      .1
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let (author, community) = reported_author_and_community(&object, context).await?;
+    // Reports can only be forwarded by the instance of the community, to the instance of the author
+    verify_domains_match(self.actor.inner(), &community.actor_id())?;
+    if !author.local {
+      return Err(LemmyError::from_message(
+        "Report can only be forwarded to the instance of the author",
+      ));
+    }
+    verify_urls_match(self.object.0.inner(), &author.actor_id())?;
+    verify_urls_match(self.to[0].inner(), &author.actor_id())?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let site = self
+      .actor
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let object = self
+      .object
+      .1
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    create_report(ReportCreator::Site(site.id), &object, self.summary, context).await
+  }
+}
@@ -11,8 +11,10 @@ use url::Url;
 
 pub mod add_mod;
 pub mod announce;
+pub mod forward_report;
 pub mod remove_mod;
 pub mod report;
+pub mod resolve_report;
 pub mod update;
 
 #[tracing::instrument(skip_all)]
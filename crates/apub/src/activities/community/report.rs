@@ -6,7 +6,7 @@ use crate::{
     verify_person_in_community,
   },
   objects::{community::ApubCommunity, person::ApubPerson},
-  protocol::activities::community::report::Report,
+  protocol::activities::community::{forward_report::ForwardReport, report::Report},
   PostOrComment,
 };
 use activitystreams_kinds::activity::FlagType;
@@ -17,11 +17,16 @@ use lemmy_apub_lib::{
   traits::{ActivityHandler, ActorType},
 };
 use lemmy_db_schema::{
+  newtypes::PersonId,
   source::{
     comment_report::{CommentReport, CommentReportForm},
+    community::Community,
+    person::Person,
+    post::Post,
     post_report::{PostReport, PostReportForm},
   },
-  traits::Reportable,
+  traits::{Crud, Reportable},
+  ReportCreator,
 };
 use lemmy_db_views::{comment_report_view::CommentReportView, post_report_view::PostReportView};
 use lemmy_utils::LemmyError;
@@ -59,7 +64,17 @@ impl Report {
       vec![community.shared_inbox_or_inbox_url()],
       false,
     )
-    .await
+    .await?;
+
+    // Remote communities forward the report themselves once they receive it
+    if community.local {
+      let object = report
+        .object
+        .dereference(context, context.client(), &mut 0)
+        .await?;
+      ForwardReport::send(&object, &actor.actor_id(), report.summary, context).await?;
+    }
+    Ok(())
   }
 }
 
@@ -73,7 +88,7 @@ impl ActivityHandler for Report {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    verify_activity(&self.id, self.actor.inner(), &context.settings())?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
     let community = self.to[0]
       .dereference(context, context.client(), request_counter)
       .await?;
@@ -91,67 +106,111 @@ impl ActivityHandler for Report {
       .actor
       .dereference(context, context.client(), request_counter)
       .await?;
-    match self
+    let object = self
       .object
       .dereference(context, context.client(), request_counter)
-      .await?
-    {
-      PostOrComment::Post(post) => {
-        let report_form = PostReportForm {
-          creator_id: actor.id,
-          post_id: post.id,
-          original_post_name: post.name.clone(),
-          original_post_url: post.url.clone(),
-          reason: self.summary,
-          original_post_body: post.body.clone(),
-        };
+      .await?;
+    create_report(
+      ReportCreator::Person(actor.id),
+      &object,
+      self.summary.clone(),
+      context,
+    )
+    .await?;
+    ForwardReport::send(&object, self.actor.inner(), self.summary, context).await
+  }
+}
+
+/// Stores a report which was received from another instance, and notifies the moderators of the
+/// community.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn create_report(
+  creator: ReportCreator,
+  object: &PostOrComment,
+  reason: String,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let (creator_id, forwarded_by_site_id) = creator.columns();
+  // Only used for the vote of the viewer, which doesnt matter here
+  let my_person_id = creator_id.unwrap_or(PersonId(-1));
+  match object {
+    PostOrComment::Post(post) => {
+      let report_form = PostReportForm {
+        creator_id,
+        post_id: post.id,
+        original_post_name: post.name.clone(),
+        original_post_url: post.url.clone(),
+        reason,
+        original_post_body: post.body.clone(),
+        forwarded_by_site_id,
+      };
+
+      let report = blocking(context.pool(), move |conn| {
+        PostReport::report(conn, &report_form)
+      })
+      .await??;
 
-        let report = blocking(context.pool(), move |conn| {
-          PostReport::report(conn, &report_form)
-        })
-        .await??;
+      let post_report_view = blocking(context.pool(), move |conn| {
+        PostReportView::read(conn, report.id, my_person_id)
+      })
+      .await??;
 
-        let post_report_view = blocking(context.pool(), move |conn| {
-          PostReportView::read(conn, report.id, actor.id)
-        })
-        .await??;
+      context.chat_server().do_send(SendModRoomMessage {
+        op: UserOperation::CreateCommentReport,
+        response: PostReportResponse { post_report_view },
+        community_id: post.community_id,
+        websocket_id: None,
+      });
+    }
+    PostOrComment::Comment(comment) => {
+      let report_form = CommentReportForm {
+        creator_id,
+        comment_id: comment.id,
+        original_comment_text: comment.content.clone(),
+        reason,
+        forwarded_by_site_id,
+      };
 
-        context.chat_server().do_send(SendModRoomMessage {
-          op: UserOperation::CreateCommentReport,
-          response: PostReportResponse { post_report_view },
-          community_id: post.community_id,
-          websocket_id: None,
-        });
-      }
-      PostOrComment::Comment(comment) => {
-        let report_form = CommentReportForm {
-          creator_id: actor.id,
-          comment_id: comment.id,
-          original_comment_text: comment.content.clone(),
-          reason: self.summary,
-        };
+      let report = blocking(context.pool(), move |conn| {
+        CommentReport::report(conn, &report_form)
+      })
+      .await??;
 
-        let report = blocking(context.pool(), move |conn| {
-          CommentReport::report(conn, &report_form)
-        })
-        .await??;
+      let comment_report_view = blocking(context.pool(), move |conn| {
+        CommentReportView::read(conn, report.id, my_person_id)
+      })
+      .await??;
+      let community_id = comment_report_view.community.id;
 
-        let comment_report_view = blocking(context.pool(), move |conn| {
-          CommentReportView::read(conn, report.id, actor.id)
-        })
-        .await??;
-        let community_id = comment_report_view.community.id;
+      context.chat_server().do_send(SendModRoomMessage {
+        op: UserOperation::CreateCommentReport,
+        response: CommentReportResponse {
+          comment_report_view,
+        },
+        community_id,
+        websocket_id: None,
+      });
+    }
+  };
+  Ok(())
+}
 
-        context.chat_server().do_send(SendModRoomMessage {
-          op: UserOperation::CreateCommentReport,
-          response: CommentReportResponse {
-            comment_report_view,
-          },
-          community_id,
-          websocket_id: None,
-        });
-      }
+/// Returns the author and the community of a reported post or comment
+#[tracing::instrument(skip_all)]
+pub(crate) async fn reported_author_and_community(
+  object: &PostOrComment,
+  context: &LemmyContext,
+) -> Result<(ApubPerson, ApubCommunity), LemmyError> {
+  let object = object.clone();
+  let (author, community) = blocking(context.pool(), move |conn| {
+    let (creator_id, community_id) = match object {
+      PostOrComment::Post(p) => (p.creator_id, p.community_id),
+      PostOrComment::Comment(c) => (c.creator_id, Post::read(conn, c.post_id)?.community_id),
     };
-    Ok(())
-  }
+    let author = Person::read(conn, creator_id)?;
+    let community = Community::read(conn, community_id)?;
+    Ok((author, community)) as Result<_, diesel::result::Error>
+  })
+  .await??;
+  Ok((author.into(), community.into()))
 }
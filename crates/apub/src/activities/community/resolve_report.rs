@@ -0,0 +1,142 @@
+use crate::{
+  activities::{
+    community::report::reported_author_and_community,
+    generate_activity_id,
+    send_lemmy_activity,
+    verify_activity,
+  },
+  objects::{instance::ApubSite, person::ApubPerson},
+  protocol::activities::community::resolve_report::{ReportReference, ResolveReport, ResolveType},
+  PostOrComment,
+};
+use activitystreams_kinds::activity::FlagType;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType},
+  verify::verify_domains_match,
+};
+use lemmy_db_schema::{
+  source::{comment_report::CommentReport, post_report::PostReport, site::Site},
+  ReportCreator,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl ResolveReport {
+  /// Informs the instances of the reporter and of the author that a report in a local community was
+  /// resolved, so that they can resolve their copies of the report as well.
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    object: ObjectId<PostOrComment>,
+    reporter: &ApubPerson,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let object = object
+      .dereference(context, context.client(), &mut 0)
+      .await?;
+    let (author, community) = reported_author_and_community(&object, context).await?;
+    if !community.local {
+      return Ok(());
+    }
+
+    let site: ApubSite = blocking(context.pool(), Site::read_local_site)
+      .await??
+      .into();
+    if !reporter.local {
+      ResolveReport::send_to(&site, reporter, reporter.actor_id(), &object, context).await?;
+    }
+    // The instance of the author received the report from the instance actor, see ForwardReport
+    if !author.local && author.actor_id.domain() != reporter.actor_id.domain() {
+      ResolveReport::send_to(&site, &author, site.actor_id(), &object, context).await?;
+    }
+    Ok(())
+  }
+
+  async fn send_to(
+    site: &ApubSite,
+    recipient: &ApubPerson,
+    report_creator: Url,
+    object: &PostOrComment,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let kind = ResolveType::Resolve;
+    let id = generate_activity_id(
+      kind.clone(),
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let resolve = ResolveReport {
+      actor: ObjectId::new(site.actor_id()),
+      to: [ObjectId::new(recipient.actor_id())],
+      object: ReportReference {
+        actor: report_creator,
+        object: ObjectId::new(object.ap_id()),
+        kind: FlagType::Flag,
+      },
+      kind,
+      id: id.clone(),
+      unparsed: Default::default(),
+    };
+    let inboxes = vec![recipient.shared_inbox_or_inbox_url()];
+    send_lemmy_activity(context, &resolve, &id, site, inboxes, false).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for ResolveReport {
+  type DataType = LemmyContext;
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    let object = self
+      .object
+      .object
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    // Only the instance of the community can resolve reports about it
+    let (_, community) = reported_author_and_community(&object, context).await?;
+    verify_domains_match(self.actor.inner(), &community.actor_id())?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let site = self
+      .actor
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let object = self
+      .object
+      .object
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    // Forwarded reports are stored with the site as creator, other reports were created by a local
+    // user
+    let creator = if &self.object.actor == self.actor.inner() {
+      ReportCreator::Site(site.id)
+    } else {
+      let person = ObjectId::<ApubPerson>::new(self.object.actor)
+        .dereference_local(context)
+        .await?;
+      ReportCreator::Person(person.id)
+    };
+
+    blocking(context.pool(), move |conn| match object {
+      PostOrComment::Post(post) => PostReport::resolve_apub(conn, post.id, creator),
+      PostOrComment::Comment(comment) => CommentReport::resolve_apub(conn, comment.id, creator),
+    })
+    .await??;
+    Ok(())
+  }
+}
@@ -31,6 +31,27 @@ impl UpdateCommunity {
     community: ApubCommunity,
     actor: &ApubPerson,
     context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    Self::send_update(community, actor, None, context).await
+  }
+
+  /// Announces the new public key of a local community. The community must still hold the previous
+  /// private key, so that the announce is signed with a key which other instances know about.
+  #[tracing::instrument(skip_all)]
+  pub async fn send_key_rotation(
+    community: ApubCommunity,
+    actor: &ApubPerson,
+    revoke_previous_key: bool,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    Self::send_update(community, actor, Some(revoke_previous_key), context).await
+  }
+
+  async fn send_update(
+    community: ApubCommunity,
+    actor: &ApubPerson,
+    revoke_previous_key: Option<bool>,
+    context: &LemmyContext,
   ) -> Result<(), LemmyError> {
     let id = generate_activity_id(
       UpdateType::Update,
@@ -43,6 +64,7 @@ impl UpdateCommunity {
       cc: vec![community.actor_id()],
       kind: UpdateType::Update,
       id: id.clone(),
+      revoke_previous_key,
       unparsed: Default::default(),
     };
 
@@ -62,7 +84,7 @@ impl ActivityHandler for UpdateCommunity {
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     verify_is_public(&self.to, &self.cc)?;
-    verify_activity(&self.id, self.actor.inner(), &context.settings())?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
     let community = self.get_community(context, request_counter).await?;
     verify_person_in_community(&self.actor, &community, context, request_counter).await?;
     verify_mod_action(
@@ -90,6 +112,10 @@ impl ActivityHandler for UpdateCommunity {
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     let community = self.get_community(context, request_counter).await?;
+    // The activity may be signed with a leaked previous key, so a new key is never taken from it
+    let key_changed = community.public_key != self.object.public_key.public_key_pem;
+    let current_key = community.public_key.clone();
+    let community_actor_id = community.actor_id.clone();
 
     let updated_community = self.object.into_form();
     let cf = CommunityForm {
@@ -100,10 +126,29 @@ impl ActivityHandler for UpdateCommunity {
       // TODO: icon and banner would be hosted on the other instance, ideally we would copy it to ours
       icon: updated_community.icon,
       banner: updated_community.banner,
+      also_known_as: updated_community.also_known_as,
+      public_key: current_key,
       ..CommunityForm::default()
     };
+    let community_id = community.id;
+    blocking(context.pool(), move |conn| {
+      Community::update(conn, community_id, &cf)
+    })
+    .await??;
+    // Instead, the community is fetched from its instance, to get the key it actually uses. The
+    // previous key is kept by the database.
+    if key_changed {
+      ObjectId::<ApubCommunity>::new(community_actor_id)
+        .dereference_forced(context, context.client(), request_counter)
+        .await?;
+    }
+    let revoke_previous_key = self.revoke_previous_key == Some(true);
     let updated_community = blocking(context.pool(), move |conn| {
-      Community::update(conn, community.id, &cf)
+      if revoke_previous_key {
+        Community::revoke_previous_key(conn, community_id)
+      } else {
+        Community::read(conn, community_id)
+      }
     })
     .await??;
 
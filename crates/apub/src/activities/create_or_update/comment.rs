@@ -32,6 +32,30 @@ use lemmy_utils::LemmyError;
 use lemmy_websocket::{send::send_comment_ws_message, LemmyContext, UserOperationCrud};
 
 impl CreateOrUpdateComment {
+  pub(crate) async fn new(
+    comment: ApubComment,
+    actor: &ApubPerson,
+    kind: CreateOrUpdateType,
+    context: &LemmyContext,
+  ) -> Result<CreateOrUpdateComment, LemmyError> {
+    let id = generate_activity_id(
+      kind.clone(),
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let note = comment.into_apub(context).await?;
+
+    Ok(CreateOrUpdateComment {
+      actor: ObjectId::new(actor.actor_id()),
+      to: vec![public()],
+      cc: note.cc.clone(),
+      tag: note.tag.clone(),
+      object: note,
+      kind,
+      id,
+      unparsed: Default::default(),
+    })
+  }
+
   #[tracing::instrument(skip(comment, actor, kind, context))]
   pub async fn send(
     comment: ApubComment,
@@ -50,22 +74,8 @@ impl CreateOrUpdateComment {
     .await??
     .into();
 
-    let id = generate_activity_id(
-      kind.clone(),
-      &context.settings().get_protocol_and_hostname(),
-    )?;
-    let note = comment.into_apub(context).await?;
-
-    let create_or_update = CreateOrUpdateComment {
-      actor: ObjectId::new(actor.actor_id()),
-      to: vec![public()],
-      cc: note.cc.clone(),
-      tag: note.tag.clone(),
-      object: note,
-      kind,
-      id: id.clone(),
-      unparsed: Default::default(),
-    };
+    let create_or_update = CreateOrUpdateComment::new(comment, actor, kind, context).await?;
+    let id = create_or_update.id.clone();
 
     let tagged_users: Vec<ObjectId<ApubPerson>> = create_or_update
       .tag
@@ -100,7 +110,7 @@ impl ActivityHandler for CreateOrUpdateComment {
     let post = self.object.get_parents(context, request_counter).await?.0;
     let community = self.get_community(context, request_counter).await?;
 
-    verify_activity(&self.id, self.actor.inner(), &context.settings())?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
     verify_person_in_community(&self.actor, &community, context, request_counter).await?;
     verify_domains_match(self.actor.inner(), self.object.id.inner())?;
     check_community_deleted_or_removed(&community)?;
@@ -47,7 +47,7 @@ impl ActivityHandler for Delete {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    verify_activity(&self.id, self.actor.inner(), &context.settings())?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
     verify_delete_activity(self, self.summary.is_some(), context, request_counter).await?;
     Ok(())
   }
@@ -90,7 +90,7 @@ impl ActivityHandler for Delete {
 }
 
 impl Delete {
-  pub(in crate::activities::deletion) fn new(
+  pub(crate) fn new(
     actor: &Person,
     object: DeletableObjects,
     to: Url,
@@ -1,5 +1,6 @@
 use crate::{
   activities::{generate_activity_id, send_lemmy_activity, verify_activity},
+  fetcher::community_backfill::start_community_backfill,
   protocol::activities::following::{accept::AcceptFollowCommunity, follow::FollowCommunity},
 };
 use activitystreams_kinds::activity::AcceptType;
@@ -53,7 +54,7 @@ impl ActivityHandler for AcceptFollowCommunity {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    verify_activity(&self.id, self.actor.inner(), &context.settings())?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
     verify_urls_match(self.actor.inner(), self.object.object.inner())?;
     self.object.verify(context, request_counter).await?;
     Ok(())
@@ -65,21 +66,24 @@ impl ActivityHandler for AcceptFollowCommunity {
     context: &Data<LemmyContext>,
     request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    let person = self
+    let community = self
       .actor
       .dereference(context, context.client(), request_counter)
       .await?;
-    let community = self
+    let person = self
       .object
       .actor
       .dereference(context, context.client(), request_counter)
       .await?;
     // This will throw an error if no follow was requested
+    let community_id = community.id;
     blocking(context.pool(), move |conn| {
-      CommunityFollower::follow_accepted(conn, person.id, community.id)
+      CommunityFollower::follow_accepted(conn, community_id, person.id)
     })
     .await??;
 
+    start_community_backfill(community, context).await?;
+
     Ok(())
   }
 }
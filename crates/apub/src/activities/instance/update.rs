@@ -0,0 +1,99 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity, verify_activity, verify_is_public},
+  objects::instance::ApubSite,
+  protocol::activities::instance::update::UpdateInstance,
+};
+use activitystreams_kinds::{activity::UpdateType, public};
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType, ApubObject},
+  verify::verify_urls_match,
+};
+use lemmy_db_schema::source::site::Site;
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+
+impl UpdateInstance {
+  /// Sends the updated site to all known instances. The activity is signed with the key of
+  /// `signer`, so that a new key can be announced with the previous one.
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    site: ApubSite,
+    signer: &ApubSite,
+    revoke_previous_key: bool,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let id = generate_activity_id(
+      UpdateType::Update,
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let update = UpdateInstance {
+      actor: ObjectId::new(site.actor_id()),
+      to: vec![public()],
+      object: Box::new(site.into_apub(context).await?),
+      cc: vec![],
+      kind: UpdateType::Update,
+      id: id.clone(),
+      revoke_previous_key: Some(revoke_previous_key),
+      unparsed: Default::default(),
+    };
+
+    let remote_sites = blocking(context.pool(), Site::read_remote_sites).await??;
+    let inboxes = remote_sites
+      .into_iter()
+      .map(|s| s.inbox_url.into())
+      .collect();
+    send_lemmy_activity(context, &update, &id, signer, inboxes, false).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for UpdateInstance {
+  type DataType = LemmyContext;
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_is_public(&self.to, &self.cc)?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    // An instance can only update itself
+    verify_urls_match(self.actor.inner(), self.object.id.inner())?;
+    ApubSite::verify(&self.object, self.actor.inner(), context, request_counter).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let current = self
+      .actor
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    // The activity may be signed with a leaked previous key, so a new key is never taken from it.
+    // Instead the site is fetched from its instance, to get the key it actually uses.
+    let site = if current.public_key != self.object.public_key.public_key_pem {
+      self
+        .actor
+        .dereference_forced(context, context.client(), request_counter)
+        .await?
+    } else {
+      ApubSite::from_apub(*self.object, context, request_counter).await?
+    };
+    if self.revoke_previous_key == Some(true) {
+      let site_id = site.id;
+      blocking(context.pool(), move |conn| {
+        Site::revoke_previous_key(conn, site_id)
+      })
+      .await??;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,2 @@
+pub mod move_community;
+pub mod move_person;
@@ -0,0 +1,227 @@
+use crate::{
+  activities::{
+    community::{announce::GetCommunity, send_activity_in_community},
+    following::follow::FollowCommunity,
+    generate_activity_id,
+    verify_activity,
+    verify_is_public,
+    verify_mod_action,
+    verify_person_in_community,
+  },
+  activity_lists::AnnouncableActivities,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::migration::move_community::MoveCommunity,
+};
+use activitystreams_kinds::{activity::MoveType, public};
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType},
+};
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::{
+    community::{Community, CommunityFollower},
+    person::Person,
+  },
+  traits::Crud,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::{send::send_community_ws_message, LemmyContext, UserOperationCrud};
+use url::Url;
+
+impl MoveCommunity {
+  /// Moves `community` to `target`, which needs to list the community in `alsoKnownAs`. Local
+  /// followers are transferred directly, remote instances do the same once they receive the
+  /// announced activity.
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    community: &ApubCommunity,
+    target: ObjectId<ApubCommunity>,
+    actor: &ApubPerson,
+    context: &LemmyContext,
+  ) -> Result<ApubCommunity, LemmyError> {
+    if community.moved_to.is_some() {
+      return Err(LemmyError::from_message("community_already_moved"));
+    }
+    let target = verify_move_target(&community.actor_id(), &target, context, &mut 0).await?;
+
+    let id = generate_activity_id(
+      MoveType::Move,
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let move_community = MoveCommunity {
+      actor: ObjectId::new(actor.actor_id()),
+      to: vec![public()],
+      object: ObjectId::new(community.actor_id()),
+      target: ObjectId::new(target.actor_id()),
+      cc: vec![community.actor_id()],
+      kind: MoveType::Move,
+      id: id.clone(),
+      unparsed: Default::default(),
+    };
+
+    // Announce to followers before the community is marked as moved
+    let activity = AnnouncableActivities::MoveCommunity(move_community);
+    send_activity_in_community(activity, &id, actor, community, vec![], context).await?;
+    move_community_followers(community, &target, context).await?;
+    Ok(target)
+  }
+
+  /// Verifies a move which was announced by `announcer`. This is the only way that moves of remote
+  /// communities by their moderators are accepted, because mod status can't be checked remotely.
+  #[tracing::instrument(skip_all)]
+  pub(crate) async fn verify_announced(
+    &self,
+    announcer: &ObjectId<ApubCommunity>,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    if announcer.inner() != self.object.inner() {
+      return Err(LemmyError::from_message(
+        "move_community_announced_by_other_community",
+      ));
+    }
+    self.verify_move(true, context, request_counter).await
+  }
+
+  async fn verify_move(
+    &self,
+    announced: bool,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_is_public(&self.to, &self.cc)?;
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    let community = self.get_community(context, request_counter).await?;
+    // A remote community may also send the move itself, in that case there is no mod to check
+    let sent_by_community = !community.local && self.actor.inner() == self.object.inner();
+    if !sent_by_community {
+      // verify_mod_action() accepts anyone for remote communities, so it needs to come from the
+      // community itself. Otherwise any user could move the followers of a remote community.
+      if !community.local && !announced {
+        return Err(LemmyError::from_message(
+          "move_community_not_announced_by_community",
+        ));
+      }
+      verify_person_in_community(&self.actor, &community, context, request_counter).await?;
+      verify_mod_action(
+        &self.actor,
+        self.object.inner(),
+        &community,
+        context,
+        request_counter,
+      )
+      .await?;
+    }
+    verify_move_target(self.object.inner(), &self.target, context, request_counter).await?;
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for MoveCommunity {
+  type DataType = LemmyContext;
+
+  /// Moves which are received directly, without announce. See [`MoveCommunity::verify_announced`]
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    self.verify_move(false, context, request_counter).await
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    // Also runs if the community is already marked as moved, in case that was fetched before this
+    // activity arrived. Only follows which are still pointing to the old community get moved.
+    let community = self.get_community(context, request_counter).await?;
+    let target = self
+      .target
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    move_community_followers(&community, &target, context).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl GetCommunity for MoveCommunity {
+  #[tracing::instrument(skip_all)]
+  async fn get_community(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<ApubCommunity, LemmyError> {
+    self
+      .object
+      .dereference(context, context.client(), request_counter)
+      .await
+  }
+}
+
+/// Fetches the latest version of the target community, and checks that it lists the moving
+/// community as alias. This way the moderators of both communities need to agree to the move.
+async fn verify_move_target(
+  old_community: &Url,
+  target: &ObjectId<ApubCommunity>,
+  context: &LemmyContext,
+  request_counter: &mut i32,
+) -> Result<ApubCommunity, LemmyError> {
+  if target.inner() == old_community {
+    return Err(LemmyError::from_message("cant_move_to_same_community"));
+  }
+  let target = target
+    .dereference_forced(context, context.client(), request_counter)
+    .await?;
+  let old_community: DbUrl = old_community.clone().into();
+  if !target.also_known_as.contains(&old_community) {
+    return Err(LemmyError::from_message("move_target_missing_alias"));
+  }
+  if target.moved_to.is_some() {
+    return Err(LemmyError::from_message("community_already_moved"));
+  }
+  Ok(target)
+}
+
+/// Freezes the old community with a pointer to the new one, and moves the follows of local users
+/// over. If the new community is remote, a follow activity is sent for each of them.
+async fn move_community_followers(
+  community: &ApubCommunity,
+  target: &ApubCommunity,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let (community_id, target_id) = (community.id, target.id);
+  let target_actor_id = target.actor_id.clone();
+  let pending = !target.local;
+  let followers = blocking(context.pool(), move |conn| {
+    Community::set_moved_to(conn, community_id, target_actor_id)?;
+    CommunityFollower::transfer_local_followers(conn, community_id, target_id, pending)?
+      .into_iter()
+      .map(|person_id| Person::read(conn, person_id))
+      .collect::<Result<Vec<Person>, _>>()
+  })
+  .await??;
+
+  if !target.local {
+    for follower in followers {
+      FollowCommunity::send(&follower.into(), target, context).await?;
+    }
+  }
+
+  send_community_ws_message(
+    community_id,
+    UserOperationCrud::EditCommunity,
+    None,
+    None,
+    context,
+  )
+  .await?;
+  Ok(())
+}
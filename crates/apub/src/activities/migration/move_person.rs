@@ -0,0 +1,135 @@
+use crate::{
+  activities::{generate_activity_id, send_lemmy_activity, verify_activity, verify_person},
+  objects::person::ApubPerson,
+  protocol::activities::migration::move_person::MovePerson,
+};
+use activitystreams_kinds::activity::MoveType;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType},
+  verify::verify_urls_match,
+};
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::{community::CommunityFollower, person::Person},
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use url::Url;
+
+impl MovePerson {
+  /// Moves a local account to `target`, which needs to list the local account in `alsoKnownAs`.
+  /// Follows of local communities are transferred directly, and the instances of followed remote
+  /// communities are informed so that they can do the same.
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    actor: &ApubPerson,
+    target: ObjectId<ApubPerson>,
+    context: &LemmyContext,
+  ) -> Result<ApubPerson, LemmyError> {
+    if actor.moved_to.is_some() {
+      return Err(LemmyError::from_message("account_already_moved"));
+    }
+    let target = verify_move_target(&actor.actor_id(), &target, context, &mut 0).await?;
+    move_account(actor, &target, context).await?;
+
+    let id = generate_activity_id(
+      MoveType::Move,
+      &context.settings().get_protocol_and_hostname(),
+    )?;
+    let move_person = MovePerson {
+      actor: ObjectId::new(actor.actor_id()),
+      object: ObjectId::new(actor.actor_id()),
+      target: ObjectId::new(target.actor_id()),
+      kind: MoveType::Move,
+      id: id.clone(),
+      unparsed: Default::default(),
+    };
+
+    let person_id = actor.id;
+    let inboxes = blocking(context.pool(), move |conn| {
+      CommunityFollower::remote_community_inboxes(conn, person_id)
+    })
+    .await??
+    .into_iter()
+    .map(Into::into)
+    .collect();
+    send_lemmy_activity(context, &move_person, &id, actor, inboxes, false).await?;
+    Ok(target)
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for MovePerson {
+  type DataType = LemmyContext;
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    // Accounts can only move themselves
+    verify_urls_match(self.actor.inner(), self.object.inner())?;
+    verify_person(&self.actor, context, request_counter).await?;
+    verify_move_target(self.actor.inner(), &self.target, context, request_counter).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let person = self
+      .object
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let target = self
+      .target
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    move_account(&person, &target, context).await
+  }
+}
+
+/// Fetches the latest version of the target account, and checks that it lists the moving account
+/// as alias. Otherwise anyone could take over the followers of another account.
+async fn verify_move_target(
+  old_account: &Url,
+  target: &ObjectId<ApubPerson>,
+  context: &LemmyContext,
+  request_counter: &mut i32,
+) -> Result<ApubPerson, LemmyError> {
+  if target.inner() == old_account {
+    return Err(LemmyError::from_message("cant_move_to_same_account"));
+  }
+  let target = target
+    .dereference_forced(context, context.client(), request_counter)
+    .await?;
+  let old_account: DbUrl = old_account.clone().into();
+  if !target.also_known_as.contains(&old_account) {
+    return Err(LemmyError::from_message("move_target_missing_alias"));
+  }
+  Ok(target)
+}
+
+/// Marks the account as moved, and transfers its follows of local communities to the new account.
+async fn move_account(
+  person: &ApubPerson,
+  target: &ApubPerson,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let (person_id, target_id) = (person.id, target.id);
+  let target_actor_id = target.actor_id.clone();
+  blocking(context.pool(), move |conn| {
+    Person::set_moved_to(conn, person_id, target_actor_id)?;
+    CommunityFollower::transfer_local_follows(conn, person_id, target_id)
+  })
+  .await??;
+  Ok(())
+}
@@ -7,6 +7,7 @@ use crate::{
 };
 use activitystreams_kinds::public;
 use anyhow::anyhow;
+use itertools::Itertools;
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::{
   activity_queue::send_activity,
@@ -14,14 +15,19 @@ use lemmy_apub_lib::{
   traits::ActorType,
   verify::verify_domains_match,
 };
-use lemmy_db_schema::source::community::Community;
+use lemmy_db_schema::{
+  newtypes::DbUrl,
+  source::{community::Community, instance_delivery::InstanceDelivery, person::Person},
+  DbPool,
+};
 use lemmy_db_views_actor::{
   community_person_ban_view::CommunityPersonBanView,
   community_view::CommunityView,
 };
-use lemmy_utils::{settings::structs::Settings, LemmyError};
+use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::Serialize;
+use std::collections::HashMap;
 use tracing::info;
 use url::{ParseError, Url};
 use uuid::Uuid;
@@ -31,6 +37,10 @@ pub mod community;
 pub mod create_or_update;
 pub mod deletion;
 pub mod following;
+pub mod instance;
+pub mod migration;
+pub mod poll;
+pub mod reaction;
 pub mod voting;
 
 /// Checks that the specified Url actually identifies a Person (by fetching it), and that the person
@@ -77,8 +87,8 @@ pub(crate) async fn verify_person_in_community(
   Ok(())
 }
 
-fn verify_activity(id: &Url, actor: &Url, settings: &Settings) -> Result<(), LemmyError> {
-  check_is_apub_id_valid(actor, false, settings)?;
+fn verify_activity(id: &Url, actor: &Url, context: &LemmyContext) -> Result<(), LemmyError> {
+  check_is_apub_id_valid(actor, false, context)?;
   verify_domains_match(id, actor)?;
   Ok(())
 }
@@ -154,6 +164,10 @@ pub(crate) fn check_community_deleted_or_removed(community: &Community) -> Resul
     Err(LemmyError::from_message(
       "New post or comment cannot be created in deleted or removed community",
     ))
+  } else if community.moved_to.is_some() {
+    Err(LemmyError::from_message(
+      "New post or comment cannot be created in moved community",
+    ))
   } else {
     Ok(())
   }
@@ -193,10 +207,17 @@ async fn send_lemmy_activity<T: Serialize>(
   // Don't send anything to ourselves
   // TODO: this should be a debug assert
   let hostname = context.settings().get_hostname_without_port()?;
-  let inboxes: Vec<&Url> = inboxes
-    .iter()
-    .filter(|i| i.domain().expect("valid inbox url") != hostname)
+  // Nor to instances which are known to be dead
+  let suspended = blocking(context.pool(), InstanceDelivery::suspended_domains).await??;
+  let inboxes: Vec<Url> = inboxes
+    .into_iter()
+    .filter(|i| {
+      let domain = i.domain().expect("valid inbox url");
+      domain != hostname && !suspended.iter().any(|s| s == domain)
+    })
     .collect();
+  let shared_inboxes = read_shared_inboxes(&inboxes, context.pool()).await?;
+  let inboxes = dedup_inboxes(inboxes, &shared_inboxes);
 
   let serialised_activity = serde_json::to_string(&activity)?;
 
@@ -206,10 +227,114 @@ async fn send_lemmy_activity<T: Serialize>(
   send_activity(
     activity_id,
     actor,
-    inboxes,
+    inboxes.iter().collect(),
     serialised_activity,
     context.client(),
     context.activity_queue(),
   )
   .await
 }
+
+/// For hosts which would receive the activity more than once, looks up the shared inboxes which
+/// the actors behind these inboxes advertise.
+async fn read_shared_inboxes(
+  inboxes: &[Url],
+  pool: &DbPool,
+) -> Result<HashMap<Url, Url>, LemmyError> {
+  let inboxes: Vec<DbUrl> = inboxes
+    .iter()
+    .filter(|i| inboxes.iter().filter(|j| j.host() == i.host()).count() > 1)
+    .map(|i| i.clone().into())
+    .collect();
+  if inboxes.is_empty() {
+    return Ok(HashMap::new());
+  }
+  let shared_inboxes = blocking(pool, move |conn| {
+    let mut shared_inboxes = Person::read_shared_inboxes(conn, inboxes.clone())?;
+    shared_inboxes.append(&mut Community::read_shared_inboxes(conn, inboxes)?);
+    Ok(shared_inboxes) as Result<_, diesel::result::Error>
+  })
+  .await??;
+  Ok(
+    shared_inboxes
+      .into_iter()
+      .filter_map(|(inbox, shared_inbox)| Some((inbox.into(), shared_inbox?.into())))
+      .collect(),
+  )
+}
+
+/// Makes sure that each remote instance receives the activity only once, if it has a shared
+/// inbox. Personal inboxes are replaced by the shared inbox of their actor, or by another shared
+/// inbox on the same host, as the receiving instance passes the activity on to all of the
+/// addressed local actors anyway. Hosts with a single inbox are left alone.
+fn dedup_inboxes(inboxes: Vec<Url>, shared_inboxes: &HashMap<Url, Url>) -> Vec<Url> {
+  let inboxes: Vec<Url> = inboxes.into_iter().unique().collect();
+  let mut host_shared_inboxes: HashMap<String, Url> = HashMap::new();
+  for shared_inbox in shared_inboxes.values() {
+    if let Some(host) = shared_inbox.host_str() {
+      host_shared_inboxes
+        .entry(host.to_string())
+        .or_insert_with(|| shared_inbox.clone());
+    }
+  }
+  let host_count = |host: Option<&str>| inboxes.iter().filter(|i| i.host_str() == host).count();
+
+  inboxes
+    .iter()
+    .map(|inbox| {
+      if host_count(inbox.host_str()) < 2 {
+        return inbox.clone();
+      }
+      shared_inboxes
+        .get(inbox)
+        .or_else(|| host_shared_inboxes.get(inbox.host_str()?))
+        .unwrap_or(inbox)
+        .clone()
+    })
+    .unique()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::dedup_inboxes;
+  use std::collections::HashMap;
+  use url::Url;
+
+  fn url(s: &str) -> Url {
+    Url::parse(s).unwrap()
+  }
+
+  #[test]
+  fn test_dedup_inboxes() {
+    let shared_inboxes = HashMap::from([
+      (
+        url("https://a.tld/u/alice/inbox"),
+        url("https://a.tld/inbox"),
+      ),
+      (
+        url("https://b.tld/u/carol/inbox"),
+        url("https://b.tld/inbox"),
+      ),
+    ]);
+    let inboxes = vec![
+      url("https://a.tld/u/alice/inbox"),
+      url("https://a.tld/u/bob/inbox"),
+      url("https://a.tld/inbox"),
+      url("https://b.tld/u/carol/inbox"),
+      url("https://c.tld/u/dave/inbox"),
+      url("https://c.tld/u/dave/inbox"),
+    ];
+
+    let deduped = dedup_inboxes(inboxes, &shared_inboxes);
+    assert_eq!(
+      vec![
+        url("https://a.tld/inbox"),
+        // single inbox on this host, so it stays untouched
+        url("https://b.tld/u/carol/inbox"),
+        url("https://c.tld/u/dave/inbox"),
+      ],
+      deduped
+    );
+  }
+}
@@ -0,0 +1,132 @@
+use crate::{
+  activities::{
+    generate_activity_id,
+    poll::vote_in_poll,
+    send_lemmy_activity,
+    verify_activity,
+    verify_person_in_community,
+  },
+  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
+  protocol::activities::poll::create_poll_answer::{CreatePollAnswer, PollAnswer},
+};
+use activitystreams_kinds::{activity::CreateType, object::NoteType};
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType},
+  verify::verify_domains_match,
+};
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    person::Person,
+    poll::{Poll, PollOption},
+  },
+  traits::Crud,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperation};
+use url::Url;
+
+impl CreatePollAnswer {
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    post: &ApubPost,
+    actor: &ApubPerson,
+    options: Vec<PollOption>,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let creator_id = post.creator_id;
+    let creator: ApubPerson = blocking(context.pool(), move |conn| Person::read(conn, creator_id))
+      .await??
+      .into();
+
+    for option in options {
+      let id = generate_activity_id(
+        CreateType::Create,
+        &context.settings().get_protocol_and_hostname(),
+      )?;
+      let answer = CreatePollAnswer {
+        actor: ObjectId::new(actor.actor_id()),
+        to: [ObjectId::new(creator.actor_id())],
+        object: PollAnswer {
+          r#type: NoteType::Note,
+          id: Url::parse(&format!("{}#votes/{}", actor.actor_id(), option.id.0))?,
+          attributed_to: ObjectId::new(actor.actor_id()),
+          to: [ObjectId::new(creator.actor_id())],
+          name: option.name,
+          in_reply_to: ObjectId::new(post.ap_id.clone()),
+        },
+        kind: CreateType::Create,
+        id: id.clone(),
+        unparsed: Default::default(),
+      };
+      let inbox = vec![creator.shared_inbox_or_inbox_url()];
+      send_lemmy_activity(context, &answer, &id, actor, inbox, true).await?;
+    }
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for CreatePollAnswer {
+  type DataType = LemmyContext;
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    verify_domains_match(self.actor.inner(), &self.object.id)?;
+    verify_domains_match(self.actor.inner(), self.object.attributed_to.inner())?;
+
+    // Votes are only counted by the instance of the poll author
+    let post = self.object.in_reply_to.dereference_local(context).await?;
+    if !post.local {
+      return Err(LemmyError::from_message("Poll is not local"));
+    }
+    let community_id = post.community_id;
+    let community: ApubCommunity = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??
+    .into();
+    verify_person_in_community(&self.actor, &community, context, request_counter).await?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let actor = self
+      .actor
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let post = self.object.in_reply_to.dereference_local(context).await?;
+
+    let post_id = post.id;
+    let name = self.object.name;
+    blocking(context.pool(), move |conn| -> Result<(), LemmyError> {
+      let poll = Poll::read_for_post(conn, post_id)?
+        .ok_or_else(|| LemmyError::from_message("post_has_no_poll"))?;
+      let option = PollOption::list_for_poll(conn, poll.id)?
+        .into_iter()
+        .find(|o| o.name == name)
+        .ok_or_else(|| LemmyError::from_message("invalid_poll_vote"))?;
+      vote_in_poll(conn, &poll, &[option.id], actor.id)?;
+      // The results are sent to other instances by a scheduled task
+      Poll::mark_results_pending(conn, poll.id)?;
+      Ok(())
+    })
+    .await??;
+
+    send_post_ws_message(post.id, UserOperation::CreatePollVote, None, None, context).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,92 @@
+use crate::{
+  objects::{person::ApubPerson, post::ApubPost},
+  protocol::activities::{create_or_update::post::CreateOrUpdatePost, CreateOrUpdateType},
+};
+use diesel::PgConnection;
+use lemmy_api_common::blocking;
+use lemmy_db_schema::{
+  newtypes::{PersonId, PollOptionId, PostId},
+  source::{
+    person::Person,
+    poll::{Poll, PollOption, PollVote, PollVoteForm},
+    post::Post,
+  },
+  traits::Crud,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use tracing::warn;
+
+pub mod create_poll_answer;
+
+/// Stores the votes of a person in a poll, after checking that the poll is still open and that
+/// the options are valid. Used for local votes as well as answers from other instances.
+pub fn vote_in_poll(
+  conn: &PgConnection,
+  poll: &Poll,
+  option_ids: &[PollOptionId],
+  person_id: PersonId,
+) -> Result<(), LemmyError> {
+  if poll.is_closed() {
+    return Err(LemmyError::from_message("poll_closed"));
+  }
+  if option_ids.is_empty() || (!poll.multiple_choice && option_ids.len() > 1) {
+    return Err(LemmyError::from_message("invalid_poll_vote"));
+  }
+  let options = PollOption::list_for_poll(conn, poll.id)?;
+  if !option_ids
+    .iter()
+    .all(|id| options.iter().any(|o| &o.id == id))
+  {
+    return Err(LemmyError::from_message("invalid_poll_vote"));
+  }
+
+  // Other instances send one answer per option for multiple choice polls, so only reject options
+  // which were chosen before.
+  let previous_votes = PollVote::list_for_person(conn, poll.id, person_id)?;
+  let already_voted = if poll.multiple_choice {
+    option_ids.iter().any(|id| previous_votes.contains(id))
+  } else {
+    !previous_votes.is_empty()
+  };
+  if already_voted {
+    return Err(LemmyError::from_message("already_voted_in_poll"));
+  }
+
+  for option_id in option_ids {
+    let form = PollVoteForm {
+      poll_option_id: *option_id,
+      person_id,
+    };
+    PollVote::vote(conn, &form)
+      .map_err(|e| LemmyError::from_error_message(e, "couldnt_vote_in_poll"))?;
+  }
+  Ok(())
+}
+
+/// Sends the posts of local polls which got new votes, so that other instances can show the
+/// current results. Called periodically, so that a busy poll doesn't cause an update for every
+/// vote. Only the poll author can do this, so the updates are signed by the post creators.
+#[tracing::instrument(skip_all)]
+pub async fn send_pending_poll_results(context: &LemmyContext) -> Result<(), LemmyError> {
+  let polls = blocking(context.pool(), Poll::take_pending_results).await??;
+  for poll in polls {
+    let post_id = poll.post_id;
+    if let Err(e) = send_poll_results(post_id, context).await {
+      warn!("Failed to send results of poll in post {}: {}", post_id, e);
+    }
+  }
+  Ok(())
+}
+
+async fn send_poll_results(post_id: PostId, context: &LemmyContext) -> Result<(), LemmyError> {
+  let (post, creator) = blocking(context.pool(), move |conn| -> Result<_, LemmyError> {
+    let post = Post::read(conn, post_id)?;
+    let creator = Person::read(conn, post.creator_id)?;
+    Ok((post, creator))
+  })
+  .await??;
+  let post: ApubPost = post.into();
+  let creator: ApubPerson = creator.into();
+  CreateOrUpdatePost::send(post, &creator, CreateOrUpdateType::Update, context).await
+}
@@ -0,0 +1,130 @@
+use crate::{
+  activities::{
+    community::{announce::GetCommunity, send_activity_in_community},
+    generate_activity_id,
+    reaction::apply_reaction,
+    verify_activity,
+    verify_person_in_community,
+  },
+  activity_lists::AnnouncableActivities,
+  objects::{community::ApubCommunity, person::ApubPerson},
+  protocol::activities::reaction::emoji_react::{EmojiReact, EmojiReactType},
+  PostOrComment,
+};
+use activitystreams_kinds::public;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ActorType},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{community::Community, post::Post},
+  traits::Crud,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+
+impl EmojiReact {
+  pub(in crate::activities::reaction) fn new(
+    object: &PostOrComment,
+    actor: &ApubPerson,
+    community: &ApubCommunity,
+    emoji: String,
+    context: &LemmyContext,
+  ) -> Result<EmojiReact, LemmyError> {
+    let kind = EmojiReactType::EmojiReact;
+    Ok(EmojiReact {
+      actor: ObjectId::new(actor.actor_id()),
+      to: vec![community.actor_id()],
+      object: ObjectId::new(object.ap_id()),
+      cc: vec![public()],
+      content: emoji,
+      kind: kind.clone(),
+      id: generate_activity_id(kind, &context.settings().get_protocol_and_hostname())?,
+      unparsed: Default::default(),
+    })
+  }
+
+  #[tracing::instrument(skip_all)]
+  pub async fn send(
+    object: &PostOrComment,
+    actor: &ApubPerson,
+    community_id: CommunityId,
+    emoji: String,
+    context: &LemmyContext,
+  ) -> Result<(), LemmyError> {
+    let community = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??
+    .into();
+    let react = EmojiReact::new(object, actor, &community, emoji, context)?;
+    let react_id = react.id.clone();
+
+    let activity = AnnouncableActivities::EmojiReact(react);
+    send_activity_in_community(activity, &react_id, actor, &community, vec![], context).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl ActivityHandler for EmojiReact {
+  type DataType = LemmyContext;
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    &self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_activity(&self.id, self.actor.inner(), context)?;
+    let community = self.get_community(context, request_counter).await?;
+    verify_person_in_community(&self.actor, &community, context, request_counter).await?;
+    // The emoji itself is checked when applying the reaction, an unsupported one (eg a remote
+    // custom emoji from Misskey) only gets that reaction ignored
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn receive(
+    self,
+    context: &Data<LemmyContext>,
+    request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    let actor = self
+      .actor
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let object = self
+      .object
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    apply_reaction(true, self.content, actor, object, context).await
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl GetCommunity for EmojiReact {
+  #[tracing::instrument(skip_all)]
+  async fn get_community(
+    &self,
+    context: &LemmyContext,
+    request_counter: &mut i32,
+  ) -> Result<ApubCommunity, LemmyError> {
+    let object = self
+      .object
+      .dereference(context, context.client(), request_counter)
+      .await?;
+    let cid = match object {
+      PostOrComment::Post(p) => p.community_id,
+      PostOrComment::Comment(c) => {
+        blocking(context.pool(), move |conn| Post::read(conn, c.post_id))
+          .await??
+          .community_id
+      }
+    };
+    let community = blocking(context.pool(), move |conn| Community::read(conn, cid)).await??;
+    Ok(community.into())
+  }
+}
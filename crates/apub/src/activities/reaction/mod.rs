@@ -0,0 +1,116 @@
+use crate::{
+  objects::{comment::ApubComment, person::ApubPerson, post::ApubPost},
+  PostOrComment,
+};
+use lemmy_api_common::{blocking, reaction_limit_reached};
+use lemmy_db_schema::{
+  source::{
+    comment::{CommentReaction, CommentReactionForm},
+    post::{PostReaction, PostReactionForm},
+  },
+  traits::Reactable,
+};
+use lemmy_utils::{utils::is_valid_reaction, LemmyError};
+use lemmy_websocket::{
+  send::{send_comment_ws_message_simple, send_post_ws_message},
+  LemmyContext,
+  UserOperation,
+};
+use tracing::debug;
+
+pub mod emoji_react;
+pub mod undo_emoji_react;
+
+/// Adds or removes the reaction of a remote person. Reactions which Lemmy doesn't support, or which
+/// go over the limit per person, are ignored without rejecting the activity.
+#[tracing::instrument(skip_all)]
+async fn apply_reaction(
+  react: bool,
+  emoji: String,
+  actor: ApubPerson,
+  object: PostOrComment,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  if react && !is_valid_reaction(&emoji) {
+    debug!(
+      "Ignoring unsupported reaction {} by {}",
+      emoji, actor.actor_id
+    );
+    return Ok(());
+  }
+  match object {
+    PostOrComment::Post(post) => react_post(react, emoji, actor, &post, context).await,
+    PostOrComment::Comment(comment) => react_comment(react, emoji, actor, &comment, context).await,
+  }
+}
+
+async fn react_post(
+  react: bool,
+  emoji: String,
+  actor: ApubPerson,
+  post: &ApubPost,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let form = PostReactionForm {
+    post_id: post.id,
+    person_id: actor.id,
+    emoji,
+  };
+  if react && reaction_limit_reached::<PostReaction>(form.clone(), context.pool()).await? {
+    debug!(
+      "Ignoring reaction by {}, who reached the limit",
+      actor.actor_id
+    );
+    return Ok(());
+  }
+  blocking(context.pool(), move |conn| {
+    if react {
+      PostReaction::react(conn, &form).map(|_| ())
+    } else {
+      PostReaction::unreact(conn, &form).map(|_| ())
+    }
+  })
+  .await??;
+
+  send_post_ws_message(
+    post.id,
+    UserOperation::CreatePostReaction,
+    None,
+    None,
+    context,
+  )
+  .await?;
+  Ok(())
+}
+
+async fn react_comment(
+  react: bool,
+  emoji: String,
+  actor: ApubPerson,
+  comment: &ApubComment,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let form = CommentReactionForm {
+    comment_id: comment.id,
+    person_id: actor.id,
+    emoji,
+  };
+  if react && reaction_limit_reached::<CommentReaction>(form.clone(), context.pool()).await? {
+    debug!(
+      "Ignoring reaction by {}, who reached the limit",
+      actor.actor_id
+    );
+    return Ok(());
+  }
+  blocking(context.pool(), move |conn| {
+    if react {
+      CommentReaction::react(conn, &form).map(|_| ())
+    } else {
+      CommentReaction::unreact(conn, &form).map(|_| ())
+    }
+  })
+  .await??;
+
+  send_comment_ws_message_simple(comment.id, UserOperation::CreateCommentReaction, context).await?;
+  Ok(())
+}
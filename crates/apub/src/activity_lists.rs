@@ -7,8 +7,10 @@ use crate::{
       community::{
         add_mod::AddMod,
         announce::AnnounceActivity,
+        forward_report::ForwardReport,
         remove_mod::RemoveMod,
         report::Report,
+        resolve_report::ResolveReport,
         update::UpdateCommunity,
       },
       create_or_update::{
@@ -22,6 +24,10 @@ use crate::{
         follow::FollowCommunity,
         undo_follow::UndoFollowCommunity,
       },
+      instance::update::UpdateInstance,
+      migration::{move_community::MoveCommunity, move_person::MovePerson},
+      poll::create_poll_answer::CreatePollAnswer,
+      reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
       voting::{undo_vote::UndoVote, vote::Vote},
     },
     objects::page::Page,
@@ -36,6 +42,9 @@ use serde::{Deserialize, Serialize};
 #[serde(untagged)]
 #[activity_handler(LemmyContext)]
 pub enum SharedInboxActivities {
+  // Poll answers need to be first, otherwise they are parsed as post
+  CreatePollAnswer(Box<CreatePollAnswer>),
+  /// Also contains account moves, which are sent to the shared inbox of followed communities
   GroupInboxActivities(Box<GroupInboxActivities>),
   // Note, pm activities need to be at the end, otherwise comments will end up here. We can probably
   // avoid this problem by replacing createpm.object with our own struct, instead of NoteExt.
@@ -50,6 +59,7 @@ pub enum GroupInboxActivities {
   UndoFollowCommunity(UndoFollowCommunity),
   AnnouncableActivities(Box<AnnouncableActivities>),
   Report(Report),
+  MovePerson(MovePerson),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, ActivityHandler)]
@@ -57,12 +67,19 @@ pub enum GroupInboxActivities {
 #[activity_handler(LemmyContext)]
 pub enum PersonInboxActivities {
   AcceptFollowCommunity(AcceptFollowCommunity),
+  /// Needs to be before AnnouncableActivities, where the answer would be parsed as post
+  CreatePollAnswer(CreatePollAnswer),
   /// Some activities can also be sent from user to user, eg a comment with mentions
   AnnouncableActivities(AnnouncableActivities),
+  /// Needs to be after AnnouncableActivities, otherwise community moves are parsed as account move
+  MovePerson(MovePerson),
   CreateOrUpdatePrivateMessage(CreateOrUpdatePrivateMessage),
   Delete(Delete),
   UndoDelete(UndoDelete),
   AnnounceActivity(AnnounceActivity),
+  /// Reports which are forwarded to the instance of the author, and report resolutions
+  ForwardReport(ForwardReport),
+  ResolveReport(ResolveReport),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, ActivityHandler)]
@@ -71,6 +88,9 @@ pub enum PersonInboxActivities {
 pub enum AnnouncableActivities {
   CreateOrUpdateComment(CreateOrUpdateComment),
   CreateOrUpdatePost(Box<CreateOrUpdatePost>),
+  // Reactions need to be before votes, as a Like with content would also be parsed as vote
+  EmojiReact(EmojiReact),
+  UndoEmojiReact(UndoEmojiReact),
   Vote(Vote),
   UndoVote(UndoVote),
   Delete(Delete),
@@ -80,6 +100,7 @@ pub enum AnnouncableActivities {
   UndoBlockUser(UndoBlockUser),
   AddMod(AddMod),
   RemoveMod(RemoveMod),
+  MoveCommunity(MoveCommunity),
   // For compatibility with Pleroma/Mastodon (send only)
   Page(Page),
 }
@@ -92,6 +113,7 @@ pub enum SiteInboxActivities {
   BlockUser(BlockUser),
   UndoBlockUser(UndoBlockUser),
   DeleteUser(DeleteUser),
+  UpdateInstance(UpdateInstance),
 }
 
 #[async_trait::async_trait(?Send)]
@@ -106,6 +128,8 @@ impl GetCommunity for AnnouncableActivities {
     let community = match self {
       CreateOrUpdateComment(a) => a.get_community(context, request_counter).await?,
       CreateOrUpdatePost(a) => a.get_community(context, request_counter).await?,
+      EmojiReact(a) => a.get_community(context, request_counter).await?,
+      UndoEmojiReact(a) => a.get_community(context, request_counter).await?,
       Vote(a) => a.get_community(context, request_counter).await?,
       UndoVote(a) => a.get_community(context, request_counter).await?,
       Delete(a) => a.get_community(context, request_counter).await?,
@@ -115,6 +139,7 @@ impl GetCommunity for AnnouncableActivities {
       UndoBlockUser(a) => a.get_community(context, request_counter).await?,
       AddMod(a) => a.get_community(context, request_counter).await?,
       RemoveMod(a) => a.get_community(context, request_counter).await?,
+      MoveCommunity(a) => a.get_community(context, request_counter).await?,
       Page(_) => unimplemented!(),
     };
     Ok(community)
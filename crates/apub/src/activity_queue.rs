@@ -0,0 +1,251 @@
+use background_jobs::dev::{JobInfo, Stats, Storage};
+use chrono::NaiveDateTime;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::activity_queue::InstanceHealth;
+use lemmy_db_schema::{
+  naive_now,
+  source::{
+    activity_queue::{ActivityQueueJob, ActivityQueueJobForm, ActivityQueueStats},
+    instance_delivery::InstanceDelivery,
+  },
+  DbPool,
+};
+use lemmy_utils::{settings::structs::Settings, LemmyError};
+use serde_json::Value;
+use thiserror::Error;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Error)]
+#[error("Activity queue storage error, {0}")]
+pub struct StorageError(pub String);
+
+impl From<LemmyError> for StorageError {
+  fn from(e: LemmyError) -> Self {
+    StorageError(e.to_string())
+  }
+}
+
+impl From<diesel::result::Error> for StorageError {
+  fn from(e: diesel::result::Error) -> Self {
+    StorageError(e.to_string())
+  }
+}
+
+impl From<serde_json::Error> for StorageError {
+  fn from(e: serde_json::Error) -> Self {
+    StorageError(e.to_string())
+  }
+}
+
+/// Keeps the outgoing activity queue in the database instead of in memory. This way activities
+/// which haven't been delivered yet are not lost on restart, and retries continue with the
+/// backoff they had reached before.
+#[derive(Clone)]
+pub struct ActivityQueueStorage {
+  pool: DbPool,
+}
+
+impl ActivityQueueStorage {
+  /// Jobs which were being processed when the server stopped are released, so that they are
+  /// picked up again by the new workers.
+  pub async fn new(pool: DbPool) -> Result<Self, LemmyError> {
+    let released = blocking(&pool, ActivityQueueJob::release_all).await??;
+    if released > 0 {
+      info!("Released {} interrupted activity deliveries", released);
+    }
+    Ok(ActivityQueueStorage { pool })
+  }
+}
+
+#[async_trait::async_trait]
+impl Storage for ActivityQueueStorage {
+  type Error = StorageError;
+
+  async fn generate_id(&self) -> Result<Uuid, Self::Error> {
+    Ok(Uuid::new_v4())
+  }
+
+  async fn save_job(&self, job: JobInfo) -> Result<(), Self::Error> {
+    let value = serde_json::to_value(&job)?;
+    let form = ActivityQueueJobForm {
+      id: job.id().to_string(),
+      queue: job.queue().to_owned(),
+      next_queue: Some(next_queue(&value)?),
+      job: value,
+      updated: naive_now(),
+    };
+    blocking(&self.pool, move |conn| {
+      ActivityQueueJob::upsert(conn, &form)
+    })
+    .await??;
+    Ok(())
+  }
+
+  async fn fetch_job(&self, id: Uuid) -> Result<Option<JobInfo>, Self::Error> {
+    let id = id.to_string();
+    let job = blocking(&self.pool, move |conn| ActivityQueueJob::read(conn, &id)).await??;
+    Ok(job.map(|j| serde_json::from_value(j.job)).transpose()?)
+  }
+
+  /// Claims the job in the database right away, so that no other worker can take it as well. The
+  /// actual runner id is only known later, and is set by `run_job`.
+  async fn fetch_job_from_queue(&self, queue: &str) -> Result<Option<JobInfo>, Self::Error> {
+    let queue_name = queue.to_owned();
+    let claim_id = Uuid::new_v4().to_string();
+    let claimed = blocking(&self.pool, move |conn| {
+      ActivityQueueJob::claim_next(conn, &queue_name, &claim_id)
+    })
+    .await??;
+
+    let job: JobInfo = match claimed {
+      Some(j) => serde_json::from_value(j.job)?,
+      None => return Ok(None),
+    };
+    let now = OffsetDateTime::now_utc();
+    if job.is_pending(now) && job.is_ready(now) && job.is_in_queue(queue) {
+      Ok(Some(job))
+    } else {
+      self.queue_job(queue, job.id()).await?;
+      Ok(None)
+    }
+  }
+
+  async fn queue_job(&self, queue: &str, id: Uuid) -> Result<(), Self::Error> {
+    let queue = queue.to_owned();
+    let id = id.to_string();
+    blocking(&self.pool, move |conn| {
+      ActivityQueueJob::enqueue(conn, &id, &queue)
+    })
+    .await??;
+    Ok(())
+  }
+
+  async fn run_job(&self, id: Uuid, runner_id: Uuid) -> Result<(), Self::Error> {
+    let id = id.to_string();
+    let runner_id = runner_id.to_string();
+    blocking(&self.pool, move |conn| {
+      ActivityQueueJob::claim(conn, &id, &runner_id)
+    })
+    .await??;
+    Ok(())
+  }
+
+  async fn delete_job(&self, id: Uuid) -> Result<(), Self::Error> {
+    let id = id.to_string();
+    blocking(&self.pool, move |conn| ActivityQueueJob::delete(conn, &id)).await??;
+    Ok(())
+  }
+
+  async fn get_stats(&self) -> Result<Stats, Self::Error> {
+    let (stats, pending, running) = blocking(&self.pool, move |conn| {
+      Ok((
+        ActivityQueueStats::read(conn)?,
+        ActivityQueueJob::count_pending(conn)?,
+        ActivityQueueJob::count_running(conn)?,
+      )) as Result<_, diesel::result::Error>
+    })
+    .await??;
+
+    let mut stats: Stats = match stats {
+      Some(s) => serde_json::from_value(s)?,
+      None => Stats::default(),
+    };
+    // The counters for queued jobs are taken from the job table, as they would drift across
+    // restarts otherwise
+    stats.pending = pending as usize;
+    stats.running = running as usize;
+    Ok(stats)
+  }
+
+  async fn update_stats<F>(&self, f: F) -> Result<(), Self::Error>
+  where
+    F: Fn(Stats) -> Stats + Send + 'static,
+  {
+    blocking(&self.pool, move |conn| {
+      ActivityQueueStats::update(conn, |current| {
+        let stats = match current {
+          Some(s) => serde_json::from_value(s)?,
+          None => Stats::default(),
+        };
+        Ok(serde_json::to_value(f(stats))?) as Result<_, StorageError>
+      })
+    })
+    .await??;
+    Ok(())
+  }
+}
+
+/// The time after which a failed job may be retried. It is read from the serialized job, because
+/// `JobInfo` doesn't expose it.
+fn next_queue(job: &Value) -> Result<Option<NaiveDateTime>, serde_json::Error> {
+  let next_queue: Option<OffsetDateTime> =
+    serde_json::from_value(job.get("next_queue").cloned().unwrap_or_default())?;
+  Ok(next_queue.map(|t| NaiveDateTime::from_timestamp(t.unix_timestamp(), t.nanosecond())))
+}
+
+/// Stores delivery results per remote instance in the database, and suspends deliveries to
+/// instances which keep failing.
+pub struct InstanceHealthTracker {
+  pool: DbPool,
+  failure_threshold: i32,
+  min_downtime: i64,
+  probe_interval: i64,
+}
+
+impl InstanceHealthTracker {
+  pub fn new(pool: DbPool, settings: &Settings) -> Self {
+    InstanceHealthTracker {
+      pool,
+      failure_threshold: settings.federation.dead_instance_failure_threshold,
+      min_downtime: settings.federation.dead_instance_min_downtime,
+      probe_interval: settings.federation.dead_instance_probe_interval,
+    }
+  }
+}
+
+#[async_trait::async_trait(?Send)]
+impl InstanceHealth for InstanceHealthTracker {
+  async fn should_deliver(&self, domain: &str) -> bool {
+    let domain = domain.to_owned();
+    let suspended = blocking(&self.pool, move |conn| {
+      InstanceDelivery::is_suspended(conn, &domain)
+    })
+    .await
+    .and_then(|s| s.map_err(LemmyError::from));
+    match suspended {
+      Ok(suspended) => !suspended,
+      // Rather send too much than drop activities because of a database problem
+      Err(e) => {
+        warn!("Failed to read instance delivery status: {}", e);
+        true
+      }
+    }
+  }
+
+  async fn record_delivery(&self, domain: &str, reachable: bool) {
+    let domain = domain.to_owned();
+    let failure_threshold = self.failure_threshold;
+    let min_downtime = self.min_downtime;
+    let probe_interval = self.probe_interval;
+    let res = blocking(&self.pool, move |conn| {
+      if reachable {
+        InstanceDelivery::record_success(conn, &domain)
+      } else {
+        InstanceDelivery::record_failure(
+          conn,
+          &domain,
+          failure_threshold,
+          min_downtime,
+          probe_interval,
+        )
+      }
+    })
+    .await
+    .and_then(|r| r.map_err(LemmyError::from));
+    if let Err(e) = res {
+      warn!("Failed to store instance delivery status: {}", e);
+    }
+  }
+}
@@ -1,6 +1,7 @@
 use crate::{
   activity_lists::AnnouncableActivities,
-  collections::CommunityContext,
+  collections::{read_page_param, CommunityContext},
+  generate_outbox_page_url,
   generate_outbox_url,
   objects::post::ApubPost,
   protocol::{
@@ -21,8 +22,18 @@ use lemmy_db_schema::source::post::Post;
 use lemmy_utils::LemmyError;
 use url::Url;
 
+/// Number of posts on each page of the community outbox
+const COMMUNITY_OUTBOX_PAGE_SIZE: i64 = 20;
+
 #[derive(Clone, Debug)]
-pub(crate) struct ApubCommunityOutbox(Vec<ApubPost>);
+pub(crate) struct ApubCommunityOutbox {
+  page: i64,
+  pub(crate) posts: Vec<ApubPost>,
+  /// Link to the following page, if there are more posts
+  pub(crate) next: Option<Url>,
+  /// Collections with the comments of each received post, only for remote outboxes
+  pub(crate) replies: Vec<(ApubPost, Url)>,
+}
 
 #[async_trait::async_trait(?Send)]
 impl ApubObject for ApubCommunityOutbox {
@@ -36,20 +47,32 @@ impl ApubObject for ApubCommunityOutbox {
 
   #[tracing::instrument(skip_all)]
   async fn read_from_apub_id(
-    _object_id: Url,
+    object_id: Url,
     data: &Self::DataType,
   ) -> Result<Option<Self>, LemmyError> {
     // Only read from database if its a local community, otherwise fetch over http
     if data.0.local {
+      let page = read_page_param(&object_id);
       let community_id = data.0.id;
       let post_list: Vec<ApubPost> = blocking(data.1.pool(), move |conn| {
-        Post::list_for_community(conn, community_id)
+        Post::list_for_community(conn, community_id, page, COMMUNITY_OUTBOX_PAGE_SIZE)
       })
       .await??
       .into_iter()
       .map(Into::into)
       .collect();
-      Ok(Some(ApubCommunityOutbox(post_list)))
+      let next = if post_list.len() as i64 == COMMUNITY_OUTBOX_PAGE_SIZE {
+        let outbox_id = generate_outbox_url(&data.0.actor_id)?.into();
+        Some(generate_outbox_page_url(&outbox_id, page + 1)?)
+      } else {
+        None
+      };
+      Ok(Some(ApubCommunityOutbox {
+        page,
+        posts: post_list,
+        next,
+        replies: vec![],
+      }))
     } else {
       Ok(None)
     }
@@ -63,18 +86,26 @@ impl ApubObject for ApubCommunityOutbox {
   #[tracing::instrument(skip_all)]
   async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
     let mut ordered_items = vec![];
-    for post in self.0 {
+    for post in self.posts {
       let page = post.into_apub(&data.1).await?;
       let announcable = AnnouncableActivities::Page(page);
       let announce = AnnounceActivity::new(announcable, &data.0, &data.1)?;
       ordered_items.push(announce);
     }
 
+    // The first page is served at the plain outbox url, for compatibility
+    let outbox_id = generate_outbox_url(&data.0.actor_id)?.into();
+    let id = if self.page > 1 {
+      generate_outbox_page_url(&outbox_id, self.page)?
+    } else {
+      outbox_id
+    };
     Ok(GroupOutbox {
       r#type: OrderedCollectionType::OrderedCollection,
-      id: generate_outbox_url(&data.0.actor_id)?.into(),
+      id,
       total_items: ordered_items.len() as i32,
       ordered_items,
+      next: self.next,
     })
   }
 
@@ -98,33 +129,61 @@ impl ApubObject for ApubCommunityOutbox {
   async fn from_apub(
     apub: Self::ApubType,
     data: &Self::DataType,
-    _request_counter: &mut i32,
+    request_counter: &mut i32,
   ) -> Result<Self, LemmyError> {
     let mut outbox_activities = apub.ordered_items;
-    if outbox_activities.len() > 20 {
-      outbox_activities = outbox_activities[0..20].to_vec();
+    if outbox_activities.len() > COMMUNITY_OUTBOX_PAGE_SIZE as usize {
+      outbox_activities = outbox_activities[0..COMMUNITY_OUTBOX_PAGE_SIZE as usize].to_vec();
     }
+    let post_ids: Vec<(Url, Option<Url>)> = outbox_activities
+      .iter()
+      .filter_map(|a| match &a.object {
+        AnnouncableActivities::Page(p) => Some((p.id.inner().clone(), p.replies.clone())),
+        AnnouncableActivities::CreateOrUpdatePost(c) => {
+          Some((c.object.id.inner().clone(), c.object.replies.clone()))
+        }
+        _ => None,
+      })
+      .collect();
 
     // We intentionally ignore errors here. This is because the outbox might contain posts from old
     // Lemmy versions, or from other software which we cant parse. In that case, we simply skip the
     // item and only parse the ones that work.
     let data = Data::new(data.1.clone());
     // process items in parallel, to avoid long delay from fetch_site_metadata() and other processing
-    join_all(outbox_activities.into_iter().map(|activity| {
+    let item_requests = join_all(outbox_activities.into_iter().map(|activity| {
       async {
         // use separate request counter for each item, otherwise there will be problems with
         // parallel processing
-        let request_counter = &mut 0;
-        let verify = activity.verify(&data, request_counter).await;
+        let item_counter = &mut 0;
+        let verify = activity.verify(&data, item_counter).await;
         if verify.is_ok() {
-          activity.receive(&data, request_counter).await.ok();
+          activity.receive(&data, item_counter).await.ok();
         }
+        *item_counter
       }
     }))
     .await;
+    // Added up afterwards, so that a backfill can charge them to its budget
+    *request_counter += item_requests.iter().sum::<i32>();
 
-    // This return value is unused, so just set an empty vec
-    Ok(ApubCommunityOutbox(Vec::new()))
+    // Return the posts which were received, so that their comments can be backfilled
+    let mut posts = vec![];
+    let mut replies = vec![];
+    for (id, replies_id) in post_ids {
+      if let Some(post) = ApubPost::read_from_apub_id(id, &data).await? {
+        if let Some(replies_id) = replies_id {
+          replies.push((post.clone(), replies_id));
+        }
+        posts.push(post);
+      }
+    }
+    Ok(ApubCommunityOutbox {
+      page: 1,
+      posts,
+      next: apub.next,
+      replies,
+    })
   }
 
   type DbType = ();
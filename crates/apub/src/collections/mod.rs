@@ -1,9 +1,69 @@
+use lemmy_apub_lib::{
+  signatures::{FetchSigner, FetchSigning},
+  traits::InstanceSettings,
+};
+use lemmy_utils::settings::structs::Settings;
 use lemmy_websocket::LemmyContext;
+use url::Url;
 
-use crate::objects::community::ApubCommunity;
+use crate::objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost};
 
 pub(crate) mod community_moderators;
 pub(crate) mod community_outbox;
+pub(crate) mod person_outbox;
+pub(crate) mod post_replies;
 
 /// Put community in the data, so we dont have to read it again from the database.
 pub(crate) struct CommunityContext(pub ApubCommunity, pub LemmyContext);
+
+/// Put person in the data, so we dont have to read it again from the database.
+pub(crate) struct PersonContext(pub ApubPerson, pub LemmyContext);
+
+/// Put post in the data, so we dont have to read it again from the database.
+pub(crate) struct PostContext(pub ApubPost, pub LemmyContext);
+
+impl InstanceSettings for CommunityContext {
+  fn instance_settings(&self) -> Settings {
+    self.1.settings()
+  }
+}
+
+impl FetchSigning for CommunityContext {
+  fn fetch_signer(&self) -> &FetchSigner {
+    self.1.fetch_signer()
+  }
+}
+
+impl InstanceSettings for PersonContext {
+  fn instance_settings(&self) -> Settings {
+    self.1.settings()
+  }
+}
+
+impl FetchSigning for PersonContext {
+  fn fetch_signer(&self) -> &FetchSigner {
+    self.1.fetch_signer()
+  }
+}
+
+impl InstanceSettings for PostContext {
+  fn instance_settings(&self) -> Settings {
+    self.1.settings()
+  }
+}
+
+impl FetchSigning for PostContext {
+  fn fetch_signer(&self) -> &FetchSigner {
+    self.1.fetch_signer()
+  }
+}
+
+/// Reads the page number from an outbox page url, defaults to the first page.
+pub(crate) fn read_page_param(url: &Url) -> i64 {
+  url
+    .query_pairs()
+    .find(|(k, _)| k == "page")
+    .and_then(|(_, v)| v.parse::<i64>().ok())
+    .filter(|p| *p > 0)
+    .unwrap_or(1)
+}
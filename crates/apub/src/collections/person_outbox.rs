@@ -0,0 +1,383 @@
+use crate::{
+  activities::deletion::DeletableObjects,
+  activity_lists::AnnouncableActivities,
+  collections::{read_page_param, PersonContext},
+  generate_outbox_page_url,
+  generate_outbox_url,
+  objects::{comment::ApubComment, community::ApubCommunity, post::ApubPost},
+  protocol::{
+    activities::{
+      create_or_update::{comment::CreateOrUpdateComment, post::CreateOrUpdatePost},
+      deletion::delete::Delete,
+      CreateOrUpdateType,
+    },
+    collections::person_outbox::{PersonOutbox, PersonOutboxPage},
+  },
+};
+use activitystreams_kinds::{
+  collection::{OrderedCollectionPageType, OrderedCollectionType},
+  public,
+};
+use chrono::NaiveDateTime;
+use futures::future::join_all;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  data::Data,
+  object_id::ObjectId,
+  traits::{ActivityHandler, ApubObject},
+  verify::{verify_domains_match, verify_urls_match},
+};
+use lemmy_db_schema::{
+  newtypes::{PersonId, PostId},
+  source::{comment::Comment, community::Community, person::Person, post::Post},
+};
+use lemmy_utils::LemmyError;
+use std::{cmp::max, ops::Deref};
+use url::Url;
+
+/// Number of activities on each page of the person outbox
+const PERSON_OUTBOX_PAGE_SIZE: i64 = 20;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ApubPersonOutbox {
+  total_items: i64,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubPersonOutbox {
+  type DataType = PersonContext;
+  type TombstoneType = ();
+  type ApubType = PersonOutbox;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    None
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    _object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    // Only read from database if its a local person, otherwise fetch over http
+    if data.0.local {
+      let person_id = data.0.id;
+      let total_items = blocking(data.1.pool(), move |conn| {
+        Person::count_outbox_items(conn, person_id)
+      })
+      .await??;
+      Ok(Some(ApubPersonOutbox { total_items }))
+    } else {
+      Ok(None)
+    }
+  }
+
+  async fn delete(self, _data: &Self::DataType) -> Result<(), LemmyError> {
+    // do nothing (it gets deleted automatically with the person)
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    let id: Url = generate_outbox_url(&data.0.actor_id)?.into();
+    Ok(PersonOutbox {
+      r#type: OrderedCollectionType::OrderedCollection,
+      first: ObjectId::new(generate_outbox_page_url(&id, 1)?),
+      id,
+      total_items: self.total_items,
+    })
+  }
+
+  fn to_tombstone(&self) -> Result<Self::TombstoneType, LemmyError> {
+    // no tombstone for this, there is only a tombstone for the person
+    unimplemented!()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    outbox: &PersonOutbox,
+    expected_domain: &Url,
+    data: &PersonContext,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_domains_match(expected_domain, &outbox.id)?;
+    verify_urls_match(&generate_outbox_url(&data.0.actor_id)?.into(), &outbox.id)?;
+    Ok(())
+  }
+
+  /// Only fetches the first page, older activities are not needed to display the person.
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    apub
+      .first
+      .dereference(data, data.1.client(), request_counter)
+      .await?;
+    Ok(ApubPersonOutbox {
+      total_items: apub.total_items,
+    })
+  }
+
+  type DbType = ();
+}
+
+#[derive(Clone, Debug)]
+enum OutboxItem {
+  Post(ApubPost),
+  Comment(ApubComment),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ApubPersonOutboxPage {
+  page: i64,
+  has_next: bool,
+  /// Each post or comment together with the community it was posted in
+  items: Vec<(OutboxItem, ApubCommunity)>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubPersonOutboxPage {
+  type DataType = PersonContext;
+  type TombstoneType = ();
+  type ApubType = PersonOutboxPage;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    None
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    if !data.0.local {
+      return Ok(None);
+    }
+    let page = read_page_param(&object_id);
+    let person_id = data.0.id;
+    let total_items = blocking(data.1.pool(), move |conn| {
+      Person::count_outbox_items(conn, person_id)
+    })
+    .await??;
+    // Pages after the last one don't exist, except for the first page of an empty outbox
+    let last_page = max(
+      1,
+      (total_items + PERSON_OUTBOX_PAGE_SIZE - 1) / PERSON_OUTBOX_PAGE_SIZE,
+    );
+    if page > last_page {
+      return Ok(None);
+    }
+
+    let offset = (page - 1) * PERSON_OUTBOX_PAGE_SIZE;
+    let items = blocking(data.1.pool(), move |conn| {
+      read_outbox_items(conn, person_id, offset)
+    })
+    .await??;
+
+    Ok(Some(ApubPersonOutboxPage {
+      page,
+      has_next: page < last_page,
+      items,
+    }))
+  }
+
+  async fn delete(self, _data: &Self::DataType) -> Result<(), LemmyError> {
+    // do nothing (it gets deleted automatically with the person)
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    let mut ordered_items = vec![];
+    for (item, community) in self.items {
+      ordered_items.push(item_to_activity(item, &community, data).await?);
+    }
+
+    let outbox_id: Url = generate_outbox_url(&data.0.actor_id)?.into();
+    let next = if self.has_next {
+      Some(ObjectId::new(generate_outbox_page_url(
+        &outbox_id,
+        self.page + 1,
+      )?))
+    } else {
+      None
+    };
+    Ok(PersonOutboxPage {
+      r#type: OrderedCollectionPageType::OrderedCollectionPage,
+      id: generate_outbox_page_url(&outbox_id, self.page)?,
+      part_of: outbox_id,
+      next,
+      ordered_items,
+    })
+  }
+
+  fn to_tombstone(&self) -> Result<Self::TombstoneType, LemmyError> {
+    unimplemented!()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    page: &PersonOutboxPage,
+    expected_domain: &Url,
+    data: &PersonContext,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_domains_match(expected_domain, &page.id)?;
+    verify_urls_match(
+      &generate_outbox_url(&data.0.actor_id)?.into(),
+      &page.part_of,
+    )?;
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    _request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    let mut outbox_activities = apub.ordered_items;
+    if outbox_activities.len() > PERSON_OUTBOX_PAGE_SIZE as usize {
+      outbox_activities = outbox_activities[0..PERSON_OUTBOX_PAGE_SIZE as usize].to_vec();
+    }
+
+    // Errors are ignored, same as for the community outbox. Items which can't be parsed or
+    // verified are simply skipped.
+    let data = Data::new(data.1.clone());
+    join_all(outbox_activities.into_iter().map(|activity| {
+      async {
+        // use separate request counter for each item, otherwise there will be problems with
+        // parallel processing
+        let request_counter = &mut 0;
+        let verify = activity.verify(&data, request_counter).await;
+        if verify.is_ok() {
+          activity.receive(&data, request_counter).await.ok();
+        }
+      }
+    }))
+    .await;
+
+    // This return value is unused, so just set an empty page
+    Ok(ApubPersonOutboxPage {
+      page: 1,
+      has_next: false,
+      items: vec![],
+    })
+  }
+
+  type DbType = ();
+}
+
+/// Reads one page of outbox items, with the communities they belong to. Posts, comments and
+/// communities are each read with a single query.
+fn read_outbox_items(
+  conn: &diesel::PgConnection,
+  person_id: PersonId,
+  offset: i64,
+) -> Result<Vec<(OutboxItem, ApubCommunity)>, diesel::result::Error> {
+  let items = Person::list_outbox_items(conn, person_id, PERSON_OUTBOX_PAGE_SIZE, offset)?;
+  let comments = Comment::read_many(conn, items.iter().filter_map(|i| i.comment_id).collect())?;
+  let post_ids = items
+    .iter()
+    .filter_map(|i| i.post_id)
+    .chain(comments.iter().map(|c| c.post_id))
+    .collect();
+  let posts = Post::read_many(conn, post_ids)?;
+  let community_ids = posts.iter().map(|p| p.community_id).collect();
+  let communities = Community::read_many(conn, community_ids)?;
+
+  let community_of_post = |post_id: PostId| -> Option<ApubCommunity> {
+    let post = posts.iter().find(|p| p.id == post_id)?;
+    let community = communities.iter().find(|c| c.id == post.community_id)?;
+    Some(community.clone().into())
+  };
+  // Keep the order of the outbox items. Items which were purged in the meantime are skipped.
+  Ok(
+    items
+      .into_iter()
+      .filter_map(|item| match (item.post_id, item.comment_id) {
+        (Some(post_id), _) => {
+          let post = posts.iter().find(|p| p.id == post_id)?;
+          Some((
+            OutboxItem::Post(post.clone().into()),
+            community_of_post(post_id)?,
+          ))
+        }
+        (_, Some(comment_id)) => {
+          let comment = comments.iter().find(|c| c.id == comment_id)?;
+          Some((
+            OutboxItem::Comment(comment.clone().into()),
+            community_of_post(comment.post_id)?,
+          ))
+        }
+        _ => None,
+      })
+      .collect(),
+  )
+}
+
+/// Deleted posts and comments get a delete activity, removed ones are not part of the outbox.
+/// Everything else gets a create activity, or update if it was edited. The activity ids are
+/// derived from the object and its last change, so that they are the same each time the outbox is
+/// read.
+async fn item_to_activity(
+  item: OutboxItem,
+  community: &ApubCommunity,
+  data: &PersonContext,
+) -> Result<AnnouncableActivities, LemmyError> {
+  let (person, context) = (&data.0, &data.1);
+  let (object_id, published, updated, deleted) = match &item {
+    OutboxItem::Post(p) => (p.ap_id.clone(), p.published, p.updated, p.deleted),
+    OutboxItem::Comment(c) => (c.ap_id.clone(), c.published, c.updated, c.deleted),
+  };
+  let kind = if deleted {
+    "delete"
+  } else if updated.is_some() {
+    "update"
+  } else {
+    "create"
+  };
+  let id = Url::parse(&format!(
+    "{}#{}-{}",
+    object_id,
+    kind,
+    updated.unwrap_or(published).timestamp()
+  ))?;
+
+  if deleted {
+    let object = match item {
+      OutboxItem::Post(p) => DeletableObjects::Post(Box::new(p)),
+      OutboxItem::Comment(c) => DeletableObjects::Comment(Box::new(c)),
+    };
+    let mut activity = Delete::new(
+      person,
+      object,
+      public(),
+      Some(community.deref()),
+      None,
+      context,
+    )?;
+    activity.id = id;
+    return Ok(AnnouncableActivities::Delete(activity));
+  }
+
+  let kind = if updated.is_some() {
+    CreateOrUpdateType::Update
+  } else {
+    CreateOrUpdateType::Create
+  };
+  Ok(match item {
+    OutboxItem::Post(p) => {
+      let mut activity = CreateOrUpdatePost::new(p, person, community, kind, context).await?;
+      activity.id = id;
+      AnnouncableActivities::CreateOrUpdatePost(Box::new(activity))
+    }
+    OutboxItem::Comment(c) => {
+      let mut activity = CreateOrUpdateComment::new(c, person, kind, context).await?;
+      activity.id = id;
+      AnnouncableActivities::CreateOrUpdateComment(activity)
+    }
+  })
+}
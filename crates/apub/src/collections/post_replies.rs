@@ -0,0 +1,165 @@
+use crate::{
+  collections::{read_page_param, PostContext},
+  generate_outbox_page_url,
+  generate_replies_url,
+  objects::comment::ApubComment,
+  protocol::collections::post_replies::PostReplies,
+};
+use activitystreams_kinds::collection::OrderedCollectionType;
+use chrono::NaiveDateTime;
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::{
+  traits::ApubObject,
+  verify::{verify_domains_match, verify_urls_match},
+};
+use lemmy_db_schema::source::comment::Comment;
+use lemmy_utils::LemmyError;
+use tracing::debug;
+use url::Url;
+
+/// Number of comments on each page of the replies collection of a post
+const POST_REPLIES_PAGE_SIZE: i64 = 20;
+
+#[derive(Clone, Debug)]
+pub(crate) struct ApubPostReplies {
+  page: i64,
+  pub(crate) comments: Vec<ApubComment>,
+  /// Link to the following page, if there are more comments
+  pub(crate) next: Option<Url>,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ApubObject for ApubPostReplies {
+  type DataType = PostContext;
+  type TombstoneType = ();
+  type ApubType = PostReplies;
+
+  fn last_refreshed_at(&self) -> Option<NaiveDateTime> {
+    None
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn read_from_apub_id(
+    object_id: Url,
+    data: &Self::DataType,
+  ) -> Result<Option<Self>, LemmyError> {
+    // Only read from database if its a local post, otherwise fetch over http
+    if data.0.local {
+      let page = read_page_param(&object_id);
+      let post_id = data.0.id;
+      let comments: Vec<ApubComment> = blocking(data.1.pool(), move |conn| {
+        Comment::list_for_post(conn, post_id, page, POST_REPLIES_PAGE_SIZE)
+      })
+      .await??
+      .into_iter()
+      .map(Into::into)
+      .collect();
+      let next = if comments.len() as i64 == POST_REPLIES_PAGE_SIZE {
+        let replies_id = generate_replies_url(&data.0.ap_id)?.into();
+        Some(generate_outbox_page_url(&replies_id, page + 1)?)
+      } else {
+        None
+      };
+      Ok(Some(ApubPostReplies {
+        page,
+        comments,
+        next,
+      }))
+    } else {
+      Ok(None)
+    }
+  }
+
+  async fn delete(self, _data: &Self::DataType) -> Result<(), LemmyError> {
+    // do nothing (it gets deleted automatically with the post)
+    Ok(())
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn into_apub(self, data: &Self::DataType) -> Result<Self::ApubType, LemmyError> {
+    let mut ordered_items = vec![];
+    for comment in self.comments {
+      ordered_items.push(comment.into_apub(&data.1).await?);
+    }
+
+    // The first page is served at the plain replies url, which posts link to
+    let replies_id = generate_replies_url(&data.0.ap_id)?.into();
+    let id = if self.page > 1 {
+      generate_outbox_page_url(&replies_id, self.page)?
+    } else {
+      replies_id
+    };
+    Ok(PostReplies {
+      r#type: OrderedCollectionType::OrderedCollection,
+      id,
+      total_items: ordered_items.len() as i32,
+      ordered_items,
+      next: self.next,
+    })
+  }
+
+  fn to_tombstone(&self) -> Result<Self::TombstoneType, LemmyError> {
+    // no tombstone for this, there is only a tombstone for the post
+    unimplemented!()
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn verify(
+    replies: &PostReplies,
+    expected_domain: &Url,
+    data: &PostContext,
+    _request_counter: &mut i32,
+  ) -> Result<(), LemmyError> {
+    verify_domains_match(expected_domain, &replies.id)?;
+    let mut replies_id: Url = replies.id.clone();
+    replies_id.set_query(None);
+    verify_urls_match(&generate_replies_url(&data.0.ap_id)?.into(), &replies_id)?;
+    Ok(())
+  }
+
+  /// Comments are processed in order, so that parent comments are already known when their
+  /// replies are parsed. Comments which fail to parse are skipped. The requests made for each
+  /// comment are added to the request counter, so that a backfill can charge them to its budget.
+  #[tracing::instrument(skip_all)]
+  async fn from_apub(
+    apub: Self::ApubType,
+    data: &Self::DataType,
+    request_counter: &mut i32,
+  ) -> Result<Self, LemmyError> {
+    let context = &data.1;
+    let mut comments = vec![];
+    for note in apub
+      .ordered_items
+      .into_iter()
+      .take(POST_REPLIES_PAGE_SIZE as usize)
+    {
+      // use separate request counter for each item, so that one deep comment chain doesnt block
+      // all the others
+      let item_counter = &mut 0;
+      let res = async {
+        let (post, _) = note.get_parents(context, item_counter).await?;
+        if post.id != data.0.id {
+          return Err(LemmyError::from_message(
+            "Comment belongs to different post",
+          ));
+        }
+        let id = note.id.inner().clone();
+        ApubComment::verify(&note, &id, context, item_counter).await?;
+        ApubComment::from_apub(note, context, item_counter).await
+      }
+      .await;
+      *request_counter += *item_counter;
+      match res {
+        Ok(comment) => comments.push(comment),
+        Err(e) => debug!("Failed to parse reply: {}", e),
+      }
+    }
+    Ok(ApubPostReplies {
+      page: 1,
+      comments,
+      next: apub.next,
+    })
+  }
+
+  type DbType = ();
+}
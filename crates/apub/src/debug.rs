@@ -0,0 +1,311 @@
+use crate::{
+  activity_lists::{SharedInboxActivities, SiteInboxActivities},
+  check_is_apub_id_valid,
+  fetcher::search::SearchableObjects,
+  http::{
+    actor_public_keys,
+    assert_activity_not_local,
+    verify_signature_with_keys,
+    ActivityCommonFields,
+  },
+  protocol::activities::{
+    block::{block_user::BlockUser, undo_block_user::UndoBlockUser},
+    community::{
+      add_mod::AddMod,
+      announce::AnnounceActivity,
+      forward_report::ForwardReport,
+      remove_mod::RemoveMod,
+      report::Report,
+      resolve_report::ResolveReport,
+      update::UpdateCommunity,
+    },
+    create_or_update::{
+      comment::CreateOrUpdateComment,
+      post::CreateOrUpdatePost,
+      private_message::CreateOrUpdatePrivateMessage,
+    },
+    deletion::{delete::Delete, delete_user::DeleteUser, undo_delete::UndoDelete},
+    following::{
+      accept::AcceptFollowCommunity,
+      follow::FollowCommunity,
+      undo_follow::UndoFollowCommunity,
+    },
+    instance::update::UpdateInstance,
+    migration::{move_community::MoveCommunity, move_person::MovePerson},
+    poll::create_poll_answer::CreatePollAnswer,
+    reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+    voting::{undo_vote::UndoVote, vote::Vote},
+  },
+};
+use actix_web::http::{
+  header::{HeaderMap, HeaderName, HeaderValue},
+  uri::PathAndQuery,
+  Method,
+};
+use lemmy_api_common::{blocking, site::DebugActivityStep};
+use lemmy_apub_lib::{object_id::fetch_object_json, signatures::FetchSigning, traits::ApubObject};
+use lemmy_db_schema::{newtypes::DbUrl, source::activity::Activity};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+use url::Url;
+
+/// The inbox request which delivered an activity, to check its HTTP signature
+pub struct InboxRequest {
+  pub path: String,
+  pub headers: HashMap<String, String>,
+}
+
+/// Runs an activity or object through the same parsing and verification steps as an incoming
+/// activity, without applying it. Stops at the first step which fails.
+///
+/// The verification of the activity or object itself is not available, because it fetches and
+/// stores the objects which are referenced by it, and that can't be undone.
+#[tracing::instrument(skip(activity, inbox_request, context))]
+pub async fn debug_activity(
+  activity: Option<String>,
+  url: Option<Url>,
+  inbox_request: Option<InboxRequest>,
+  context: &LemmyContext,
+) -> Result<Vec<DebugActivityStep>, LemmyError> {
+  let mut steps = Steps(vec![]);
+  let json = match (activity, &url) {
+    (Some(activity), _) => steps.record("parse_json", serde_json::from_str::<Value>(&activity)),
+    (None, Some(url)) => steps.record(
+      "fetch",
+      fetch_object_json(context.client(), url, context.fetch_signer()).await,
+    ),
+    (None, None) => return Err(LemmyError::from_message("no_id_given")),
+  };
+  if let Some(json) = json {
+    if json.get("actor").is_some() {
+      debug_activity_json(&mut steps, json, inbox_request, context).await;
+    } else {
+      debug_object_json(&mut steps, json);
+    }
+  }
+  Ok(steps.0)
+}
+
+const VERIFY_SKIPPED: &str = "Skipped, as it would fetch and store referenced objects";
+
+struct Steps(Vec<DebugActivityStep>);
+
+impl Steps {
+  fn record<T, E: Display>(&mut self, step: &str, result: Result<T, E>) -> Option<T> {
+    let message = result.as_ref().err().map(ToString::to_string);
+    self.0.push(DebugActivityStep {
+      step: step.to_string(),
+      success: result.is_ok(),
+      message,
+    });
+    result.ok()
+  }
+
+  fn info(&mut self, step: &str, message: String) {
+    self.0.push(DebugActivityStep {
+      step: step.to_string(),
+      success: true,
+      message: Some(message),
+    });
+  }
+}
+
+async fn debug_activity_json(
+  steps: &mut Steps,
+  json: Value,
+  inbox_request: Option<InboxRequest>,
+  context: &LemmyContext,
+) -> Option<()> {
+  let common: ActivityCommonFields =
+    steps.record("parse_common_fields", serde_json::from_value(json.clone()))?;
+  steps.record(
+    "check_actor_domain",
+    check_is_apub_id_valid(&common.actor, false, context),
+  )?;
+  let public_keys = steps.record(
+    "fetch_actor_key",
+    actor_public_keys(common.actor.clone(), context).await,
+  )?;
+  match &inbox_request {
+    Some(request) => steps.record("check_signature", check_signature(request, &public_keys))?,
+    None => steps.info(
+      "check_signature",
+      "Skipped, needs the headers of the inbox request".to_string(),
+    ),
+  }
+  steps.record(
+    "check_not_local",
+    assert_activity_not_local(&common.id, &context.settings().hostname),
+  )?;
+
+  // Duplicates are dropped by the inbox, but the remaining steps are still useful to look at
+  let ap_id: DbUrl = common.id.clone().into();
+  let previous = blocking(context.pool(), move |conn| {
+    Activity::read_from_apub_id(conn, &ap_id)
+  })
+  .await;
+  let duplicate = match previous {
+    Ok(Ok(previous)) => Err(format!(
+      "Activity was already received at {}",
+      previous.published
+    )),
+    _ => Ok(()),
+  };
+  steps.record("check_duplicate", duplicate);
+
+  // Activities for community and person inboxes are tried first, same as in the shared inbox
+  if serde_json::from_value::<SharedInboxActivities>(json.clone()).is_ok() {
+    steps.info(
+      "parse_activity",
+      "Parsed as shared inbox activity".to_string(),
+    );
+    steps.info("verify", VERIFY_SKIPPED.to_string());
+    return Some(());
+  }
+  if serde_json::from_value::<SiteInboxActivities>(json.clone()).is_ok() {
+    steps.info(
+      "parse_activity",
+      "Parsed as site inbox activity".to_string(),
+    );
+    steps.info("verify", VERIFY_SKIPPED.to_string());
+    return Some(());
+  }
+  let kind = json.get("type").and_then(Value::as_str).unwrap_or_default();
+  let errors = candidate_parse_errors(kind, &json);
+  let message = if errors.is_empty() {
+    format!("Unknown activity type {}", kind)
+  } else {
+    errors.join("\n")
+  };
+  steps.record::<(), _>("parse_activity", Err(message))
+}
+
+/// Accepts the signature if it was made with any of the actor's keys, same as the inbox.
+fn check_signature(request: &InboxRequest, public_keys: &[String]) -> Result<(), LemmyError> {
+  let mut headers = HeaderMap::new();
+  for (name, value) in &request.headers {
+    headers.insert(HeaderName::from_str(name)?, HeaderValue::from_str(value)?);
+  }
+  let path = PathAndQuery::from_str(&request.path)?;
+  verify_signature_with_keys(&Method::POST, Some(&path), &headers, public_keys)
+}
+
+fn debug_object_json(steps: &mut Steps, json: Value) -> Option<()> {
+  steps.record(
+    "parse_object",
+    serde_json::from_value::<<SearchableObjects as ApubObject>::ApubType>(json),
+  )?;
+  steps.info("verify", VERIFY_SKIPPED.to_string());
+  Some(())
+}
+
+/// The inbox enums are untagged, so serde only says that no variant matched. Parsing as each of the
+/// activities with the same type gives the actual reason.
+fn candidate_parse_errors(kind: &str, json: &Value) -> Vec<String> {
+  let errors = match kind {
+    "Create" | "Update" => vec![
+      parse_error::<CreateOrUpdatePost>("CreateOrUpdatePost", json),
+      parse_error::<CreateOrUpdateComment>("CreateOrUpdateComment", json),
+      parse_error::<CreateOrUpdatePrivateMessage>("CreateOrUpdatePrivateMessage", json),
+      parse_error::<CreatePollAnswer>("CreatePollAnswer", json),
+      parse_error::<UpdateCommunity>("UpdateCommunity", json),
+      parse_error::<UpdateInstance>("UpdateInstance", json),
+    ],
+    "Like" | "Dislike" => vec![
+      parse_error::<EmojiReact>("EmojiReact", json),
+      parse_error::<Vote>("Vote", json),
+    ],
+    "EmojiReact" => vec![parse_error::<EmojiReact>("EmojiReact", json)],
+    "Undo" => vec![
+      parse_error::<UndoEmojiReact>("UndoEmojiReact", json),
+      parse_error::<UndoVote>("UndoVote", json),
+      parse_error::<UndoDelete>("UndoDelete", json),
+      parse_error::<UndoFollowCommunity>("UndoFollowCommunity", json),
+      parse_error::<UndoBlockUser>("UndoBlockUser", json),
+    ],
+    "Delete" => vec![
+      parse_error::<Delete>("Delete", json),
+      parse_error::<DeleteUser>("DeleteUser", json),
+    ],
+    "Follow" => vec![parse_error::<FollowCommunity>("FollowCommunity", json)],
+    "Accept" => vec![parse_error::<AcceptFollowCommunity>(
+      "AcceptFollowCommunity",
+      json,
+    )],
+    "Announce" => vec![parse_error::<AnnounceActivity>("AnnounceActivity", json)],
+    "Block" => vec![parse_error::<BlockUser>("BlockUser", json)],
+    "Add" => vec![parse_error::<AddMod>("AddMod", json)],
+    "Remove" => vec![parse_error::<RemoveMod>("RemoveMod", json)],
+    "Flag" => vec![
+      parse_error::<Report>("Report", json),
+      parse_error::<ForwardReport>("ForwardReport", json),
+    ],
+    "Resolve" => vec![parse_error::<ResolveReport>("ResolveReport", json)],
+    "Move" => vec![
+      parse_error::<MovePerson>("MovePerson", json),
+      parse_error::<MoveCommunity>("MoveCommunity", json),
+    ],
+    _ => vec![],
+  };
+  errors.into_iter().flatten().collect()
+}
+
+fn parse_error<T: DeserializeOwned>(name: &str, json: &Value) -> Option<String> {
+  serde_json::from_value::<T>(json.clone())
+    .err()
+    .map(|e| format!("{}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::protocol::tests::file_to_json_object;
+
+  #[test]
+  fn test_steps() {
+    let mut steps = Steps(vec![]);
+    assert_eq!(Some(1), steps.record::<_, String>("first", Ok(1)));
+    steps.info("second", "some info".to_string());
+    assert_eq!(
+      None,
+      steps.record::<i32, _>("third", Err("failed".to_string()))
+    );
+
+    let steps: Vec<_> = steps
+      .0
+      .into_iter()
+      .map(|s| (s.step, s.success, s.message))
+      .collect();
+    assert_eq!(
+      vec![
+        ("first".to_string(), true, None),
+        ("second".to_string(), true, Some("some info".to_string())),
+        ("third".to_string(), false, Some("failed".to_string())),
+      ],
+      steps
+    );
+  }
+
+  #[test]
+  fn test_candidate_parse_errors() {
+    // Only the candidates which fail to parse are listed
+    let like: Value = file_to_json_object("assets/lemmy/activities/voting/like_note.json").unwrap();
+    let errors = candidate_parse_errors("Like", &like);
+    assert_eq!(1, errors.len());
+    assert!(errors[0].starts_with("EmojiReact: "));
+    assert!(errors[0].contains("content"));
+
+    let mut move_person: Value =
+      file_to_json_object("assets/lemmy/activities/migration/move_person.json").unwrap();
+    move_person.as_object_mut().unwrap().remove("target");
+    let errors = candidate_parse_errors("Move", &move_person);
+    assert_eq!(2, errors.len());
+    assert!(errors[0].starts_with("MovePerson: "));
+    assert!(errors[1].starts_with("MoveCommunity: "));
+
+    assert!(candidate_parse_errors("Unknown", &like).is_empty());
+  }
+}
@@ -0,0 +1,54 @@
+use lemmy_api_common::blocking;
+use lemmy_db_schema::source::{
+  federation_list::{FederationListEntry, FederationListEntryForm, FederationLists},
+  site::Site,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use tracing::info;
+
+/// Reloads the lists from the database into the context, so that checking incoming and outgoing
+/// ids doesn't need a query. Needs to be called whenever an admin changes the lists.
+pub async fn refresh_federation_lists(context: &LemmyContext) -> Result<(), LemmyError> {
+  let lists = blocking(context.pool(), FederationLists::read).await??;
+  context.set_federation_lists(lists);
+  Ok(())
+}
+
+/// Loads the lists on startup. If the database doesn't have any entries yet, the lists and the
+/// strict allowlist setting from the config file are imported first, so that existing setups keep
+/// working.
+pub async fn init_federation_lists(context: &LemmyContext) -> Result<(), LemmyError> {
+  let federation = context.settings().federation;
+  let entries = blocking(context.pool(), FederationListEntry::list).await??;
+  if entries.is_empty() {
+    let strict_allowlist = federation.strict_allowlist;
+    let allowed = federation.allowed_instances.unwrap_or_default();
+    let blocked = federation.blocked_instances.unwrap_or_default();
+    let forms = allowed
+      .into_iter()
+      .map(|d| (d, false))
+      .chain(blocked.into_iter().map(|d| (d, true)))
+      .map(|(domain, blocked)| FederationListEntryForm {
+        domain,
+        blocked,
+        admin_person_id: None,
+        reason: Some("Imported from config file".to_string()),
+      })
+      .collect::<Vec<_>>();
+    if !forms.is_empty() {
+      info!(
+        "Importing {} federation list entries from config",
+        forms.len()
+      );
+      blocking(context.pool(), move |conn| {
+        forms
+          .iter()
+          .try_for_each(|form| FederationListEntry::upsert(conn, form).map(|_| ()))?;
+        Site::update_federation_strict_allowlist(conn, strict_allowlist).map(|_| ())
+      })
+      .await??;
+    }
+  }
+  refresh_federation_lists(context).await
+}
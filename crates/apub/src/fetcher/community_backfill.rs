@@ -0,0 +1,147 @@
+use crate::{
+  collections::{
+    community_outbox::ApubCommunityOutbox,
+    post_replies::ApubPostReplies,
+    CommunityContext,
+    PostContext,
+  },
+  generate_outbox_url,
+  objects::community::ApubCommunity,
+};
+use lemmy_api_common::blocking;
+use lemmy_apub_lib::object_id::ObjectId;
+use lemmy_db_schema::{
+  source::{
+    community::Community,
+    community_backfill::{CommunityBackfill, CommunityBackfillProgressForm},
+  },
+  traits::Crud,
+};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// Starts fetching the older posts of a remote community and their comments in the background.
+/// This only happens the first time that the community is followed from this instance.
+#[tracing::instrument(skip_all)]
+pub async fn start_community_backfill(
+  community: ApubCommunity,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  if community.local {
+    return Ok(());
+  }
+  let community_id = community.id;
+  let backfill = blocking(context.pool(), move |conn| {
+    CommunityBackfill::start(conn, community_id)
+  })
+  .await??;
+  if let Some(backfill) = backfill {
+    spawn_backfill(backfill, community, context.clone());
+  }
+  Ok(())
+}
+
+/// Restarts backfills which were interrupted by a restart. These begin again from the first
+/// page, posts and comments which were already fetched are simply updated.
+pub async fn resume_community_backfills(context: &LemmyContext) -> Result<(), LemmyError> {
+  let backfills = blocking(context.pool(), CommunityBackfill::list_unfinished).await??;
+  for backfill in backfills {
+    let community_id = backfill.community_id;
+    let community: ApubCommunity = blocking(context.pool(), move |conn| {
+      Community::read(conn, community_id)
+    })
+    .await??
+    .into();
+    spawn_backfill(backfill, community, context.clone());
+  }
+  Ok(())
+}
+
+fn spawn_backfill(backfill: CommunityBackfill, community: ApubCommunity, context: LemmyContext) {
+  actix_rt::spawn(async move {
+    info!("Starting backfill of community {}", community.actor_id);
+    let error = match run_backfill(&backfill, community, &context).await {
+      Ok(()) => None,
+      Err(e) => {
+        warn!("Community backfill failed: {}", e);
+        Some(e.to_string())
+      }
+    };
+    let backfill_id = backfill.id;
+    let res = blocking(context.pool(), move |conn| {
+      CommunityBackfill::finish(conn, backfill_id, error)
+    })
+    .await
+    .and_then(|r| r.map_err(LemmyError::from));
+    if let Err(e) = res {
+      warn!("Failed to store community backfill status: {}", e);
+    }
+  });
+}
+
+/// Walks through the pages of the community outbox, and the pages of comments of each post. Every
+/// request counts against the configured budget, including those for the creators and parents of
+/// posts and comments. The budget is checked before each page, so the requests for the items of
+/// the last page may exceed it a little.
+async fn run_backfill(
+  backfill: &CommunityBackfill,
+  community: ApubCommunity,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let budget = context.settings().federation.community_backfill_budget;
+  let mut progress = CommunityBackfillProgressForm::default();
+  let outbox_id: Url = generate_outbox_url(&community.actor_id)?.into();
+  let outbox_data = CommunityContext(community, context.clone());
+
+  let mut next = Some(outbox_id);
+  while let Some(page_id) = next {
+    if progress.requests_used >= budget {
+      break;
+    }
+    // use separate request counter for each page, as it is also limited per fetch
+    let request_counter = &mut 0;
+    let page = ObjectId::<ApubCommunityOutbox>::new(page_id)
+      .dereference(&outbox_data, context.client(), request_counter)
+      .await;
+    progress.requests_used += *request_counter;
+    let page = page?;
+    progress.pages_fetched += 1;
+    progress.posts_fetched += page.posts.len() as i32;
+
+    for (post, replies_id) in page.replies {
+      let replies_data = PostContext(post, context.clone());
+      let mut next_replies = Some(replies_id);
+      while let Some(replies_id) = next_replies {
+        if progress.requests_used >= budget {
+          break;
+        }
+        let request_counter = &mut 0;
+        let replies = ObjectId::<ApubPostReplies>::new(replies_id)
+          .dereference(&replies_data, context.client(), request_counter)
+          .await;
+        progress.requests_used += *request_counter;
+        next_replies = match replies {
+          Ok(replies) => {
+            progress.comments_fetched += replies.comments.len() as i32;
+            replies.next
+          }
+          Err(e) => {
+            debug!("Failed to fetch comments during backfill: {}", e);
+            None
+          }
+        };
+      }
+    }
+
+    let backfill_id = backfill.id;
+    let form = progress.clone();
+    blocking(context.pool(), move |conn| {
+      CommunityBackfill::update_progress(conn, backfill_id, &form)
+    })
+    .await??;
+    next = page.next;
+  }
+  Ok(())
+}
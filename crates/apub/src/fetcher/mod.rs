@@ -3,9 +3,10 @@ use itertools::Itertools;
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::traits::{ActorType, ApubObject};
 use lemmy_db_schema::traits::ApubActor;
-use lemmy_utils::{settings::structs::Settings, LemmyError};
+use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
 
+pub mod community_backfill;
 pub mod post_or_comment;
 pub mod search;
 pub mod user_or_community;
@@ -32,7 +33,7 @@ where
       .collect_tuple()
       .expect("invalid query");
     let name = name.to_string();
-    let domain = format!("{}://{}", Settings::get().get_protocol_string(), domain);
+    let domain = format!("{}://{}", context.settings().get_protocol_string(), domain);
     let actor = blocking(context.pool(), move |conn| {
       DbActor::read_from_name_and_domain(conn, &name, &domain)
     })
@@ -115,6 +115,13 @@ impl ActorType for UserOrCommunity {
     }
   }
 
+  fn previous_public_key(&self) -> Option<String> {
+    match self {
+      UserOrCommunity::User(p) => p.previous_public_key(),
+      UserOrCommunity::Community(p) => p.previous_public_key(),
+    }
+  }
+
   fn private_key(&self) -> Option<String> {
     todo!()
   }
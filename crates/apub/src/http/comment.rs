@@ -1,8 +1,8 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response},
+  http::{check_authorized_fetch, create_apub_response, create_apub_tombstone_response},
   objects::comment::ApubComment,
 };
-use actix_web::{web, web::Path, HttpResponse};
+use actix_web::{web, web::Path, HttpRequest, HttpResponse};
 use diesel::result::Error::NotFound;
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::traits::ApubObject;
@@ -19,9 +19,13 @@ pub(crate) struct CommentQuery {
 /// Return the ActivityPub json representation of a local comment over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_comment(
+  request: HttpRequest,
   info: Path<CommentQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let id = CommentId(info.comment_id.parse::<i32>()?);
   let comment: ApubComment = blocking(context.pool(), move |conn| Comment::read(conn, id))
     .await??
@@ -7,12 +7,16 @@ use crate::{
     CommunityContext,
   },
   context::WithContext,
+  generate_outbox_page_url,
   generate_outbox_url,
   http::{
+    check_authorized_fetch,
     create_apub_response,
     create_apub_tombstone_response,
+    inbox_queue::InboxKind,
     payload_to_string,
     receive_activity,
+    receive_verified_activity,
     ActivityCommonFields,
   },
   objects::community::ApubCommunity,
@@ -35,7 +39,8 @@ pub(crate) struct CommunityQuery {
   community_name: String,
 }
 
-/// Return the ActivityPub json representation of a local community over HTTP.
+/// Return the ActivityPub json representation of a local community over HTTP. This is not guarded by
+/// authorized fetch, because other instances need the public key to verify our signed fetches.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_community_http(
   info: web::Path<CommunityQuery>,
@@ -69,19 +74,26 @@ pub async fn community_inbox(
   let activity_data: ActivityCommonFields = serde_json::from_str(&unparsed)?;
   let activity = serde_json::from_str::<WithContext<GroupInboxActivities>>(&unparsed)?;
 
-  receive_group_inbox(activity.inner(), activity_data, request, &context).await?;
-
-  Ok(HttpResponse::Ok().finish())
+  receive_activity(
+    request,
+    activity.inner(),
+    activity_data,
+    InboxKind::Group,
+    &context,
+  )
+  .await
 }
 
 pub(in crate::http) async fn receive_group_inbox(
   activity: GroupInboxActivities,
   activity_data: ActivityCommonFields,
-  request: HttpRequest,
   context: &LemmyContext,
-) -> Result<HttpResponse, LemmyError> {
+) -> Result<(), LemmyError> {
   let actor_id = ObjectId::new(activity_data.actor.clone());
-  let res = receive_activity(request, activity.clone(), activity_data, context).await?;
+  let received = receive_verified_activity(activity.clone(), &activity_data, context).await?;
+  if !received {
+    return Ok(());
+  }
 
   if let GroupInboxActivities::AnnouncableActivities(announcable) = activity {
     // Ignore failures in get_community(). those happen because Delete/PrivateMessage is not in a
@@ -95,14 +107,18 @@ pub(in crate::http) async fn receive_group_inbox(
     }
   }
 
-  Ok(res)
+  Ok(())
 }
 
 /// Returns an empty followers collection, only populating the size (for privacy).
 pub(crate) async fn get_apub_community_followers(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let community = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name)
   })
@@ -111,17 +127,31 @@ pub(crate) async fn get_apub_community_followers(
   Ok(create_apub_response(&followers))
 }
 
-/// Returns the community outbox, which is populated by a maximum of 20 posts (but no other
-/// activites like votes or comments).
+#[derive(Deserialize)]
+pub(crate) struct CommunityOutboxQuery {
+  page: Option<i64>,
+}
+
+/// Returns the community outbox, which is populated by pages of 20 posts (but no other
+/// activites like votes or comments). Older posts can be read by following the `next` links.
 pub(crate) async fn get_apub_community_outbox(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
+  query: web::Query<CommunityOutboxQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let community = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name)
   })
   .await??;
-  let id = ObjectId::new(generate_outbox_url(&community.actor_id)?);
+  let outbox_id = generate_outbox_url(&community.actor_id)?.into();
+  let id = ObjectId::new(generate_outbox_page_url(
+    &outbox_id,
+    query.page.unwrap_or(1),
+  )?);
   let outbox_data = CommunityContext(community.into(), context.get_ref().clone());
   let outbox: ApubCommunityOutbox = id
     .dereference(&outbox_data, context.client(), &mut 0)
@@ -131,9 +161,13 @@ pub(crate) async fn get_apub_community_outbox(
 
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_community_moderators(
+  request: HttpRequest,
   info: web::Path<CommunityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let community: ApubCommunity = blocking(context.pool(), move |conn| {
     Community::read_from_name(conn, &info.community_name)
   })
@@ -0,0 +1,133 @@
+use crate::http::{
+  community::receive_group_inbox,
+  person::receive_person_inbox,
+  site::receive_site_inbox,
+  ActivityCommonFields,
+};
+use actix_rt::time::sleep;
+use lemmy_api_common::blocking;
+use lemmy_db_schema::source::inbox_queue::{InboxQueueEntry, InboxQueueEntryForm};
+use lemmy_utils::LemmyError;
+use lemmy_websocket::LemmyContext;
+use serde_json::Value;
+use std::{str::FromStr, time::Duration};
+use strum_macros::{Display, EnumString};
+use tracing::{info, warn};
+
+/// How long an idle worker waits before looking for new activities
+const INBOX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of times an activity is processed before it is given up. Failures are often temporary,
+/// like a timeout while fetching the parent of a comment. The retries are made after 1 minute,
+/// 5 minutes, 25 minutes and about 2 hours.
+const INBOX_MAX_ATTEMPTS: i32 = 5;
+
+/// The inbox which received an activity, determines how it is parsed and handled.
+#[derive(Clone, Copy, Debug, Display, EnumString)]
+pub(crate) enum InboxKind {
+  Group,
+  Person,
+  Site,
+}
+
+/// Stores an activity with valid signature, so that it can be processed by the inbox workers.
+pub(crate) async fn queue_activity(
+  kind: InboxKind,
+  activity_data: ActivityCommonFields,
+  data: Value,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let form = InboxQueueEntryForm {
+    ap_id: activity_data.id.into(),
+    actor: activity_data.actor.into(),
+    inbox: kind.to_string(),
+    data,
+  };
+  blocking(context.pool(), move |conn| {
+    InboxQueueEntry::create(conn, &form)
+  })
+  .await??;
+  Ok(())
+}
+
+/// Verifies and applies an activity whose signature was already checked.
+pub(crate) async fn process_activity(
+  kind: InboxKind,
+  activity_data: ActivityCommonFields,
+  data: Value,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  match kind {
+    InboxKind::Group => {
+      receive_group_inbox(serde_json::from_value(data)?, activity_data, context).await
+    }
+    InboxKind::Person => {
+      receive_person_inbox(serde_json::from_value(data)?, activity_data, context).await
+    }
+    InboxKind::Site => {
+      receive_site_inbox(serde_json::from_value(data)?, activity_data, context).await
+    }
+  }
+}
+
+/// Starts the workers which process queued incoming activities, so that the inbox handlers can
+/// respond without waiting for objects to be fetched from other instances.
+pub async fn start_inbox_workers(context: LemmyContext) -> Result<(), LemmyError> {
+  let released = blocking(context.pool(), InboxQueueEntry::release_unfinished).await??;
+  if released > 0 {
+    info!("Requeued {} interrupted incoming activities", released);
+  }
+
+  for _ in 0..context.settings().federation.inbox_worker_count {
+    let context = context.clone();
+    actix_rt::spawn(async move {
+      loop {
+        match process_next(&context).await {
+          Ok(true) => {}
+          Ok(false) => sleep(INBOX_POLL_INTERVAL).await,
+          Err(e) => {
+            warn!("Failed to read inbox queue: {}", e);
+            sleep(INBOX_POLL_INTERVAL).await
+          }
+        }
+      }
+    });
+  }
+  Ok(())
+}
+
+/// Processes the next queued activity. Returns false if the queue is empty.
+async fn process_next(context: &LemmyContext) -> Result<bool, LemmyError> {
+  let entry = match blocking(context.pool(), InboxQueueEntry::claim_next).await?? {
+    Some(e) => e,
+    None => return Ok(false),
+  };
+
+  let activity_data = ActivityCommonFields {
+    id: entry.ap_id.into(),
+    actor: entry.actor.into(),
+  };
+  let res = match InboxKind::from_str(&entry.inbox) {
+    Ok(kind) => process_activity(kind, activity_data.clone(), entry.data, context).await,
+    Err(e) => Err(e.into()),
+  };
+  let entry_id = entry.id;
+  match res {
+    Ok(()) => {
+      blocking(context.pool(), move |conn| {
+        InboxQueueEntry::mark_finished(conn, entry_id, None)
+      })
+      .await??;
+    }
+    Err(e) => {
+      warn!("Failed to process activity {}: {}", activity_data.id, e);
+      let error = e.to_string();
+      let retry_delay = chrono::Duration::minutes(5_i64.pow(entry.attempts.max(0) as u32));
+      blocking(context.pool(), move |conn| {
+        InboxQueueEntry::mark_failed(conn, entry_id, error, INBOX_MAX_ATTEMPTS, retry_delay)
+      })
+      .await??;
+    }
+  }
+  Ok(true)
+}
@@ -3,10 +3,13 @@ use crate::{
   check_is_apub_id_valid,
   context::WithContext,
   fetcher::user_or_community::UserOrCommunity,
-  http::{community::receive_group_inbox, person::receive_person_inbox},
+  http::inbox_queue::{process_activity, queue_activity, InboxKind},
   insert_activity,
+  objects::instance::ApubSite,
 };
+use activitystreams_kinds::actor::ApplicationType;
 use actix_web::{
+  http::{header::HeaderMap, uri::PathAndQuery, Method},
   web,
   web::{Bytes, BytesMut, Payload},
   HttpRequest,
@@ -18,21 +21,23 @@ use http::StatusCode;
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::{
   data::Data,
-  object_id::ObjectId,
-  signatures::verify_signature,
+  object_id::{fetch_object_json, ObjectId},
+  signatures::{signature_actor_id, verify_signature_parts, FetchSigning, PublicKey},
   traits::{ActivityHandler, ActorType},
+  verify::verify_urls_match,
   APUB_JSON_CONTENT_TYPE,
 };
 use lemmy_db_schema::source::activity::Activity;
 use lemmy_utils::{location_info, LemmyError};
 use lemmy_websocket::LemmyContext;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Debug, io::Read};
+use std::{env, fmt::Debug, io::Read};
 use tracing::{debug, info};
 use url::Url;
 
 mod comment;
 mod community;
+pub mod inbox_queue;
 mod person;
 mod post;
 pub mod routes;
@@ -49,11 +54,14 @@ pub async fn shared_inbox(
   let activity_data: ActivityCommonFields = serde_json::from_str(&unparsed)?;
   let activity = serde_json::from_str::<WithContext<SharedInboxActivities>>(&unparsed)?;
   match activity.inner() {
+    SharedInboxActivities::CreatePollAnswer(a) => {
+      receive_activity(request, a, activity_data, InboxKind::Person, &context).await
+    }
     SharedInboxActivities::GroupInboxActivities(g) => {
-      receive_group_inbox(*g, activity_data, request, &context).await
+      receive_activity(request, g, activity_data, InboxKind::Group, &context).await
     }
     SharedInboxActivities::PersonInboxActivities(p) => {
-      receive_person_inbox(*p, activity_data, request, &context).await
+      receive_activity(request, p, activity_data, InboxKind::Person, &context).await
     }
   }
 }
@@ -76,34 +84,56 @@ pub(crate) struct ActivityCommonFields {
 }
 
 // TODO: move most of this code to library
+/// Checks the signature of an incoming activity and stores it in the inbox queue, where it is
+/// picked up by the inbox workers. Responds with 202 Accepted, as the activity is processed
+/// asynchronously.
 #[tracing::instrument(skip_all)]
-async fn receive_activity<'a, T>(
+async fn receive_activity<T>(
   request: HttpRequest,
   activity: T,
   activity_data: ActivityCommonFields,
+  kind: InboxKind,
   context: &LemmyContext,
 ) -> Result<HttpResponse, LemmyError>
 where
-  T: ActivityHandler<DataType = LemmyContext>
-    + Clone
-    + Deserialize<'a>
-    + Serialize
-    + std::fmt::Debug
-    + Send
-    + 'static,
+  T: Serialize + Debug,
 {
-  check_is_apub_id_valid(&activity_data.actor, false, &context.settings())?;
-  let request_counter = &mut 0;
-  let actor = ObjectId::<UserOrCommunity>::new(activity_data.actor)
-    .dereference(context, context.client(), request_counter)
-    .await?;
-  verify_signature(&request, &actor.public_key())?;
+  check_is_apub_id_valid(&activity_data.actor, false, context)?;
+  let public_keys = actor_public_keys(activity_data.actor.clone(), context).await?;
+  verify_signature_with_keys(
+    request.method(),
+    request.uri().path_and_query(),
+    request.headers(),
+    &public_keys,
+  )?;
+  assert_activity_not_local(&activity_data.id, &context.settings().hostname)?;
 
+  let data = serde_json::to_value(&activity)?;
+  // Process activities immediately during api tests, so that they can check the result right away
+  if env::var("APUB_TESTING_SEND_SYNC").is_ok() {
+    process_activity(kind, activity_data, data, context).await?;
+    return Ok(HttpResponse::Ok().finish());
+  }
+  queue_activity(kind, activity_data, data, context).await?;
+  Ok(HttpResponse::Accepted().finish())
+}
+
+/// Verifies and applies an activity whose signature was already checked. Returns false if the
+/// activity was received before.
+#[tracing::instrument(skip_all)]
+async fn receive_verified_activity<T>(
+  activity: T,
+  activity_data: &ActivityCommonFields,
+  context: &LemmyContext,
+) -> Result<bool, LemmyError>
+where
+  T: ActivityHandler<DataType = LemmyContext> + Serialize + Debug + Send + 'static,
+{
+  let request_counter = &mut 0;
   info!("Verifying activity {}", activity_data.id.to_string());
   activity
     .verify(&Data::new(context.clone()), request_counter)
     .await?;
-  assert_activity_not_local(&activity_data.id, &context.settings().hostname)?;
 
   // Log the activity, so we avoid receiving and parsing it twice. Note that this could still happen
   // if we receive the same activity twice in very quick succession.
@@ -115,14 +145,125 @@ where
       "Received duplicate activity {}",
       activity_data.id.to_string()
     );
-    return Ok(HttpResponse::BadRequest().finish());
+    return Ok(false);
   }
 
   info!("Receiving activity {}", activity_data.id.to_string());
   activity
     .receive(&Data::new(context.clone()), request_counter)
     .await?;
-  Ok(HttpResponse::Ok().finish())
+  Ok(true)
+}
+
+/// With authorized fetch enabled, fetching objects requires a valid HTTP signature from an actor
+/// whose instance isn't blocked. Returns the response to send if the fetch is rejected.
+#[tracing::instrument(skip_all)]
+async fn check_authorized_fetch(
+  request: &HttpRequest,
+  context: &LemmyContext,
+) -> Option<HttpResponse> {
+  if !context.settings().federation.authorized_fetch {
+    return None;
+  }
+  match verify_fetch_signature(request, context).await {
+    Ok(()) => None,
+    Err(e) => {
+      debug!("Rejected fetch of {}: {}", request.uri(), e);
+      Some(HttpResponse::Unauthorized().finish())
+    }
+  }
+}
+
+async fn verify_fetch_signature(
+  request: &HttpRequest,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  let actor_id = signature_actor_id(request)?;
+  check_is_apub_id_valid(&actor_id, false, context)?;
+  let public_keys = actor_public_keys(actor_id, context).await?;
+  verify_signature_with_keys(
+    request.method(),
+    request.uri().path_and_query(),
+    request.headers(),
+    &public_keys,
+  )
+}
+
+/// Fetches the public key of a person, community or instance actor. If the actor rotated its key
+/// recently, the previous key is returned as well. That is why updates of instances and
+/// communities never take a new key from the activity, but fetch it from the actor instead.
+pub(crate) async fn actor_public_keys(
+  actor_id: Url,
+  context: &LemmyContext,
+) -> Result<Vec<String>, LemmyError> {
+  let request_counter = &mut 0;
+  // Lemmy instance actors are at the root path, they sign fetches and forwarded reports
+  let actor: Box<dyn ActorType> = if actor_id.path() == "/" {
+    Box::new(
+      ObjectId::<ApubSite>::new(actor_id)
+        .dereference(context, context.client(), request_counter)
+        .await?,
+    )
+  } else {
+    let actor = ObjectId::<UserOrCommunity>::new(actor_id.clone())
+      .dereference(context, context.client(), request_counter)
+      .await;
+    match actor {
+      Ok(actor) => Box::new(actor),
+      // Other software signs fetches with an instance actor at a different path, like `/actor`
+      Err(e) => {
+        return application_actor_public_key(&actor_id, context)
+          .await
+          .map(|key| vec![key])
+          .map_err(|_| e)
+      }
+    }
+  };
+  Ok(
+    std::iter::once(actor.public_key())
+      .chain(actor.previous_public_key())
+      .collect(),
+  )
+}
+
+/// Instance actor of another software, like `/actor` on Mastodon. It is not stored, because it is
+/// only needed to check signatures.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApplicationActor {
+  #[serde(rename = "type")]
+  #[allow(dead_code)]
+  kind: ApplicationType,
+  id: Url,
+  public_key: PublicKey,
+}
+
+async fn application_actor_public_key(
+  actor_id: &Url,
+  context: &LemmyContext,
+) -> Result<String, LemmyError> {
+  check_is_apub_id_valid(actor_id, false, context)?;
+  let json = fetch_object_json(context.client(), actor_id, context.fetch_signer()).await?;
+  let actor: ApplicationActor = serde_json::from_value(json)?;
+  verify_urls_match(actor_id, &actor.id)?;
+  Ok(actor.public_key.public_key_pem)
+}
+
+/// Accepts the signature if it was made with any of the keys, see [`actor_public_keys`].
+pub(crate) fn verify_signature_with_keys(
+  method: &Method,
+  path_and_query: Option<&PathAndQuery>,
+  headers: &HeaderMap,
+  public_keys: &[String],
+) -> Result<(), LemmyError> {
+  let mut result = Err(LemmyError::from_message("actor has no public key"));
+  for public_key in public_keys {
+    result = verify_signature_parts(method, path_and_query, headers.clone(), public_key);
+    if result.is_ok() {
+      break;
+    }
+  }
+  result
 }
 
 /// Convert the data to json and turn it into an HTTP Response with the correct ActivityPub
@@ -161,9 +302,13 @@ pub struct ActivityQuery {
 /// Return the ActivityPub json representation of a local activity over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_activity(
+  request: HttpRequest,
   info: web::Path<ActivityQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let settings = context.settings();
   let activity_id = Url::parse(&format!(
     "{}/activities/{}/{}",
@@ -185,7 +330,7 @@ pub(crate) async fn get_activity(
   }
 }
 
-fn assert_activity_not_local(id: &Url, hostname: &str) -> Result<(), LemmyError> {
+pub(crate) fn assert_activity_not_local(id: &Url, hostname: &str) -> Result<(), LemmyError> {
   let activity_domain = id.domain().context(location_info!())?;
 
   if activity_domain == hostname {
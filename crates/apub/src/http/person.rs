@@ -1,32 +1,41 @@
 use crate::{
   activity_lists::PersonInboxActivities,
+  collections::{
+    person_outbox::{ApubPersonOutbox, ApubPersonOutboxPage},
+    PersonContext,
+  },
   context::WithContext,
+  generate_outbox_page_url,
   generate_outbox_url,
   http::{
+    check_authorized_fetch,
     create_apub_response,
     create_apub_tombstone_response,
+    inbox_queue::InboxKind,
     payload_to_string,
     receive_activity,
+    receive_verified_activity,
     ActivityCommonFields,
   },
   objects::person::ApubPerson,
-  protocol::collections::empty_outbox::EmptyOutbox,
 };
 use actix_web::{web, web::Payload, HttpRequest, HttpResponse};
 use lemmy_api_common::blocking;
-use lemmy_apub_lib::traits::ApubObject;
+use lemmy_apub_lib::{object_id::ObjectId, traits::ApubObject};
 use lemmy_db_schema::{source::person::Person, traits::ApubActor};
 use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::Deserialize;
 use tracing::info;
+use url::Url;
 
 #[derive(Deserialize)]
 pub struct PersonQuery {
   user_name: String,
 }
 
-/// Return the ActivityPub json representation of a local person over HTTP.
+/// Return the ActivityPub json representation of a local person over HTTP. This is not guarded by
+/// authorized fetch, because other instances need the public key to verify our signed fetches.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_person_http(
   info: web::Path<PersonQuery>,
@@ -60,28 +69,60 @@ pub async fn person_inbox(
   info!("Received person inbox activity {}", unparsed);
   let activity_data: ActivityCommonFields = serde_json::from_str(&unparsed)?;
   let activity = serde_json::from_str::<WithContext<PersonInboxActivities>>(&unparsed)?;
-  receive_person_inbox(activity.inner(), activity_data, request, &context).await
+  receive_activity(
+    request,
+    activity.inner(),
+    activity_data,
+    InboxKind::Person,
+    &context,
+  )
+  .await
 }
 
 pub(in crate::http) async fn receive_person_inbox(
   activity: PersonInboxActivities,
   activity_data: ActivityCommonFields,
-  request: HttpRequest,
   context: &LemmyContext,
-) -> Result<HttpResponse, LemmyError> {
-  receive_activity(request, activity, activity_data, context).await
+) -> Result<(), LemmyError> {
+  receive_verified_activity(activity, &activity_data, context).await?;
+  Ok(())
+}
+
+#[derive(Deserialize)]
+pub struct PersonOutboxQuery {
+  page: Option<i64>,
 }
 
+/// Returns the person outbox, with the person's posts and comments as activities. Without page
+/// parameter only the collection itself is returned, which links to the first page.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_person_outbox(
+  request: HttpRequest,
   info: web::Path<PersonQuery>,
+  query: web::Query<PersonOutboxQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
-  let person = blocking(context.pool(), move |conn| {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
+  let person: ApubPerson = blocking(context.pool(), move |conn| {
     Person::read_from_name(conn, &info.user_name)
   })
-  .await??;
-  let outbox_id = generate_outbox_url(&person.actor_id)?.into();
-  let outbox = EmptyOutbox::new(outbox_id).await?;
-  Ok(create_apub_response(&outbox))
+  .await??
+  .into();
+  let outbox_id: Url = generate_outbox_url(&person.actor_id)?.into();
+  let outbox_data = PersonContext(person, context.get_ref().clone());
+  match query.page {
+    Some(page) if page < 1 => Err(LemmyError::from_message("invalid_page")),
+    Some(page) => {
+      let id = ObjectId::new(generate_outbox_page_url(&outbox_id, page)?);
+      let page: ApubPersonOutboxPage = id.dereference_local(&outbox_data).await?;
+      Ok(create_apub_response(&page.into_apub(&outbox_data).await?))
+    }
+    None => {
+      let id = ObjectId::new(outbox_id);
+      let outbox: ApubPersonOutbox = id.dereference_local(&outbox_data).await?;
+      Ok(create_apub_response(&outbox.into_apub(&outbox_data).await?))
+    }
+  }
 }
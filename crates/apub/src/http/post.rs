@@ -1,11 +1,14 @@
 use crate::{
-  http::{create_apub_response, create_apub_tombstone_response},
+  collections::{post_replies::ApubPostReplies, PostContext},
+  generate_outbox_page_url,
+  generate_replies_url,
+  http::{check_authorized_fetch, create_apub_response, create_apub_tombstone_response},
   objects::post::ApubPost,
 };
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use diesel::result::Error::NotFound;
 use lemmy_api_common::blocking;
-use lemmy_apub_lib::traits::ApubObject;
+use lemmy_apub_lib::{object_id::ObjectId, traits::ApubObject};
 use lemmy_db_schema::{newtypes::PostId, source::post::Post, traits::Crud};
 use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
@@ -19,14 +22,19 @@ pub(crate) struct PostQuery {
 /// Return the ActivityPub json representation of a local post over HTTP.
 #[tracing::instrument(skip_all)]
 pub(crate) async fn get_apub_post(
+  request: HttpRequest,
   info: web::Path<PostQuery>,
   context: web::Data<LemmyContext>,
 ) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let id = PostId(info.post_id.parse::<i32>()?);
   let post: ApubPost = blocking(context.pool(), move |conn| Post::read(conn, id))
     .await??
     .into();
-  if !post.local {
+  // Scheduled posts dont exist for other instances until they are published
+  if !post.local || post.scheduled_publish_time.is_some() {
     return Err(NotFound.into());
   }
 
@@ -36,3 +44,40 @@ pub(crate) async fn get_apub_post(
     Ok(create_apub_tombstone_response(&post.to_tombstone()?))
   }
 }
+
+#[derive(Deserialize)]
+pub(crate) struct PostRepliesQuery {
+  page: Option<i64>,
+}
+
+/// Returns one page of the comments of a local post, oldest first. Newer comments can be read by
+/// following the `next` links.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn get_apub_post_replies(
+  request: HttpRequest,
+  info: web::Path<PostQuery>,
+  query: web::Query<PostRepliesQuery>,
+  context: web::Data<LemmyContext>,
+) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
+  let id = PostId(info.post_id.parse::<i32>()?);
+  let post: ApubPost = blocking(context.pool(), move |conn| Post::read(conn, id))
+    .await??
+    .into();
+  if !post.local || post.deleted || post.removed || post.scheduled_publish_time.is_some() {
+    return Err(NotFound.into());
+  }
+
+  let replies_id = generate_replies_url(&post.ap_id)?.into();
+  let id = ObjectId::new(generate_outbox_page_url(
+    &replies_id,
+    query.page.unwrap_or(1),
+  )?);
+  let replies_data = PostContext(post, context.get_ref().clone());
+  let replies: ApubPostReplies = id.dereference_local(&replies_data).await?;
+  Ok(create_apub_response(
+    &replies.into_apub(&replies_data).await?,
+  ))
+}
@@ -9,7 +9,7 @@ use crate::http::{
   },
   get_activity,
   person::{get_apub_person_http, get_apub_person_outbox, person_inbox},
-  post::get_apub_post,
+  post::{get_apub_post, get_apub_post_replies},
   shared_inbox,
   site::{get_apub_site_http, get_apub_site_inbox, get_apub_site_outbox},
 };
@@ -51,6 +51,10 @@ pub fn config(cfg: &mut web::ServiceConfig, settings: &Settings) {
         web::get().to(get_apub_person_outbox),
       )
       .route("/post/{post_id}", web::get().to(get_apub_post))
+      .route(
+        "/post/{post_id}/replies",
+        web::get().to(get_apub_post_replies),
+      )
       .route("/comment/{comment_id}", web::get().to(get_apub_comment))
       .route("/activities/{type_}/{id}", web::get().to(get_activity));
 
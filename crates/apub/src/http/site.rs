@@ -1,7 +1,15 @@
 use crate::{
   activity_lists::SiteInboxActivities,
   context::WithContext,
-  http::{create_apub_response, payload_to_string, receive_activity, ActivityCommonFields},
+  http::{
+    check_authorized_fetch,
+    create_apub_response,
+    inbox_queue::InboxKind,
+    payload_to_string,
+    receive_activity,
+    receive_verified_activity,
+    ActivityCommonFields,
+  },
   objects::instance::ApubSite,
   protocol::collections::empty_outbox::EmptyOutbox,
 };
@@ -9,7 +17,7 @@ use actix_web::{web, web::Payload, HttpRequest, HttpResponse};
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::traits::ApubObject;
 use lemmy_db_schema::source::site::Site;
-use lemmy_utils::{settings::structs::Settings, LemmyError};
+use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
 use tracing::info;
 use url::Url;
@@ -26,10 +34,16 @@ pub(crate) async fn get_apub_site_http(
 }
 
 #[tracing::instrument(skip_all)]
-pub(crate) async fn get_apub_site_outbox() -> Result<HttpResponse, LemmyError> {
+pub(crate) async fn get_apub_site_outbox(
+  request: HttpRequest,
+  context: web::Data<LemmyContext>,
+) -> Result<HttpResponse, LemmyError> {
+  if let Some(res) = check_authorized_fetch(&request, &context).await {
+    return Ok(res);
+  }
   let outbox_id = format!(
     "{}/site_outbox",
-    Settings::get().get_protocol_and_hostname()
+    context.settings().get_protocol_and_hostname()
   );
   let outbox = EmptyOutbox::new(Url::parse(&outbox_id)?).await?;
   Ok(create_apub_response(&outbox))
@@ -45,5 +59,21 @@ pub async fn get_apub_site_inbox(
   info!("Received site inbox activity {}", unparsed);
   let activity_data: ActivityCommonFields = serde_json::from_str(&unparsed)?;
   let activity = serde_json::from_str::<WithContext<SiteInboxActivities>>(&unparsed)?;
-  receive_activity(request, activity.inner(), activity_data, &context).await
+  receive_activity(
+    request,
+    activity.inner(),
+    activity_data,
+    InboxKind::Site,
+    &context,
+  )
+  .await
+}
+
+pub(in crate::http) async fn receive_site_inbox(
+  activity: SiteInboxActivities,
+  activity_data: ActivityCommonFields,
+  context: &LemmyContext,
+) -> Result<(), LemmyError> {
+  receive_verified_activity(activity, &activity_data, context).await?;
+  Ok(())
 }
@@ -2,15 +2,19 @@ use crate::fetcher::post_or_comment::PostOrComment;
 use anyhow::{anyhow, Context};
 use lemmy_api_common::blocking;
 use lemmy_db_schema::{newtypes::DbUrl, source::activity::Activity, DbPool};
-use lemmy_utils::{location_info, settings::structs::Settings, LemmyError};
+use lemmy_utils::{location_info, LemmyError};
+use lemmy_websocket::LemmyContext;
 use serde::{Deserialize, Deserializer};
 use std::net::IpAddr;
 use url::{ParseError, Url};
 
 pub mod activities;
 pub(crate) mod activity_lists;
+pub mod activity_queue;
 pub(crate) mod collections;
 mod context;
+pub mod debug;
+pub mod federation_list;
 pub mod fetcher;
 pub mod http;
 pub(crate) mod mentions;
@@ -28,12 +32,13 @@ pub mod protocol;
 ///
 /// `use_strict_allowlist` should be true only when parsing a remote community, or when parsing a
 /// post/comment in a local community.
-#[tracing::instrument(skip(settings))]
+#[tracing::instrument(skip(context))]
 pub(crate) fn check_is_apub_id_valid(
   apub_id: &Url,
   use_strict_allowlist: bool,
-  settings: &Settings,
+  context: &LemmyContext,
 ) -> Result<(), LemmyError> {
+  let settings = context.settings();
   let domain = apub_id.domain().context(location_info!())?.to_string();
   let local_instance = settings.get_hostname_without_port()?;
 
@@ -64,17 +69,16 @@ pub(crate) fn check_is_apub_id_valid(
   // TODO: might be good to put the part above in one method, and below in another
   //       (which only gets called in apub::objects)
   //        -> no that doesnt make sense, we still need the code below for blocklist and strict allowlist
-  if let Some(blocked) = settings.to_owned().federation.blocked_instances {
-    if blocked.contains(&domain) {
-      let err = anyhow!("{} is in federation blocklist", domain);
-      return Err(LemmyError::from_error_message(err, "federation_blocked"));
-    }
+  let lists = context.federation_lists();
+  if lists.blocked.contains(&domain) {
+    let err = anyhow!("{} is in federation blocklist", domain);
+    return Err(LemmyError::from_error_message(err, "federation_blocked"));
   }
 
-  if let Some(mut allowed) = settings.to_owned().federation.allowed_instances {
+  let mut allowed = lists.allowed;
+  if !allowed.is_empty() {
     // Only check allowlist if this is a community, or strict allowlist is enabled.
-    let strict_allowlist = settings.to_owned().federation.strict_allowlist;
-    if use_strict_allowlist || strict_allowlist {
+    if use_strict_allowlist || lists.strict_allowlist {
       // need to allow this explicitly because apub receive might contain objects from our local
       // instance.
       allowed.push(local_instance);
@@ -200,6 +204,16 @@ pub fn generate_outbox_url(actor_id: &DbUrl) -> Result<DbUrl, ParseError> {
   Ok(Url::parse(&format!("{}/outbox", actor_id))?.into())
 }
 
+/// Outboxes are split into pages, which are selected with a query parameter.
+pub(crate) fn generate_outbox_page_url(outbox_id: &Url, page: i64) -> Result<Url, ParseError> {
+  Url::parse(&format!("{}?page={}", outbox_id, page))
+}
+
+/// Collection with the comments of a post
+pub(crate) fn generate_replies_url(post_id: &DbUrl) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("{}/replies", post_id))?.into())
+}
+
 fn generate_moderators_url(community_id: &DbUrl) -> Result<DbUrl, LemmyError> {
   Ok(Url::parse(&format!("{}/moderators", community_id))?.into())
 }
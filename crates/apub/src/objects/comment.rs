@@ -4,7 +4,9 @@ use crate::{
   mentions::collect_non_local_mentions,
   objects::read_from_string_or_source,
   protocol::{
+    content_map,
     objects::{note::Note, tombstone::Tombstone},
+    LanguageTag,
     Source,
   },
   PostOrComment,
@@ -110,13 +112,17 @@ impl ApubObject for ApubComment {
     let maa =
       collect_non_local_mentions(&self, ObjectId::new(community.actor_id), context, &mut 0).await?;
 
+    let content = markdown_to_html(&self.content);
     let note = Note {
       r#type: NoteType::Note,
       id: ObjectId::new(self.ap_id.clone()),
       attributed_to: ObjectId::new(creator.actor_id),
       to: vec![public()],
       cc: maa.ccs,
-      content: markdown_to_html(&self.content),
+      summary: self.content_warning.clone(),
+      content: content.clone(),
+      content_map: content_map(&self.language, Some(content)),
+      language: LanguageTag::new(self.language.clone()),
       media_type: Some(MediaTypeHtml::Html),
       source: Some(Source::new(self.content.clone())),
       in_reply_to,
@@ -148,7 +154,7 @@ impl ApubObject for ApubComment {
       Community::read(conn, community_id)
     })
     .await??;
-    check_is_apub_id_valid(note.id.inner(), community.local, &context.settings())?;
+    check_is_apub_id_valid(note.id.inner(), community.local, context)?;
     verify_person_in_community(
       &note.attributed_to,
       &community.into(),
@@ -179,7 +185,11 @@ impl ApubObject for ApubComment {
 
     let content = read_from_string_or_source(&note.content, &note.source);
     let content_slurs_removed = remove_slurs(&content, &context.settings().slur_regex());
+    let content_warning = note
+      .summary
+      .map(|s| remove_slurs(&s, &context.settings().slur_regex()));
 
+    let language = LanguageTag::to_language(&note.language, &note.content_map, Some(&note.content));
     let form = CommentForm {
       creator_id: creator.id,
       post_id: post.id,
@@ -192,6 +202,8 @@ impl ApubObject for ApubComment {
       deleted: None,
       ap_id: Some(note.id.into()),
       local: Some(false),
+      content_warning: Some(content_warning),
+      language: Some(language),
     };
     let comment = blocking(context.pool(), move |conn| Comment::upsert(conn, &form)).await??;
     Ok(comment.into())
@@ -257,6 +269,8 @@ pub(crate) mod tests {
 
     assert_eq!(comment.ap_id, url.into());
     assert_eq!(comment.content.len(), 14);
+    assert_eq!(comment.content_warning.as_deref(), Some("spoilers"));
+    assert_eq!(comment.language.as_deref(), Some("en"));
     assert!(!comment.local);
     assert_eq!(request_counter, 0);
 
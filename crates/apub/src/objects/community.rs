@@ -81,6 +81,7 @@ impl ApubObject for ApubCommunity {
 
   #[tracing::instrument(skip_all)]
   async fn into_apub(self, _context: &LemmyContext) -> Result<Group, LemmyError> {
+    let also_known_as: Vec<Url> = self.also_known_as.iter().cloned().map(Into::into).collect();
     let group = Group {
       kind: GroupType::Group,
       id: ObjectId::new(self.actor_id()),
@@ -103,6 +104,8 @@ impl ApubObject for ApubCommunity {
       public_key: self.get_public_key()?,
       published: Some(convert_datetime(self.published)),
       updated: self.updated.map(convert_datetime),
+      also_known_as: Some(also_known_as).filter(|a| !a.is_empty()),
+      moved_to: self.moved_to.clone().map(ObjectId::new),
     };
     Ok(group)
   }
@@ -169,6 +172,10 @@ impl ActorType for ApubCommunity {
   fn private_key(&self) -> Option<String> {
     self.private_key.to_owned()
   }
+  /// Cleared by a scheduled task once the grace period is over
+  fn previous_public_key(&self) -> Option<String> {
+    self.previous_public_key.to_owned()
+  }
 
   fn inbox_url(&self) -> Url {
     self.inbox_url.clone().into()
@@ -204,7 +211,7 @@ impl ApubCommunity {
       .unique()
       .filter(|inbox: &Url| inbox.host_str() != Some(&context.settings().hostname))
       // Don't send to blocked instances
-      .filter(|inbox| check_is_apub_id_valid(inbox, false, &context.settings()).is_ok())
+      .filter(|inbox| check_is_apub_id_valid(inbox, false, context).is_ok())
       .collect();
 
     Ok(inboxes)
@@ -4,10 +4,12 @@ use crate::{
   protocol::{objects::instance::Instance, ImageObject, Source},
 };
 use activitystreams_kinds::actor::ServiceType;
+use anyhow::Context;
 use chrono::NaiveDateTime;
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::{
   object_id::ObjectId,
+  signatures::{FetchSigning, SigningActor},
   traits::{ActorType, ApubObject},
   values::MediaTypeHtml,
   verify::verify_domains_match,
@@ -17,6 +19,7 @@ use lemmy_db_schema::{
   source::site::{Site, SiteForm},
 };
 use lemmy_utils::{
+  location_info,
   utils::{check_slurs, check_slurs_opt, convert_datetime, markdown_to_html},
   LemmyError,
 };
@@ -102,7 +105,7 @@ impl ApubObject for ApubSite {
     data: &Self::DataType,
     _request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
-    check_is_apub_id_valid(apub.id.inner(), true, &data.settings())?;
+    check_is_apub_id_valid(apub.id.inner(), true, data)?;
     verify_domains_match(expected_domain, apub.id.inner())?;
     verify_image_domain_matches(expected_domain, &apub.icon)?;
     verify_image_domain_matches(expected_domain, &apub.image)?;
@@ -147,6 +150,10 @@ impl ActorType for ApubSite {
   fn private_key(&self) -> Option<String> {
     self.private_key.to_owned()
   }
+  /// Cleared by a scheduled task once the grace period is over
+  fn previous_public_key(&self) -> Option<String> {
+    self.previous_public_key.to_owned()
+  }
 
   fn inbox_url(&self) -> Url {
     self.inbox_url.clone().into()
@@ -166,6 +173,23 @@ pub fn instance_actor_id_from_url(mut url: Url) -> Url {
   url
 }
 
+/// With authorized fetch enabled, all outgoing fetches are signed with the instance actor, so that
+/// other instances in secure mode accept them.
+pub async fn init_authorized_fetch(context: &LemmyContext) -> Result<(), LemmyError> {
+  if !context.settings().federation.authorized_fetch {
+    return Ok(());
+  }
+  // The site might not be set up yet, then this is called again once it is created
+  if let Ok(site) = blocking(context.pool(), Site::read_local_site).await? {
+    let private_key = site.private_key.context(location_info!())?;
+    context.fetch_signer().set(Some(SigningActor {
+      actor_id: site.actor_id.into(),
+      private_key,
+    }));
+  }
+  Ok(())
+}
+
 /// try to fetch the instance actor (to make things like instance rules available)
 pub(in crate::objects) async fn fetch_instance_actor_for_object(
   object_id: Url,
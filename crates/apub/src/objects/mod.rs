@@ -40,7 +40,9 @@ pub fn verify_image_domain_matches(a: &Url, b: &Option<ImageObject>) -> Result<(
 
 #[cfg(test)]
 pub(crate) mod tests {
+  use crate::activity_queue::InstanceHealthTracker;
   use actix::Actor;
+  use background_jobs::memory_storage::Storage;
   use diesel::{
     r2d2::{ConnectionManager, Pool},
     PgConnection,
@@ -57,7 +59,7 @@ pub(crate) mod tests {
     settings::structs::Settings,
     LemmyError,
   };
-  use lemmy_websocket::{chat_server::ChatServer, LemmyContext};
+  use lemmy_websocket::{chat_server::ChatServer, InstanceState, LemmyContext};
   use parking_lot::Mutex;
   use reqwest::Client;
   use reqwest_middleware::ClientBuilder;
@@ -66,10 +68,6 @@ pub(crate) mod tests {
   // TODO: would be nice if we didnt have to use a full context for tests.
   //       or at least write a helper function so this code is shared with main.rs
   pub(crate) fn init_context() -> LemmyContext {
-    let client = reqwest::Client::new().into();
-    // activity queue isnt used in tests, so worker count makes no difference
-    let queue_manager = create_activity_queue(client, 4);
-    let activity_queue = queue_manager.queue_handle().clone();
     // call this to run migrations
     establish_unpooled_connection();
     let settings = Settings::init().unwrap();
@@ -96,9 +94,19 @@ pub(crate) mod tests {
       .max_size(settings.database.pool_size)
       .build(manager)
       .unwrap_or_else(|_| panic!("Error connecting to {}", db_url));
+    // activity queue isnt used in tests, so worker count makes no difference
+    let instance_health = InstanceHealthTracker::new(pool.clone(), &settings);
+    let queue_manager = create_activity_queue(
+      Storage::new(),
+      Arc::new(instance_health),
+      reqwest::Client::new().into(),
+      4,
+    );
+    let activity_queue = queue_manager.queue_handle().clone();
     async fn x() -> Result<String, LemmyError> {
       Ok("".to_string())
     }
+    let state = InstanceState::new(settings);
     let chat_server = ChatServer::startup(
       pool.clone(),
       rate_limiter,
@@ -106,10 +114,10 @@ pub(crate) mod tests {
       |_, _, _, _| Box::pin(x()),
       client.clone(),
       activity_queue.clone(),
-      settings.clone(),
       secret.clone(),
+      state.clone(),
     )
     .start();
-    LemmyContext::create(pool, chat_server, client, activity_queue, settings, secret)
+    LemmyContext::create(pool, chat_server, client, activity_queue, secret, state)
   }
 }
@@ -93,6 +93,7 @@ impl ApubObject for ApubPerson {
       UserTypes::Person
     };
 
+    let also_known_as: Vec<Url> = self.also_known_as.iter().cloned().map(Into::into).collect();
     let person = Person {
       kind,
       id: ObjectId::new(self.actor_id.clone()),
@@ -111,6 +112,8 @@ impl ApubObject for ApubPerson {
       public_key: self.get_public_key()?,
       updated: self.updated.map(convert_datetime),
       inbox: self.inbox_url.clone().into(),
+      also_known_as: Some(also_known_as).filter(|a| !a.is_empty()),
+      moved_to: self.moved_to.clone().map(ObjectId::new),
     };
     Ok(person)
   }
@@ -127,7 +130,7 @@ impl ApubObject for ApubPerson {
     _request_counter: &mut i32,
   ) -> Result<(), LemmyError> {
     verify_domains_match(person.id.inner(), expected_domain)?;
-    check_is_apub_id_valid(person.id.inner(), false, &context.settings())?;
+    check_is_apub_id_valid(person.id.inner(), false, context)?;
     verify_image_domain_matches(expected_domain, &person.icon)?;
     verify_image_domain_matches(expected_domain, &person.image)?;
 
@@ -169,6 +172,17 @@ impl ApubObject for ApubPerson {
       inbox_url: Some(person.inbox.into()),
       shared_inbox_url: Some(person.endpoints.map(|e| e.shared_inbox.into())),
       matrix_user_id: Some(person.matrix_user_id),
+      // Only overwrite if set, so that a move received as activity isnt reverted by software which
+      // doesnt publish it in the profile
+      moved_to: person.moved_to.map(|m| Some(m.into())),
+      also_known_as: Some(
+        person
+          .also_known_as
+          .unwrap_or_default()
+          .into_iter()
+          .map(Into::into)
+          .collect(),
+      ),
     };
     let person = blocking(context.pool(), move |conn| {
       DbPerson::upsert(conn, &person_form)
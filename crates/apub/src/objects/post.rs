@@ -1,17 +1,20 @@
 use crate::{
   activities::{verify_is_public, verify_person_in_community},
   check_is_apub_id_valid,
+  generate_replies_url,
   objects::read_from_string_or_source_opt,
   protocol::{
+    content_map,
     objects::{
-      page::{Attachment, Page, PageType},
+      page::{Attachment, Page, PageType, QuestionOption, QuestionOptionReplies},
       tombstone::Tombstone,
     },
     ImageObject,
+    LanguageTag,
     Source,
   },
 };
-use activitystreams_kinds::public;
+use activitystreams_kinds::{collection::CollectionType, object::NoteType, public};
 use chrono::NaiveDateTime;
 use lemmy_api_common::blocking;
 use lemmy_apub_lib::{
@@ -25,13 +28,14 @@ use lemmy_db_schema::{
   source::{
     community::Community,
     person::Person,
+    poll::{Poll, PollForm, PollOption, PollOptionForm},
     post::{Post, PostForm},
   },
   traits::Crud,
 };
 use lemmy_utils::{
   request::fetch_site_data,
-  utils::{check_slurs, convert_datetime, markdown_to_html, remove_slurs},
+  utils::{check_slurs, clean_url_params, convert_datetime, markdown_to_html, remove_slurs},
   LemmyError,
 };
 use lemmy_websocket::LemmyContext;
@@ -100,15 +104,52 @@ impl ApubObject for ApubPost {
       Community::read(conn, community_id)
     })
     .await??;
+    let post_id = self.id;
+    let poll = blocking(context.pool(), move |conn| -> Result<_, LemmyError> {
+      match Poll::read_for_post(conn, post_id)? {
+        Some(poll) => {
+          let options = PollOption::list_for_poll(conn, poll.id)?;
+          Ok(Some((poll, options)))
+        }
+        None => Ok(None),
+      }
+    })
+    .await??;
+    let (r#type, one_of, any_of, end_time) = match poll {
+      Some((poll, options)) => {
+        let options: Vec<QuestionOption> = options
+          .into_iter()
+          .map(|o| QuestionOption {
+            r#type: NoteType::Note,
+            name: o.name,
+            replies: QuestionOptionReplies {
+              r#type: CollectionType::Collection,
+              total_items: o.vote_count,
+            },
+          })
+          .collect();
+        let end_time = poll.end_time.map(convert_datetime);
+        if poll.multiple_choice {
+          (PageType::Question, None, Some(options), end_time)
+        } else {
+          (PageType::Question, Some(options), None, end_time)
+        }
+      }
+      None => (PageType::Page, None, None, None),
+    };
 
+    let content = self.body.as_ref().map(|b| markdown_to_html(b));
     let page = Page {
-      r#type: PageType::Page,
+      r#type,
       id: ObjectId::new(self.ap_id.clone()),
       attributed_to: ObjectId::new(creator.actor_id),
       to: vec![community.actor_id.into(), public()],
       cc: vec![],
       name: self.name.clone(),
-      content: self.body.as_ref().map(|b| markdown_to_html(b)),
+      summary: self.content_warning.clone(),
+      content: content.clone(),
+      content_map: content_map(&self.language, content),
+      language: LanguageTag::new(self.language.clone()),
       media_type: Some(MediaTypeHtml::Html),
       source: self.body.clone().map(Source::new),
       url: self.url.clone().map(|u| u.into()),
@@ -119,6 +160,15 @@ impl ApubObject for ApubPost {
       stickied: Some(self.stickied),
       published: Some(convert_datetime(self.published)),
       updated: self.updated.map(convert_datetime),
+      replies: if self.local {
+        Some(generate_replies_url(&self.ap_id)?.into())
+      } else {
+        None
+      },
+      one_of,
+      any_of,
+      end_time,
+      closed: None,
     };
     Ok(page)
   }
@@ -141,9 +191,12 @@ impl ApubObject for ApubPost {
     };
 
     let community = page.extract_community(context, request_counter).await?;
-    check_is_apub_id_valid(page.id.inner(), community.local, &context.settings())?;
+    check_is_apub_id_valid(page.id.inner(), community.local, context)?;
     verify_person_in_community(&page.attributed_to, &community, context, request_counter).await?;
     check_slurs(&page.name, &context.settings().slur_regex())?;
+    for option in page.one_of.iter().chain(page.any_of.iter()).flatten() {
+      check_slurs(&option.name, &context.settings().slur_regex())?;
+    }
     verify_domains_match(page.attributed_to.inner(), page.id.inner())?;
     verify_is_public(&page.to, &page.cc)?;
     Ok(())
@@ -180,9 +233,14 @@ impl ApubObject for ApubPost {
 
     let body_slurs_removed = read_from_string_or_source_opt(&page.content, &page.source)
       .map(|s| remove_slurs(&s, &context.settings().slur_regex()));
+    let content_warning = page
+      .summary
+      .map(|s| remove_slurs(&s, &context.settings().slur_regex()));
+    let language =
+      LanguageTag::to_language(&page.language, &page.content_map, page.content.as_deref());
     let form = PostForm {
       name: page.name.clone(),
-      url: url.map(Into::into),
+      url: url.map(|u| clean_url_params(u).into()),
       body: body_slurs_removed,
       creator_id: creator.id,
       community_id: community.id,
@@ -199,8 +257,37 @@ impl ApubObject for ApubPost {
       thumbnail_url: pictrs_thumbnail.map(|u| u.into()),
       ap_id: Some(page.id.into()),
       local: Some(false),
+      content_warning: Some(content_warning),
+      language: Some(language),
+      scheduled_publish_time: None,
     };
     let post = blocking(context.pool(), move |conn| Post::upsert(conn, &form)).await??;
+
+    let (multiple_choice, options) = match (page.one_of, page.any_of) {
+      (Some(options), _) => (false, Some(options)),
+      (None, Some(options)) => (true, Some(options)),
+      (None, None) => (false, None),
+    };
+    if let Some(options) = options {
+      let poll_form = PollForm {
+        post_id: post.id,
+        multiple_choice: Some(multiple_choice),
+        end_time: Some(page.end_time.or(page.closed).map(|t| t.naive_local())),
+      };
+      blocking(context.pool(), move |conn| -> Result<(), LemmyError> {
+        let poll = Poll::upsert(conn, &poll_form)?;
+        for option in options {
+          let option_form = PollOptionForm {
+            poll_id: poll.id,
+            name: option.name,
+            vote_count: Some(option.replies.total_items),
+          };
+          PollOption::upsert(conn, &option_form)?;
+        }
+        Ok(())
+      })
+      .await??;
+    }
     Ok(post.into())
   }
 }
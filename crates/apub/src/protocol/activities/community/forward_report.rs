@@ -0,0 +1,28 @@
+use crate::{
+  fetcher::post_or_comment::PostOrComment,
+  objects::{instance::ApubSite, person::ApubPerson},
+  protocol::Unparsed,
+};
+use activitystreams_kinds::activity::FlagType;
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent by the instance of a community to the home instance of the author of a reported post or
+/// comment. The instance actor is used so that the original reporter stays anonymous.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardReport {
+  pub(crate) actor: ObjectId<ApubSite>,
+  #[serde(deserialize_with = "crate::deserialize_one")]
+  pub(crate) to: [ObjectId<ApubPerson>; 1],
+  /// The reported account and the reported post or comment, in the same format as Mastodon
+  pub(crate) object: (ObjectId<ApubPerson>, ObjectId<PostOrComment>),
+  pub(crate) summary: String,
+  #[serde(rename = "type")]
+  pub(crate) kind: FlagType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
@@ -1,7 +1,9 @@
 pub mod add_mod;
 pub mod announce;
+pub mod forward_report;
 pub mod remove_mod;
 pub mod report;
+pub mod resolve_report;
 pub mod update;
 
 #[cfg(test)]
@@ -10,8 +12,10 @@ mod tests {
     activities::community::{
       add_mod::AddMod,
       announce::AnnounceActivity,
+      forward_report::ForwardReport,
       remove_mod::RemoveMod,
       report::Report,
+      resolve_report::ResolveReport,
       update::UpdateCommunity,
     },
     tests::test_parse_lemmy_item,
@@ -34,5 +38,13 @@ mod tests {
     .unwrap();
 
     test_parse_lemmy_item::<Report>("assets/lemmy/activities/community/report_page.json").unwrap();
+    test_parse_lemmy_item::<ForwardReport>(
+      "assets/lemmy/activities/community/forward_report_page.json",
+    )
+    .unwrap();
+    test_parse_lemmy_item::<ResolveReport>(
+      "assets/lemmy/activities/community/resolve_report_page.json",
+    )
+    .unwrap();
   }
 }
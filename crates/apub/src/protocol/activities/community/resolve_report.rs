@@ -0,0 +1,43 @@
+use crate::{
+  fetcher::post_or_comment::PostOrComment,
+  objects::{instance::ApubSite, person::ApubPerson},
+  protocol::Unparsed,
+};
+use activitystreams_kinds::activity::FlagType;
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use url::Url;
+
+#[derive(Clone, Debug, Display, Deserialize, Serialize, PartialEq)]
+pub enum ResolveType {
+  Resolve,
+}
+
+/// Sent by the instance of a community once a report was resolved there, to the instance of the
+/// reporter and to the instance of the reported author.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveReport {
+  pub(crate) actor: ObjectId<ApubSite>,
+  #[serde(deserialize_with = "crate::deserialize_one")]
+  pub(crate) to: [ObjectId<ApubPerson>; 1],
+  pub(crate) object: ReportReference,
+  #[serde(rename = "type")]
+  pub(crate) kind: ResolveType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
+
+/// Identifies the resolved report by its creator and the reported post or comment. For forwarded
+/// reports the creator is the instance actor.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportReference {
+  pub(crate) actor: Url,
+  pub(crate) object: ObjectId<PostOrComment>,
+  #[serde(rename = "type")]
+  pub(crate) kind: FlagType,
+}
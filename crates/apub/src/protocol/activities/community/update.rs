@@ -22,6 +22,11 @@ pub struct UpdateCommunity {
   #[serde(rename = "type")]
   pub(crate) kind: UpdateType,
   pub(crate) id: Url,
+  /// Lemmy extension, set when the key of the actor was replaced because the previous one can't be
+  /// trusted anymore. Receivers then stop accepting the previous key without a grace period.
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) revoke_previous_key: Option<bool>,
 
   #[serde(flatten)]
   pub(crate) unparsed: Unparsed,
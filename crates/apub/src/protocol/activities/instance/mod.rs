@@ -0,0 +1,17 @@
+pub mod update;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::{
+    activities::instance::update::UpdateInstance,
+    tests::test_parse_lemmy_item,
+  };
+
+  #[test]
+  fn test_parse_lemmy_instance_activities() {
+    test_parse_lemmy_item::<UpdateInstance>(
+      "assets/lemmy/activities/instance/update_instance.json",
+    )
+    .unwrap();
+  }
+}
@@ -0,0 +1,33 @@
+use crate::{
+  objects::instance::ApubSite,
+  protocol::{objects::instance::Instance, Unparsed},
+};
+use activitystreams_kinds::activity::UpdateType;
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent by the instance actor to all known instances when its own fields change, eg after the
+/// keys were rotated.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInstance {
+  pub(crate) actor: ObjectId<ApubSite>,
+  #[serde(deserialize_with = "crate::deserialize_one_or_many")]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: Box<Instance>,
+  #[serde(deserialize_with = "crate::deserialize_one_or_many")]
+  #[serde(default)]
+  pub(crate) cc: Vec<Url>,
+  #[serde(rename = "type")]
+  pub(crate) kind: UpdateType,
+  pub(crate) id: Url,
+  /// Lemmy extension, set when the key of the actor was replaced because the previous one can't be
+  /// trusted anymore. Receivers then stop accepting the previous key without a grace period.
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) revoke_previous_key: Option<bool>,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
@@ -0,0 +1,47 @@
+pub mod move_community;
+pub mod move_person;
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    activity_lists::{
+      AnnouncableActivities,
+      GroupInboxActivities,
+      PersonInboxActivities,
+      SharedInboxActivities,
+    },
+    protocol::{
+      activities::migration::{move_community::MoveCommunity, move_person::MovePerson},
+      tests::{file_to_json_object, test_parse_lemmy_item},
+    },
+  };
+
+  #[test]
+  fn test_parse_lemmy_move() {
+    test_parse_lemmy_item::<MovePerson>("assets/lemmy/activities/migration/move_person.json")
+      .unwrap();
+    test_parse_lemmy_item::<MoveCommunity>("assets/lemmy/activities/migration/move_community.json")
+      .unwrap();
+  }
+
+  #[test]
+  fn test_parse_move_in_inboxes() {
+    let move_person = "assets/lemmy/activities/migration/move_person.json";
+    let move_community = "assets/lemmy/activities/migration/move_community.json";
+
+    let person_inbox = file_to_json_object::<PersonInboxActivities>(move_person).unwrap();
+    assert!(matches!(person_inbox, PersonInboxActivities::MovePerson(_)));
+    let shared_inbox = file_to_json_object::<SharedInboxActivities>(move_person).unwrap();
+    assert!(matches!(
+      shared_inbox,
+      SharedInboxActivities::GroupInboxActivities(g) if matches!(*g, GroupInboxActivities::MovePerson(_))
+    ));
+
+    // community moves have the same fields, and must not be mistaken for account moves
+    let person_inbox = file_to_json_object::<PersonInboxActivities>(move_community).unwrap();
+    assert!(matches!(
+      person_inbox,
+      PersonInboxActivities::AnnouncableActivities(AnnouncableActivities::MoveCommunity(_))
+    ));
+  }
+}
@@ -0,0 +1,20 @@
+use crate::{objects::person::ApubPerson, protocol::Unparsed};
+use activitystreams_kinds::activity::MoveType;
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent by an account which moved to `target`. Actor and object are both the old account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovePerson {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  pub(crate) object: ObjectId<ApubPerson>,
+  pub(crate) target: ObjectId<ApubPerson>,
+  #[serde(rename = "type")]
+  pub(crate) kind: MoveType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
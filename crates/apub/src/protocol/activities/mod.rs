@@ -6,6 +6,10 @@ pub mod community;
 pub mod create_or_update;
 pub mod deletion;
 pub mod following;
+pub mod instance;
+pub mod migration;
+pub mod poll;
+pub mod reaction;
 pub mod voting;
 
 #[derive(Clone, Debug, Display, Deserialize, Serialize, PartialEq)]
@@ -0,0 +1,38 @@
+use crate::{
+  objects::{person::ApubPerson, post::ApubPost},
+  protocol::Unparsed,
+};
+use activitystreams_kinds::{activity::CreateType, object::NoteType};
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Vote in a poll, sent directly to the poll author. As in Mastodon, there is one activity for
+/// each chosen option.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePollAnswer {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "crate::deserialize_one")]
+  pub(crate) to: [ObjectId<ApubPerson>; 1],
+  pub(crate) object: PollAnswer,
+  #[serde(rename = "type")]
+  pub(crate) kind: CreateType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
+
+/// Unlike a comment, the answer has a `name` (the option text) and no `content`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollAnswer {
+  pub(crate) r#type: NoteType,
+  pub(crate) id: Url,
+  pub(crate) attributed_to: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "crate::deserialize_one")]
+  pub(crate) to: [ObjectId<ApubPerson>; 1],
+  pub(crate) name: String,
+  pub(crate) in_reply_to: ObjectId<ApubPost>,
+}
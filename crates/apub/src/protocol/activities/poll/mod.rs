@@ -0,0 +1,22 @@
+pub mod create_poll_answer;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::{
+    activities::poll::create_poll_answer::CreatePollAnswer,
+    tests::{test_json, test_parse_lemmy_item},
+  };
+
+  #[test]
+  fn test_parse_lemmy_poll_answer() {
+    test_parse_lemmy_item::<CreatePollAnswer>(
+      "assets/lemmy/activities/poll/create_poll_answer.json",
+    )
+    .unwrap();
+  }
+
+  #[test]
+  fn test_parse_poll_answer_other_software() {
+    test_json::<CreatePollAnswer>("assets/mastodon/activities/create_poll_answer.json").unwrap();
+  }
+}
@@ -0,0 +1,40 @@
+use crate::{
+  fetcher::post_or_comment::PostOrComment,
+  objects::person::ApubPerson,
+  protocol::Unparsed,
+};
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use url::Url;
+
+/// Emoji reaction to a post or comment, which is separate from votes. Lemmy sends it as
+/// `EmojiReact` like Pleroma, and also receives `Like` with an emoji in `content` as sent by
+/// Misskey. Older Lemmy versions would count the latter as an upvote.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiReact {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  /// Misskey doesn't address reactions to anyone
+  #[serde(deserialize_with = "crate::deserialize_one_or_many")]
+  #[serde(default)]
+  pub(crate) to: Vec<Url>,
+  pub(crate) object: ObjectId<PostOrComment>,
+  #[serde(deserialize_with = "crate::deserialize_one_or_many")]
+  #[serde(default)]
+  pub(crate) cc: Vec<Url>,
+  /// The emoji, or the shortcode of a custom emoji
+  pub(crate) content: String,
+  #[serde(rename = "type")]
+  pub(crate) kind: EmojiReactType,
+  pub(crate) id: Url,
+
+  #[serde(flatten)]
+  pub(crate) unparsed: Unparsed,
+}
+
+#[derive(Clone, Debug, Display, Deserialize, Serialize, PartialEq)]
+pub enum EmojiReactType {
+  EmojiReact,
+  Like,
+}
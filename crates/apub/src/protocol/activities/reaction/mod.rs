@@ -0,0 +1,26 @@
+pub mod emoji_react;
+pub mod undo_emoji_react;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::{
+    activities::reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+    tests::{test_json, test_parse_lemmy_item},
+  };
+
+  #[test]
+  fn test_parse_lemmy_reaction() {
+    test_parse_lemmy_item::<EmojiReact>("assets/lemmy/activities/reaction/emoji_react.json")
+      .unwrap();
+    test_parse_lemmy_item::<UndoEmojiReact>(
+      "assets/lemmy/activities/reaction/undo_emoji_react.json",
+    )
+    .unwrap();
+  }
+
+  #[test]
+  fn test_parse_reaction_other_software() {
+    test_json::<EmojiReact>("assets/pleroma/activities/emoji_react.json").unwrap();
+    test_json::<EmojiReact>("assets/misskey/activities/like_reaction.json").unwrap();
+  }
+}
@@ -1,8 +1,10 @@
 use crate::protocol::activities::community::announce::AnnounceActivity;
 use activitystreams_kinds::collection::OrderedCollectionType;
 use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use url::Url;
 
+#[skip_serializing_none]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupOutbox {
@@ -10,4 +12,6 @@ pub struct GroupOutbox {
   pub(crate) id: Url,
   pub(crate) total_items: i32,
   pub(crate) ordered_items: Vec<AnnounceActivity>,
+  /// Following page with older posts
+  pub(crate) next: Option<Url>,
 }
@@ -2,6 +2,8 @@ pub(crate) mod empty_outbox;
 pub(crate) mod group_followers;
 pub(crate) mod group_moderators;
 pub(crate) mod group_outbox;
+pub(crate) mod person_outbox;
+pub(crate) mod post_replies;
 
 #[cfg(test)]
 mod tests {
@@ -11,6 +13,7 @@ mod tests {
       group_followers::GroupFollowers,
       group_moderators::GroupModerators,
       group_outbox::GroupOutbox,
+      person_outbox::{PersonOutbox, PersonOutboxPage},
     },
     tests::test_parse_lemmy_item,
   };
@@ -24,6 +27,12 @@ mod tests {
     assert_eq!(outbox.ordered_items.len() as i32, outbox.total_items);
     test_parse_lemmy_item::<GroupModerators>("assets/lemmy/collections/group_moderators.json")
       .unwrap();
-    test_parse_lemmy_item::<EmptyOutbox>("assets/lemmy/collections/person_outbox.json").unwrap();
+    test_parse_lemmy_item::<EmptyOutbox>("assets/lemmy/collections/site_outbox.json").unwrap();
+    test_parse_lemmy_item::<PersonOutbox>("assets/lemmy/collections/person_outbox.json").unwrap();
+    let page =
+      test_parse_lemmy_item::<PersonOutboxPage>("assets/lemmy/collections/person_outbox_page.json")
+        .unwrap();
+    assert_eq!(2, page.ordered_items.len());
+    assert!(page.next.is_none());
   }
 }
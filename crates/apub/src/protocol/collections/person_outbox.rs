@@ -0,0 +1,30 @@
+use crate::{
+  activity_lists::AnnouncableActivities,
+  collections::person_outbox::ApubPersonOutboxPage,
+};
+use activitystreams_kinds::collection::{OrderedCollectionPageType, OrderedCollectionType};
+use lemmy_apub_lib::object_id::ObjectId;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// Outbox of a person, the activities themselves are split into pages.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonOutbox {
+  pub(crate) r#type: OrderedCollectionType,
+  pub(crate) id: Url,
+  pub(crate) total_items: i64,
+  pub(crate) first: ObjectId<ApubPersonOutboxPage>,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonOutboxPage {
+  pub(crate) r#type: OrderedCollectionPageType,
+  pub(crate) id: Url,
+  pub(crate) part_of: Url,
+  pub(crate) next: Option<ObjectId<ApubPersonOutboxPage>>,
+  pub(crate) ordered_items: Vec<AnnouncableActivities>,
+}
@@ -0,0 +1,18 @@
+use crate::protocol::objects::note::Note;
+use activitystreams_kinds::collection::OrderedCollectionType;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// Comments of a post, oldest first so that parent comments come before their replies.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostReplies {
+  pub(crate) r#type: OrderedCollectionType,
+  pub(crate) id: Url,
+  pub(crate) total_items: i32,
+  pub(crate) ordered_items: Vec<Note>,
+  /// Following page with newer comments
+  pub(crate) next: Option<Url>,
+}
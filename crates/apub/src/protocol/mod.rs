@@ -1,6 +1,7 @@
 use activitystreams_kinds::object::ImageType;
 use lemmy_apub_lib::values::MediaTypeMarkdown;
 use lemmy_db_schema::newtypes::DbUrl;
+use lemmy_utils::utils::is_valid_language;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use url::Url;
@@ -42,13 +43,58 @@ impl ImageObject {
   }
 }
 
+/// Language of a post or comment, in the format used by Peertube
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageTag {
+  pub(crate) identifier: String,
+}
+
+impl LanguageTag {
+  pub(crate) fn new(language: Option<String>) -> Option<Self> {
+    language.map(|identifier| LanguageTag { identifier })
+  }
+
+  /// Mastodon only sends the language as key of `contentMap`. If there are several keys, the one
+  /// for the same text as `content` is used, otherwise the first one in sorted order, so that the
+  /// result doesn't depend on the order of the map.
+  pub(crate) fn to_language(
+    language: &Option<LanguageTag>,
+    content_map: &Option<HashMap<String, String>>,
+    content: Option<&str>,
+  ) -> Option<String> {
+    language
+      .as_ref()
+      .map(|l| l.identifier.clone())
+      .or_else(|| {
+        let content_map = content_map.as_ref()?;
+        content_map
+          .iter()
+          .filter(|(_, c)| Some(c.as_str()) == content)
+          .map(|(l, _)| l)
+          .min()
+          .or_else(|| content_map.keys().min())
+          .cloned()
+      })
+      .filter(|l| is_valid_language(l))
+  }
+}
+
+/// Content keyed by its language, if there is one
+pub(crate) fn content_map(
+  language: &Option<String>,
+  content: Option<String>,
+) -> Option<HashMap<String, String>> {
+  Some(HashMap::from([(language.clone()?, content?)]))
+}
+
 #[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
 #[serde(transparent)]
 pub struct Unparsed(HashMap<String, serde_json::Value>);
 
 #[cfg(test)]
 pub(crate) mod tests {
-  use crate::context::WithContext;
+  use crate::{context::WithContext, protocol::LanguageTag};
   use assert_json_diff::assert_json_include;
   use lemmy_utils::LemmyError;
   use serde::{de::DeserializeOwned, Serialize};
@@ -78,4 +124,30 @@ pub(crate) mod tests {
     assert_json_include!(actual: &parsed, expected: raw);
     Ok(parsed)
   }
+
+  #[test]
+  fn test_language_from_content_map() {
+    let content_map = Some(HashMap::from([
+      ("fr".to_string(), "Bonjour".to_string()),
+      ("en".to_string(), "Hello".to_string()),
+      ("de".to_string(), "Hallo".to_string()),
+    ]));
+    let language = |content| LanguageTag::to_language(&None, &content_map, content);
+    assert_eq!(Some("fr".to_string()), language(Some("Bonjour")));
+    assert_eq!(Some("en".to_string()), language(Some("Hello")));
+    // Without a matching entry, the result is still the same every time
+    assert_eq!(Some("de".to_string()), language(Some("Hi")));
+    assert_eq!(Some("de".to_string()), language(None));
+
+    // An explicit language wins over the content map
+    let tag = LanguageTag::new(Some("pt-BR".to_string()));
+    assert_eq!(
+      Some("pt-BR".to_string()),
+      LanguageTag::to_language(&tag, &content_map, Some("Bonjour"))
+    );
+    assert_eq!(
+      None,
+      LanguageTag::to_language(&None, &None, Some("Bonjour"))
+    );
+  }
 }
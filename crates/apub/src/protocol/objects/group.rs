@@ -54,6 +54,10 @@ pub struct Group {
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<FixedOffset>>,
   pub(crate) updated: Option<DateTime<FixedOffset>>,
+  /// previous actors of the same community, which are allowed to move to this one
+  pub(crate) also_known_as: Option<Vec<Url>>,
+  /// set once the community was moved to another instance
+  pub(crate) moved_to: Option<ObjectId<ApubCommunity>>,
 }
 
 impl Group {
@@ -62,7 +66,7 @@ impl Group {
     expected_domain: &Url,
     context: &LemmyContext,
   ) -> Result<(), LemmyError> {
-    check_is_apub_id_valid(self.id.inner(), true, &context.settings())?;
+    check_is_apub_id_valid(self.id.inner(), true, context)?;
     verify_domains_match(expected_domain, self.id.inner())?;
     verify_image_domain_matches(expected_domain, &self.icon)?;
     verify_image_domain_matches(expected_domain, &self.image)?;
@@ -96,6 +100,17 @@ impl Group {
       followers_url: Some(self.followers.into()),
       inbox_url: Some(self.inbox.into()),
       shared_inbox_url: Some(self.endpoints.map(|e| e.shared_inbox.into())),
+      // Only overwrite if set, so that a move received as activity isnt reverted by software which
+      // doesnt publish it in the group
+      moved_to: self.moved_to.map(|m| Some(m.into())),
+      also_known_as: Some(
+        self
+          .also_known_as
+          .unwrap_or_default()
+          .into_iter()
+          .map(Into::into)
+          .collect(),
+      ),
     }
   }
 }
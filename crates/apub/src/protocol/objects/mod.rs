@@ -36,6 +36,7 @@ mod tests {
     test_parse_lemmy_item::<Group>("assets/lemmy/objects/group.json").unwrap();
     test_parse_lemmy_item::<Person>("assets/lemmy/objects/person.json").unwrap();
     test_parse_lemmy_item::<Page>("assets/lemmy/objects/page.json").unwrap();
+    test_parse_lemmy_item::<Page>("assets/lemmy/objects/question.json").unwrap();
     test_parse_lemmy_item::<Note>("assets/lemmy/objects/note.json").unwrap();
     test_parse_lemmy_item::<ChatMessage>("assets/lemmy/objects/chat_message.json").unwrap();
     test_parse_lemmy_item::<Tombstone>("assets/lemmy/objects/tombstone.json").unwrap();
@@ -2,7 +2,7 @@ use crate::{
   fetcher::post_or_comment::PostOrComment,
   mentions::Mention,
   objects::{comment::ApubComment, person::ApubPerson, post::ApubPost},
-  protocol::Source,
+  protocol::{LanguageTag, Source},
 };
 use activitystreams_kinds::object::NoteType;
 use chrono::{DateTime, FixedOffset};
@@ -13,7 +13,7 @@ use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref};
 use url::Url;
 
 #[skip_serializing_none]
@@ -28,7 +28,11 @@ pub struct Note {
   #[serde(default)]
   #[serde(deserialize_with = "crate::deserialize_one_or_many")]
   pub(crate) cc: Vec<Url>,
+  /// Content warning, Mastodon and Pleroma show this instead of the content until expanded
+  pub(crate) summary: Option<String>,
   pub(crate) content: String,
+  pub(crate) content_map: Option<HashMap<String, String>>,
+  pub(crate) language: Option<LanguageTag>,
   pub(crate) in_reply_to: ObjectId<PostOrComment>,
 
   pub(crate) media_type: Option<MediaTypeHtml>,
@@ -1,8 +1,8 @@
 use crate::{
   objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
-  protocol::{ImageObject, Source},
+  protocol::{ImageObject, LanguageTag, Source},
 };
-use activitystreams_kinds::link::LinkType;
+use activitystreams_kinds::{collection::CollectionType, link::LinkType, object::NoteType};
 use chrono::{DateTime, FixedOffset};
 use itertools::Itertools;
 use lemmy_apub_lib::{
@@ -16,6 +16,7 @@ use lemmy_utils::LemmyError;
 use lemmy_websocket::LemmyContext;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use std::collections::HashMap;
 use url::Url;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -23,6 +24,8 @@ pub enum PageType {
   Page,
   Article,
   Note,
+  /// A post with a poll
+  Question,
 }
 
 #[skip_serializing_none]
@@ -39,7 +42,11 @@ pub struct Page {
   #[serde(default)]
   #[serde(deserialize_with = "crate::deserialize_one_or_many")]
   pub(crate) cc: Vec<Url>,
+  /// Content warning, shown instead of the content until expanded
+  pub(crate) summary: Option<String>,
   pub(crate) content: Option<String>,
+  pub(crate) content_map: Option<HashMap<String, String>>,
+  pub(crate) language: Option<LanguageTag>,
   pub(crate) media_type: Option<MediaTypeHtml>,
   #[serde(default)]
   #[serde(deserialize_with = "crate::deserialize_skip_error")]
@@ -56,6 +63,33 @@ pub struct Page {
   pub(crate) stickied: Option<bool>,
   pub(crate) published: Option<DateTime<FixedOffset>>,
   pub(crate) updated: Option<DateTime<FixedOffset>>,
+  /// Collection with the comments of the post, used to fetch them when backfilling a community
+  #[serde(default)]
+  #[serde(deserialize_with = "crate::deserialize_skip_error")]
+  pub(crate) replies: Option<Url>,
+  /// Options of a single choice poll
+  pub(crate) one_of: Option<Vec<QuestionOption>>,
+  /// Options of a multiple choice poll
+  pub(crate) any_of: Option<Vec<QuestionOption>>,
+  pub(crate) end_time: Option<DateTime<FixedOffset>>,
+  /// Sent by Mastodon instead of `end_time` once the poll is over
+  pub(crate) closed: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionOption {
+  pub(crate) r#type: NoteType,
+  pub(crate) name: String,
+  pub(crate) replies: QuestionOptionReplies,
+}
+
+/// Only contains the number of votes for the option
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuestionOptionReplies {
+  pub(crate) r#type: CollectionType,
+  pub(crate) total_items: i32,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -24,7 +24,7 @@ pub struct Person {
   /// username, set at account creation and usually fixed after that
   pub(crate) preferred_username: String,
   pub(crate) inbox: Url,
-  /// mandatory field in activitypub, lemmy currently serves an empty outbox
+  /// contains the person's posts and comments, split into pages
   pub(crate) outbox: Url,
   pub(crate) public_key: PublicKey,
 
@@ -42,4 +42,8 @@ pub struct Person {
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<FixedOffset>>,
   pub(crate) updated: Option<DateTime<FixedOffset>>,
+  /// other accounts of the same person, which are allowed to move to this one
+  pub(crate) also_known_as: Option<Vec<Url>>,
+  /// set once the account was moved to a new one
+  pub(crate) moved_to: Option<ObjectId<ApubPerson>>,
 }
@@ -1,7 +1,7 @@
 use crate::{signatures::sign_and_send, traits::ActorType};
 use anyhow::{anyhow, Context, Error};
 use background_jobs::{
-  memory_storage::Storage,
+  dev::Storage,
   ActixJob,
   Backoff,
   Manager,
@@ -12,7 +12,7 @@ use background_jobs::{
 use lemmy_utils::{location_info, LemmyError};
 use reqwest_middleware::ClientWithMiddleware;
 use serde::{Deserialize, Serialize};
-use std::{env, fmt::Debug, future::Future, pin::Pin};
+use std::{env, fmt::Debug, future::Future, pin::Pin, sync::Arc};
 use tracing::{info, warn};
 use url::Url;
 
@@ -33,7 +33,7 @@ pub async fn send_activity(
       private_key: actor.private_key().context(location_info!())?,
     };
     if env::var("APUB_TESTING_SEND_SYNC").is_ok() {
-      let res = do_send(message, client).await;
+      let res = do_send(message, client, None).await;
       // Don't fail on error, as we intentionally do some invalid actions in tests, to verify that
       // they are rejected on the receiving side. These errors shouldn't bubble up to make the API
       // call fail. This matches the behaviour in production.
@@ -87,11 +87,38 @@ impl ActixJob for SendActivityTask {
   const BACKOFF: Backoff = Backoff::Exponential(3);
 
   fn run(self, state: Self::State) -> Self::Future {
-    Box::pin(async move { do_send(self, &state.client).await })
+    Box::pin(async move {
+      let domain = self.inbox.domain().unwrap_or_default();
+      // Fails the job, so that it is retried later on instead of being dropped
+      if !state.instance_health.should_deliver(domain).await {
+        return Err(anyhow!(
+          "Not sending {} to {}, instance is suspended",
+          self.activity_id,
+          self.inbox
+        ));
+      }
+      do_send(self, &state.client, Some(state.instance_health.as_ref())).await
+    })
   }
 }
 
-async fn do_send(task: SendActivityTask, client: &ClientWithMiddleware) -> Result<(), Error> {
+/// Keeps track of which remote instances can be reached, so that the workers don't spend their
+/// time retrying deliveries to instances which are dead.
+#[async_trait::async_trait(?Send)]
+pub trait InstanceHealth: Send + Sync {
+  /// Returns false if deliveries to the domain are currently suspended
+  async fn should_deliver(&self, domain: &str) -> bool;
+
+  /// Called after each delivery attempt. An instance counts as reachable if it answered at all,
+  /// even if it rejected the activity.
+  async fn record_delivery(&self, domain: &str, reachable: bool);
+}
+
+async fn do_send(
+  task: SendActivityTask,
+  client: &ClientWithMiddleware,
+  instance_health: Option<&dyn InstanceHealth>,
+) -> Result<(), Error> {
   info!("Sending {} to {}", task.activity_id, task.inbox);
   let result = sign_and_send(
     client,
@@ -102,6 +129,14 @@ async fn do_send(task: SendActivityTask, client: &ClientWithMiddleware) -> Resul
   )
   .await;
 
+  if let (Some(instance_health), Some(domain)) = (instance_health, task.inbox.domain()) {
+    let reachable = match &result {
+      Ok(o) => !o.status().is_server_error(),
+      Err(_) => false,
+    };
+    instance_health.record_delivery(domain, reachable).await;
+  }
+
   let r: Result<(), Error> = match result {
     Ok(o) => {
       if !o.status().is_success() {
@@ -129,10 +164,21 @@ async fn do_send(task: SendActivityTask, client: &ClientWithMiddleware) -> Resul
   r
 }
 
-pub fn create_activity_queue(client: ClientWithMiddleware, worker_count: u64) -> Manager {
+/// Starts the workers which deliver outgoing activities. Jobs are kept in the given storage, so
+/// a persistent storage allows pending deliveries to survive a restart.
+pub fn create_activity_queue<S>(
+  storage: S,
+  instance_health: Arc<dyn InstanceHealth>,
+  client: ClientWithMiddleware,
+  worker_count: u64,
+) -> Manager
+where
+  S: Storage + Sync + 'static,
+{
   // Configure and start our workers
-  WorkerConfig::new_managed(Storage::new(), move |_| MyState {
+  WorkerConfig::new_managed(storage, move |_| MyState {
     client: client.clone(),
+    instance_health: instance_health.clone(),
   })
   .register::<SendActivityTask>()
   .set_worker_count("default", worker_count)
@@ -142,4 +188,5 @@ pub fn create_activity_queue(client: ClientWithMiddleware, worker_count: u64) ->
 #[derive(Clone)]
 struct MyState {
   pub client: ClientWithMiddleware,
+  pub instance_health: Arc<dyn InstanceHealth>,
 }
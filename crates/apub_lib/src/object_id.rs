@@ -1,9 +1,12 @@
-use crate::{traits::ApubObject, APUB_JSON_CONTENT_TYPE};
+use crate::{
+  signatures::{build_fetch_request, FetchSigner, FetchSigning},
+  traits::{ApubObject, InstanceSettings},
+};
 use anyhow::anyhow;
 use chrono::{Duration as ChronoDuration, NaiveDateTime, Utc};
 use diesel::NotFound;
-use lemmy_utils::{request::retry, settings::structs::Settings, LemmyError, REQWEST_TIMEOUT};
-use reqwest::StatusCode;
+use lemmy_utils::{request::retry, LemmyError};
+use reqwest::{Response, StatusCode};
 use reqwest_middleware::ClientWithMiddleware;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -25,6 +28,7 @@ impl<Kind> ObjectId<Kind>
 where
   Kind: ApubObject + Send + 'static,
   for<'de2> <Kind as ApubObject>::ApubType: serde::Deserialize<'de2>,
+  <Kind as ApubObject>::DataType: InstanceSettings + FetchSigning,
 {
   pub fn new<T>(url: T) -> Self
   where
@@ -47,7 +51,7 @@ where
     let db_object = self.dereference_from_db(data).await?;
 
     // if its a local object, only fetch it from the database and not over http
-    if self.0.domain() == Some(&Settings::get().get_hostname_without_port()?) {
+    if self.is_local(data)? {
       return match db_object {
         None => Err(NotFound {}.into()),
         Some(o) => Ok(o),
@@ -74,6 +78,23 @@ where
     }
   }
 
+  /// Fetches the object over http even if it is stored in the local database, to make sure that
+  /// its latest version is used. Local objects are still read from the database.
+  pub async fn dereference_forced(
+    &self,
+    data: &<Kind as ApubObject>::DataType,
+    client: &ClientWithMiddleware,
+    request_counter: &mut i32,
+  ) -> Result<Kind, LemmyError> {
+    if self.is_local(data)? {
+      return self.dereference_local(data).await;
+    }
+    let db_object = self.dereference_from_db(data).await?;
+    self
+      .dereference_from_http(data, client, request_counter, db_object)
+      .await
+  }
+
   /// Fetch an object from the local db. Instead of falling back to http, this throws an error if
   /// the object is not found in the database.
   pub async fn dereference_local(
@@ -84,6 +105,11 @@ where
     object.ok_or_else(|| anyhow!("object not found in database {}", self).into())
   }
 
+  fn is_local(&self, data: &<Kind as ApubObject>::DataType) -> Result<bool, LemmyError> {
+    let hostname = data.instance_settings().get_hostname_without_port()?;
+    Ok(self.0.domain() == Some(&hostname))
+  }
+
   /// returning none means the object was not found in local db
   async fn dereference_from_db(
     &self,
@@ -100,23 +126,17 @@ where
     request_counter: &mut i32,
     db_object: Option<Kind>,
   ) -> Result<Kind, LemmyError> {
+    let settings = data.instance_settings();
     // dont fetch local objects this way
-    debug_assert!(self.0.domain() != Some(&Settings::get().hostname));
+    debug_assert!(self.0.domain() != Some(&settings.hostname));
     info!("Fetching remote object {}", self.to_string());
 
     *request_counter += 1;
-    if *request_counter > Settings::get().http_fetch_retry_limit {
+    if *request_counter > settings.http_fetch_retry_limit {
       return Err(LemmyError::from(anyhow!("Request retry limit reached")));
     }
 
-    let res = retry(|| {
-      client
-        .get(self.0.as_str())
-        .header("Accept", APUB_JSON_CONTENT_TYPE)
-        .timeout(REQWEST_TIMEOUT)
-        .send()
-    })
-    .await?;
+    let res = execute_fetch(client, &self.0, data.fetch_signer()).await?;
 
     if res.status() == StatusCode::GONE {
       if let Some(db_object) = db_object {
@@ -174,6 +194,36 @@ where
   }
 }
 
+/// Fetches the json of a remote object without parsing or storing it, to debug federation issues.
+pub async fn fetch_object_json(
+  client: &ClientWithMiddleware,
+  url: &Url,
+  signer: &FetchSigner,
+) -> Result<serde_json::Value, LemmyError> {
+  let res = execute_fetch(client, url, signer).await?;
+  if !res.status().is_success() {
+    return Err(anyhow!("Fetching {} failed with status {}", url, res.status()).into());
+  }
+  Ok(res.json().await?)
+}
+
+/// Sends a get request for `url`, retrying on connection errors.
+async fn execute_fetch(
+  client: &ClientWithMiddleware,
+  url: &Url,
+  signer: &FetchSigner,
+) -> Result<Response, LemmyError> {
+  let request = build_fetch_request(client, url, signer).await?;
+  let res = retry(|| async {
+    let request = request.try_clone().ok_or_else(|| {
+      reqwest_middleware::Error::Middleware(anyhow!("Failed to clone request for {}", url))
+    })?;
+    client.execute(request).await
+  })
+  .await?;
+  Ok(res)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1,5 +1,8 @@
 use crate::APUB_JSON_CONTENT_TYPE;
-use actix_web::HttpRequest;
+use actix_web::{
+  http::{header::HeaderMap as ActixHeaderMap, uri::PathAndQuery, Method},
+  HttpRequest,
+};
 use anyhow::anyhow;
 use http::{header::HeaderName, HeaderMap, HeaderValue};
 use http_signature_normalization_actix::Config as ConfigActix;
@@ -11,11 +14,12 @@ use openssl::{
   pkey::PKey,
   sign::{Signer, Verifier},
 };
-use reqwest::Response;
-use reqwest_middleware::ClientWithMiddleware;
+use parking_lot::RwLock;
+use reqwest::{Request, Response};
+use reqwest_middleware::{ClientWithMiddleware, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 use tracing::debug;
 use url::Url;
 
@@ -31,29 +35,105 @@ pub async fn sign_and_send(
   actor_id: &Url,
   private_key: String,
 ) -> Result<Response, LemmyError> {
-  let signing_key_id = format!("{}#main-key", actor_id);
-
   let mut headers = HeaderMap::new();
-  let mut host = inbox_url.domain().expect("read inbox domain").to_string();
-  if let Some(port) = inbox_url.port() {
-    host = format!("{}:{}", host, port);
-  }
   headers.insert(
     HeaderName::from_str("Content-Type")?,
     HeaderValue::from_str(APUB_JSON_CONTENT_TYPE)?,
   );
-  headers.insert(HeaderName::from_str("Host")?, HeaderValue::from_str(&host)?);
+  headers.insert(HeaderName::from_str("Host")?, host_header(inbox_url)?);
 
-  let request = client
+  let request_builder = client
     .post(&inbox_url.to_string())
     // signature is only valid for 10 seconds, so no reason to wait any longer
     .timeout(REQWEST_TIMEOUT)
-    .headers(headers)
+    .headers(headers);
+  let request = sign_request(request_builder, activity, actor_id, private_key).await?;
+
+  let response = client.execute(request).await?;
+
+  Ok(response)
+}
+
+#[derive(Clone, Debug)]
+pub struct SigningActor {
+  pub actor_id: Url,
+  pub private_key: String,
+}
+
+/// Actor whose key is used to sign outgoing GET requests. Only set if authorized fetch is enabled.
+/// Clones share the same actor, so that it can be replaced for all contexts of an instance.
+#[derive(Clone, Debug, Default)]
+pub struct FetchSigner(Arc<RwLock<Option<SigningActor>>>);
+
+impl FetchSigner {
+  /// Sets the actor for signing outgoing fetches, or disables signing if `None`.
+  pub fn set(&self, actor: Option<SigningActor>) {
+    *self.0.write() = actor;
+  }
+
+  pub fn get(&self) -> Option<SigningActor> {
+    self.0.read().clone()
+  }
+}
+
+/// Data which knows how outgoing fetches of the instance are signed.
+pub trait FetchSigning {
+  fn fetch_signer(&self) -> &FetchSigner;
+}
+
+/// Creates an HTTP get request for `url`. If a signing actor is set, the request is signed so
+/// that instances with authorized fetch enabled accept it.
+pub(crate) async fn build_fetch_request(
+  client: &ClientWithMiddleware,
+  url: &Url,
+  signer: &FetchSigner,
+) -> Result<Request, LemmyError> {
+  let request_builder = client
+    .get(url.as_str())
+    .header("Accept", APUB_JSON_CONTENT_TYPE)
+    .timeout(REQWEST_TIMEOUT);
+  match signer.get() {
+    Some(actor) => {
+      let request_builder = request_builder.header("Host", host_header(url)?);
+      sign_request(
+        request_builder,
+        String::new(),
+        &actor.actor_id,
+        actor.private_key,
+      )
+      .await
+    }
+    None => Ok(request_builder.build()?),
+  }
+}
+
+/// Value of the Host header for requests to `url`. Also works for IP addresses, which have no
+/// domain.
+fn host_header(url: &Url) -> Result<HeaderValue, LemmyError> {
+  let host = url
+    .host_str()
+    .ok_or_else(|| anyhow!("Url {} has no host", url))?;
+  let host = match url.port() {
+    Some(port) => format!("{}:{}", host, port),
+    None => host.to_string(),
+  };
+  Ok(HeaderValue::from_str(&host)?)
+}
+
+/// Signs the request with the key of the given actor, including a digest of the body.
+async fn sign_request(
+  request_builder: RequestBuilder,
+  body: String,
+  actor_id: &Url,
+  private_key: String,
+) -> Result<Request, LemmyError> {
+  let signing_key_id = format!("{}#main-key", actor_id);
+  request_builder
     .signature_with_digest(
       HTTP_SIG_CONFIG.clone(),
       signing_key_id,
       Sha256::new(),
-      activity,
+      body,
       move |signing_string| {
         let private_key = PKey::private_key_from_pem(private_key.as_bytes())?;
         let mut signer = Signer::new(MessageDigest::sha256(), &private_key)?;
@@ -62,21 +142,42 @@ pub async fn sign_and_send(
         Ok(base64::encode(signer.sign_to_vec()?)) as Result<_, LemmyError>
       },
     )
-    .await?;
-
-  let response = client.execute(request).await?;
+    .await
+}
 
-  Ok(response)
+/// Reads the actor id from the key id in the HTTP signature of an incoming request. The signature
+/// itself still needs to be checked with [`verify_signature`].
+pub fn signature_actor_id(request: &HttpRequest) -> Result<Url, LemmyError> {
+  let unverified = CONFIG2.begin_verify(
+    request.method(),
+    request.uri().path_and_query(),
+    request.headers().clone(),
+  )?;
+  let mut actor_id = Url::parse(unverified.key_id())?;
+  actor_id.set_fragment(None);
+  Ok(actor_id)
 }
 
-/// Verifies the HTTP signature on an incoming inbox request.
+/// Verifies the HTTP signature on an incoming request.
 pub fn verify_signature(request: &HttpRequest, public_key: &str) -> Result<(), LemmyError> {
+  verify_signature_parts(
+    request.method(),
+    request.uri().path_and_query(),
+    request.headers().clone(),
+    public_key,
+  )
+}
+
+/// Verifies the HTTP signature of a request which is only known by its method, path and headers,
+/// for example from logs.
+pub fn verify_signature_parts(
+  method: &Method,
+  path_and_query: Option<&PathAndQuery>,
+  headers: ActixHeaderMap,
+  public_key: &str,
+) -> Result<(), LemmyError> {
   let verified = CONFIG2
-    .begin_verify(
-      request.method(),
-      request.uri().path_and_query(),
-      request.headers().clone(),
-    )?
+    .begin_verify(method, path_and_query, headers)?
     .verify(|signature, signing_string| -> Result<bool, LemmyError> {
       debug!(
         "Verifying with key {}, message {}",
@@ -88,11 +189,12 @@ pub fn verify_signature(request: &HttpRequest, public_key: &str) -> Result<(), L
       Ok(verifier.verify(&base64::decode(signature)?)?)
     })?;
 
+  let path = path_and_query.map(PathAndQuery::as_str).unwrap_or_default();
   if verified {
-    debug!("verified signature for {}", &request.uri());
+    debug!("verified signature for {}", path);
     Ok(())
   } else {
-    Err(anyhow!("Invalid signature on request: {}", &request.uri()).into())
+    Err(anyhow!("Invalid signature on request: {}", path).into())
   }
 }
 
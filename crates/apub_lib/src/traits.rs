@@ -1,9 +1,15 @@
 use crate::{data::Data, signatures::PublicKey};
 use chrono::NaiveDateTime;
 pub use lemmy_apub_lib_derive::*;
-use lemmy_utils::LemmyError;
+use lemmy_utils::{settings::structs::Settings, LemmyError};
 use url::Url;
 
+/// Gives access to the settings of the local instance. They are read on every use, because they
+/// can be reloaded while the instance is running.
+pub trait InstanceSettings {
+  fn instance_settings(&self) -> Settings;
+}
+
 #[async_trait::async_trait(?Send)]
 pub trait ActivityHandler {
   type DataType;
@@ -73,6 +79,11 @@ pub trait ActorType {
 
   fn public_key(&self) -> String;
   fn private_key(&self) -> Option<String>;
+  /// The key which was used before the last key rotation, as long as signatures made with it are
+  /// still accepted.
+  fn previous_public_key(&self) -> Option<String> {
+    None
+  }
 
   fn inbox_url(&self) -> Url;
 
@@ -13,6 +13,12 @@ pub struct CommentAggregates {
   pub upvotes: i64,
   pub downvotes: i64,
   pub published: chrono::NaiveDateTime,
+  /// Number of replies below this comment, at any depth
+  pub child_count: i32,
+  /// High for many votes which are split evenly between up- and downvotes
+  pub controversy_rank: f64,
+  /// Orders by the rate of recent upvotes, only changes when someone votes
+  pub rising_rank: f64,
 }
 
 impl CommentAggregates {
@@ -91,7 +97,17 @@ mod tests {
       ..CommentForm::default()
     };
 
-    let _inserted_child_comment = Comment::create(&conn, &child_comment_form).unwrap();
+    let inserted_child_comment = Comment::create(&conn, &child_comment_form).unwrap();
+
+    let grandchild_comment_form = CommentForm {
+      content: "A test comment".into(),
+      creator_id: inserted_person.id,
+      post_id: inserted_post.id,
+      parent_id: Some(inserted_child_comment.id),
+      ..CommentForm::default()
+    };
+
+    let inserted_grandchild_comment = Comment::create(&conn, &grandchild_comment_form).unwrap();
 
     let comment_like = CommentLikeForm {
       comment_id: inserted_comment.id,
@@ -107,6 +123,12 @@ mod tests {
     assert_eq!(1, comment_aggs_before_delete.score);
     assert_eq!(1, comment_aggs_before_delete.upvotes);
     assert_eq!(0, comment_aggs_before_delete.downvotes);
+    // The child count includes replies at any depth
+    assert_eq!(2, comment_aggs_before_delete.child_count);
+
+    Comment::delete(&conn, inserted_grandchild_comment.id).unwrap();
+    let after_grandchild_delete = CommentAggregates::read(&conn, inserted_comment.id).unwrap();
+    assert_eq!(1, after_grandchild_delete.child_count);
 
     // Add a post dislike from the other person
     let comment_dislike = CommentLikeForm {
@@ -17,6 +17,10 @@ pub struct PostAggregates {
   pub published: chrono::NaiveDateTime,
   pub newest_comment_time_necro: chrono::NaiveDateTime, // A newest comment time, limited to 2 days, to prevent necrobumping
   pub newest_comment_time: chrono::NaiveDateTime,
+  /// High for many votes which are split evenly between up- and downvotes
+  pub controversy_rank: f64,
+  /// Orders by the rate of recent upvotes, only changes when someone votes
+  pub rising_rank: f64,
 }
 
 impl PostAggregates {
@@ -111,6 +115,8 @@ mod tests {
     assert_eq!(1, post_aggs_before_delete.score);
     assert_eq!(1, post_aggs_before_delete.upvotes);
     assert_eq!(0, post_aggs_before_delete.downvotes);
+    assert_eq!(0.0, post_aggs_before_delete.controversy_rank);
+    assert!(post_aggs_before_delete.rising_rank > 0.0);
 
     // Add a post dislike from the other person
     let post_dislike = PostLikeForm {
@@ -127,6 +133,13 @@ mod tests {
     assert_eq!(0, post_aggs_after_dislike.score);
     assert_eq!(1, post_aggs_after_dislike.upvotes);
     assert_eq!(1, post_aggs_after_dislike.downvotes);
+    // One up- and one downvote are as controversial as it gets for two votes
+    assert_eq!(2.0, post_aggs_after_dislike.controversy_rank);
+    // Downvotes don't make a post rise
+    assert_eq!(
+      post_aggs_before_delete.rising_rank,
+      post_aggs_after_dislike.rising_rank
+    );
 
     // Remove the parent comment
     Comment::delete(&conn, inserted_comment.id).unwrap();
@@ -143,6 +156,7 @@ mod tests {
     assert_eq!(-1, after_like_remove.score);
     assert_eq!(0, after_like_remove.upvotes);
     assert_eq!(1, after_like_remove.downvotes);
+    assert_eq!(0.0, after_like_remove.controversy_rank);
 
     // This should delete all the associated rows, and fire triggers
     Person::delete(&conn, another_inserted_person.id).unwrap();
@@ -0,0 +1,42 @@
+//! Postgres full text search. The `search_vector` columns of post, comment, community and person
+//! are maintained by triggers, and aren't part of the schema, as Diesel doesn't know their type.
+//! Queries refer to them with `sql::<TsVector>("post.search_vector")`.
+
+use diesel::{expression::Expression, pg::Pg, sql_types::*};
+
+#[derive(Clone, Copy, SqlType)]
+#[postgres(oid = "3614", array_oid = "3643")]
+pub struct TsVector;
+
+#[derive(Clone, Copy, SqlType)]
+#[postgres(oid = "3615", array_oid = "3645")]
+pub struct TsQuery;
+
+infix_operator!(Matches, " @@ ", backend: Pg);
+
+pub trait TsVectorExtensions: Expression<SqlType = TsVector> + Sized {
+  fn matches<T: Expression<SqlType = TsQuery>>(self, query: T) -> Matches<Self, T> {
+    Matches::new(self, query)
+  }
+}
+
+impl<T: Expression<SqlType = TsVector>> TsVectorExtensions for T {}
+
+sql_function! {
+  /// Query for the search terms in web search syntax. Matches the words as written, and also
+  /// stemmed if the language of the search terms is known.
+  fn text_search_query(search_term: Text, language: Nullable<Text>) -> TsQuery;
+}
+
+sql_function!(fn ts_rank_cd(vector: TsVector, query: TsQuery) -> Float);
+
+sql_function! {
+  /// Parts of the document around the matches, with the matched words in markdown bold
+  fn text_search_headline(document: Text, language: Nullable<Text>, query: TsQuery) -> Text;
+}
+
+/// Search terms without any words are left out, so that they match everything like the earlier
+/// substring search. Postgres would match nothing for an empty query.
+pub fn non_blank_search_term(search_term: Option<String>) -> Option<String> {
+  search_term.filter(|t| !t.trim().is_empty())
+}
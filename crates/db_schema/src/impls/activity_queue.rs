@@ -0,0 +1,212 @@
+use crate::{naive_now, source::activity_queue::*};
+use diesel::{dsl::*, result::Error, *};
+use serde_json::Value;
+
+/// The stats are stored in a single row with this id
+const STATS_ROW_ID: i32 = 1;
+
+impl ActivityQueueJob {
+  /// Inserts the job, or updates its serialized data if it already exists. This never touches
+  /// the runner, so that saving a job which is currently being processed keeps it marked as running.
+  pub fn upsert(conn: &PgConnection, form: &ActivityQueueJobForm) -> Result<Self, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    insert_into(activity_queue_job)
+      .values(form)
+      .on_conflict(id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read(conn: &PgConnection, job_id: &str) -> Result<Option<Self>, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    activity_queue_job
+      .find(job_id)
+      .first::<Self>(conn)
+      .optional()
+  }
+
+  /// Claims the next job in the given queue which is not taken by any worker, and is ready to be
+  /// retried. The row is locked while claiming, and rows locked by other workers are skipped, so
+  /// that each job is handed to exactly one worker.
+  pub fn claim_next(
+    conn: &PgConnection,
+    queue_name: &str,
+    runner: &str,
+  ) -> Result<Option<Self>, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    conn.transaction(|| {
+      let now = naive_now();
+      let job_id = activity_queue_job
+        .filter(queue.eq(queue_name))
+        .filter(runner_id.is_null())
+        .filter(next_queue.is_null().or(next_queue.le(now)))
+        // Jobs which have been ready for the longest time first
+        .order_by(sql::<sql_types::Timestamp>("coalesce(next_queue, updated)"))
+        .select(id)
+        .limit(1)
+        .for_update()
+        .skip_locked()
+        .first::<String>(conn)
+        .optional()?;
+      match job_id {
+        Some(job_id) => diesel::update(activity_queue_job.find(job_id))
+          .set((runner_id.eq(runner), updated.eq(now)))
+          .get_result::<Self>(conn)
+          .map(Some),
+        None => Ok(None),
+      }
+    })
+  }
+
+  /// Puts the job (back) into the given queue, releasing it from its worker
+  pub fn enqueue(conn: &PgConnection, job_id: &str, queue_name: &str) -> Result<usize, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    diesel::update(activity_queue_job.find(job_id))
+      .set((
+        queue.eq(queue_name),
+        runner_id.eq(None::<String>),
+        updated.eq(naive_now()),
+      ))
+      .execute(conn)
+  }
+
+  /// Marks the job as claimed by the given worker
+  pub fn claim(conn: &PgConnection, job_id: &str, runner: &str) -> Result<usize, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    diesel::update(activity_queue_job.find(job_id))
+      .set((runner_id.eq(runner), updated.eq(naive_now())))
+      .execute(conn)
+  }
+
+  pub fn delete(conn: &PgConnection, job_id: &str) -> Result<usize, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    diesel::delete(activity_queue_job.find(job_id)).execute(conn)
+  }
+
+  /// Workers don't survive a restart, so jobs which were running at shutdown are released again.
+  pub fn release_all(conn: &PgConnection) -> Result<usize, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    diesel::update(activity_queue_job.filter(runner_id.is_not_null()))
+      .set(runner_id.eq(None::<String>))
+      .execute(conn)
+  }
+
+  pub fn count_pending(conn: &PgConnection) -> Result<i64, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    activity_queue_job
+      .filter(runner_id.is_null())
+      .select(count(id))
+      .first::<i64>(conn)
+  }
+
+  pub fn count_running(conn: &PgConnection) -> Result<i64, Error> {
+    use crate::schema::activity_queue_job::dsl::*;
+    activity_queue_job
+      .filter(runner_id.is_not_null())
+      .select(count(id))
+      .first::<i64>(conn)
+  }
+}
+
+impl ActivityQueueStats {
+  pub fn read(conn: &PgConnection) -> Result<Option<Value>, Error> {
+    use crate::schema::activity_queue_stats::dsl::*;
+    activity_queue_stats
+      .find(STATS_ROW_ID)
+      .select(stats)
+      .first::<Value>(conn)
+      .optional()
+  }
+
+  /// Reads the stats, applies the given function and writes the result back, all in one
+  /// transaction.
+  pub fn update<F, E>(conn: &PgConnection, f: F) -> Result<Value, E>
+  where
+    F: FnOnce(Option<Value>) -> Result<Value, E>,
+    E: From<Error>,
+  {
+    use crate::schema::activity_queue_stats::dsl::*;
+    conn.transaction(|| {
+      let current = activity_queue_stats
+        .find(STATS_ROW_ID)
+        .select(stats)
+        .for_update()
+        .first::<Value>(conn)
+        .optional()?;
+      let form = ActivityQueueStats {
+        id: STATS_ROW_ID,
+        stats: f(current)?,
+      };
+      insert_into(activity_queue_stats)
+        .values(&form)
+        .on_conflict(id)
+        .do_update()
+        .set(&form)
+        .execute(conn)?;
+      Ok(form.stats)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    naive_now,
+    source::activity_queue::{ActivityQueueJob, ActivityQueueJobForm},
+  };
+  use chrono::Duration;
+  use serde_json::json;
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_job_lifecycle() {
+    let conn = establish_unpooled_connection();
+
+    let form = ActivityQueueJobForm {
+      id: "0b3bbb4e-3b3c-4a51-a1a5-7c7b6f6e0e11".into(),
+      queue: "default".into(),
+      job: json!({"name": "SendActivityTask"}),
+      updated: naive_now(),
+      next_queue: Some(None),
+    };
+    let inserted = ActivityQueueJob::upsert(&conn, &form).unwrap();
+    assert_eq!(None, inserted.runner_id);
+
+    // A job which is waiting for its retry backoff can't be claimed yet
+    let backoff_form = ActivityQueueJobForm {
+      id: "5d0f3c5e-8a36-4f4b-9b0e-0f3d2b6b5a21".into(),
+      queue: "default".into(),
+      job: json!({"name": "SendActivityTask"}),
+      updated: naive_now(),
+      next_queue: Some(Some(naive_now() + Duration::hours(1))),
+    };
+    ActivityQueueJob::upsert(&conn, &backoff_form).unwrap();
+
+    let claimed = ActivityQueueJob::claim_next(&conn, "default", "runner")
+      .unwrap()
+      .unwrap();
+    assert_eq!(form.id, claimed.id);
+    assert_eq!(Some("runner".to_string()), claimed.runner_id);
+    // Nothing else is ready
+    assert_eq!(
+      None,
+      ActivityQueueJob::claim_next(&conn, "default", "other").unwrap()
+    );
+
+    // Saving a running job must not release it
+    let saved = ActivityQueueJob::upsert(&conn, &form).unwrap();
+    assert_eq!(Some("runner".to_string()), saved.runner_id);
+
+    ActivityQueueJob::release_all(&conn).unwrap();
+    let released = ActivityQueueJob::read(&conn, &form.id).unwrap().unwrap();
+    assert_eq!(None, released.runner_id);
+
+    ActivityQueueJob::delete(&conn, &backoff_form.id).unwrap();
+    let num_deleted = ActivityQueueJob::delete(&conn, &form.id).unwrap();
+    assert_eq!(1, num_deleted);
+    assert_eq!(None, ActivityQueueJob::read(&conn, &form.id).unwrap());
+  }
+}
@@ -1,15 +1,18 @@
 use crate::{
   naive_now,
-  newtypes::{CommentId, DbUrl, PersonId},
+  newtypes::{CommentId, DbUrl, PersonId, PostId},
   source::comment::{
     Comment,
     CommentForm,
     CommentLike,
     CommentLikeForm,
+    CommentReaction,
+    CommentReactionForm,
+    CommentRevision,
     CommentSaved,
     CommentSavedForm,
   },
-  traits::{Crud, DeleteableOrRemoveable, Likeable, Saveable},
+  traits::{Crud, DeleteableOrRemoveable, Likeable, Reactable, Saveable},
 };
 use diesel::{dsl::*, result::Error, *};
 use url::Url;
@@ -31,7 +34,14 @@ impl Comment {
     conn: &PgConnection,
     for_creator_id: PersonId,
   ) -> Result<Vec<Self>, Error> {
-    use crate::schema::comment::dsl::*;
+    use crate::schema::{comment::dsl::*, comment_revision::dsl as revision};
+
+    // Earlier versions of the comments would otherwise remain readable
+    diesel::delete(revision::comment_revision.filter(
+      revision::comment_id.eq_any(comment.filter(creator_id.eq(for_creator_id)).select(id)),
+    ))
+    .execute(conn)?;
+
     diesel::update(comment.filter(creator_id.eq(for_creator_id)))
       .set((
         content.eq("*Permananently Deleted*"),
@@ -96,6 +106,28 @@ impl Comment {
       .get_result::<Self>(conn)
   }
 
+  pub fn update_content_warning(
+    conn: &PgConnection,
+    comment_id: CommentId,
+    new_content_warning: Option<String>,
+  ) -> Result<Self, Error> {
+    use crate::schema::comment::dsl::*;
+    diesel::update(comment.find(comment_id))
+      .set(content_warning.eq(new_content_warning))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn update_language(
+    conn: &PgConnection,
+    comment_id: CommentId,
+    new_language: Option<String>,
+  ) -> Result<Self, Error> {
+    use crate::schema::comment::dsl::*;
+    diesel::update(comment.find(comment_id))
+      .set(language.eq(new_language))
+      .get_result::<Self>(conn)
+  }
+
   pub fn upsert(conn: &PgConnection, comment_form: &CommentForm) -> Result<Comment, Error> {
     use crate::schema::comment::dsl::*;
     insert_into(comment)
@@ -105,6 +137,55 @@ impl Comment {
       .set(comment_form)
       .get_result::<Self>(conn)
   }
+
+  /// Lists one page of the comments of a post which are not deleted or removed, oldest first so
+  /// that parent comments come before their replies.
+  pub fn list_for_post(
+    conn: &PgConnection,
+    for_post_id: PostId,
+    page: i64,
+    page_size: i64,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::comment::dsl::*;
+    // Huge page numbers from remote requests give an empty page instead of overflowing
+    let offset = (page.max(1) - 1).saturating_mul(page_size);
+    comment
+      .filter(post_id.eq(for_post_id))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .order_by(published.asc())
+      .then_order_by(id.asc())
+      .limit(page_size)
+      .offset(offset)
+      .load::<Self>(conn)
+  }
+
+  /// The ids of up to `levels` parent comments, from the top down. The path ends with the comment
+  /// itself, the ids before it are its parents.
+  pub fn ancestor_ids(&self, levels: usize) -> Vec<CommentId> {
+    let parent_count = self.path.len().saturating_sub(1);
+    self.path[parent_count.saturating_sub(levels)..parent_count]
+      .iter()
+      .map(|id| CommentId(*id))
+      .collect()
+  }
+
+  pub fn read_many(conn: &PgConnection, comment_ids: Vec<CommentId>) -> Result<Vec<Self>, Error> {
+    use crate::schema::comment::dsl::*;
+    comment.filter(id.eq_any(comment_ids)).load::<Self>(conn)
+  }
+
+  /// Counts the comments of a person which are visible to everyone
+  pub fn count_for_creator(conn: &PgConnection, for_creator_id: PersonId) -> Result<i64, Error> {
+    use crate::schema::comment::dsl::*;
+    comment
+      .filter(creator_id.eq(for_creator_id))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .select(count(id))
+      .first::<i64>(conn)
+  }
+
   pub fn read_from_apub_id(conn: &PgConnection, object_id: Url) -> Result<Option<Self>, Error> {
     use crate::schema::comment::dsl::*;
     let object_id: DbUrl = object_id.into();
@@ -150,6 +231,21 @@ impl Crud for Comment {
   }
 }
 
+impl CommentRevision {
+  /// Lists the previous versions of a comment, newest first.
+  pub fn list_for_comment(
+    conn: &PgConnection,
+    for_comment_id: CommentId,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::comment_revision::dsl::*;
+    comment_revision
+      .filter(comment_id.eq(for_comment_id))
+      .order_by(published.desc())
+      .then_order_by(id.desc())
+      .load::<Self>(conn)
+  }
+}
+
 impl Likeable for CommentLike {
   type Form = CommentLikeForm;
   type IdType = CommentId;
@@ -177,6 +273,47 @@ impl Likeable for CommentLike {
   }
 }
 
+impl Reactable for CommentReaction {
+  type Form = CommentReactionForm;
+  fn react(
+    conn: &PgConnection,
+    comment_reaction_form: &CommentReactionForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::comment_reaction::dsl::*;
+    insert_into(comment_reaction)
+      .values(comment_reaction_form)
+      .on_conflict((comment_id, person_id, emoji))
+      .do_update()
+      .set(comment_reaction_form)
+      .get_result::<Self>(conn)
+  }
+  fn unreact(
+    conn: &PgConnection,
+    comment_reaction_form: &CommentReactionForm,
+  ) -> Result<usize, Error> {
+    use crate::schema::comment_reaction::dsl::*;
+    diesel::delete(
+      comment_reaction
+        .filter(comment_id.eq(comment_reaction_form.comment_id))
+        .filter(person_id.eq(comment_reaction_form.person_id))
+        .filter(emoji.eq(&comment_reaction_form.emoji)),
+    )
+    .execute(conn)
+  }
+  fn count_other_reactions(
+    conn: &PgConnection,
+    comment_reaction_form: &CommentReactionForm,
+  ) -> Result<i64, Error> {
+    use crate::schema::comment_reaction::dsl::*;
+    comment_reaction
+      .filter(comment_id.eq(comment_reaction_form.comment_id))
+      .filter(person_id.eq(comment_reaction_form.person_id))
+      .filter(emoji.ne(&comment_reaction_form.emoji))
+      .count()
+      .get_result(conn)
+  }
+}
+
 impl Saveable for CommentSaved {
   type Form = CommentSavedForm;
   fn save(conn: &PgConnection, comment_saved_form: &CommentSavedForm) -> Result<Self, Error> {
@@ -216,7 +353,7 @@ mod tests {
       person::{Person, PersonForm},
       post::*,
     },
-    traits::{Crud, Likeable, Saveable},
+    traits::{Crud, Likeable, Reactable, Saveable},
   };
   use serial_test::serial;
 
@@ -271,6 +408,9 @@ mod tests {
       updated: None,
       ap_id: inserted_comment.ap_id.to_owned(),
       local: true,
+      content_warning: None,
+      language: None,
+      path: vec![inserted_comment.id.0],
     };
 
     let child_comment_form = CommentForm {
@@ -302,6 +442,23 @@ mod tests {
       score: 1,
     };
 
+    // Comment Reaction
+    let comment_reaction_form = CommentReactionForm {
+      comment_id: inserted_comment.id,
+      person_id: inserted_person.id,
+      emoji: "👍".to_string(),
+    };
+
+    let inserted_comment_reaction = CommentReaction::react(&conn, &comment_reaction_form).unwrap();
+
+    let expected_comment_reaction = CommentReaction {
+      id: inserted_comment_reaction.id,
+      comment_id: inserted_comment.id,
+      person_id: inserted_person.id,
+      emoji: "👍".to_string(),
+      published: inserted_comment_reaction.published,
+    };
+
     // Comment Saved
     let comment_saved_form = CommentSavedForm {
       comment_id: inserted_comment.id,
@@ -318,8 +475,16 @@ mod tests {
     };
 
     let read_comment = Comment::read(&conn, inserted_comment.id).unwrap();
+    let post_comments = Comment::list_for_post(&conn, inserted_post.id, 1, 20).unwrap();
+    let creator_comment_count = Comment::count_for_creator(&conn, inserted_person.id).unwrap();
     let updated_comment = Comment::update(&conn, inserted_comment.id, &comment_form).unwrap();
+    Comment::update_content(&conn, inserted_child_comment.id, "An edited child comment").unwrap();
+    let unchanged_revisions =
+      CommentRevision::list_for_comment(&conn, inserted_comment.id).unwrap();
+    let child_revisions =
+      CommentRevision::list_for_comment(&conn, inserted_child_comment.id).unwrap();
     let like_removed = CommentLike::remove(&conn, inserted_person.id, inserted_comment.id).unwrap();
+    let reaction_removed = CommentReaction::unreact(&conn, &comment_reaction_form).unwrap();
     let saved_removed = CommentSaved::unsave(&conn, &comment_saved_form).unwrap();
     let num_deleted = Comment::delete(&conn, inserted_comment.id).unwrap();
     Comment::delete(&conn, inserted_child_comment.id).unwrap();
@@ -330,13 +495,33 @@ mod tests {
     assert_eq!(expected_comment, read_comment);
     assert_eq!(expected_comment, inserted_comment);
     assert_eq!(expected_comment, updated_comment);
+    // The parent comment is listed before its reply
+    assert_eq!(
+      vec![inserted_comment.id, inserted_child_comment.id],
+      post_comments.iter().map(|c| c.id).collect::<Vec<_>>()
+    );
+    assert_eq!(2, creator_comment_count);
+    // Saving a comment without changing its content doesn't create a revision
+    assert!(unchanged_revisions.is_empty());
+    assert_eq!(1, child_revisions.len());
+    assert_eq!("A child comment", child_revisions[0].content);
+    assert_eq!(
+      inserted_child_comment.published,
+      child_revisions[0].published
+    );
     assert_eq!(expected_comment_like, inserted_comment_like);
+    assert_eq!(expected_comment_reaction, inserted_comment_reaction);
     assert_eq!(expected_comment_saved, inserted_comment_saved);
     assert_eq!(
       expected_comment.id,
       inserted_child_comment.parent_id.unwrap()
     );
+    assert_eq!(
+      vec![inserted_comment.id.0, inserted_child_comment.id.0],
+      inserted_child_comment.path
+    );
     assert_eq!(1, like_removed);
+    assert_eq!(1, reaction_removed);
     assert_eq!(1, saved_removed);
     assert_eq!(1, num_deleted);
   }
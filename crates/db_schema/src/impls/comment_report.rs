@@ -1,8 +1,9 @@
 use crate::{
   naive_now,
-  newtypes::{CommentReportId, PersonId},
+  newtypes::{CommentId, CommentReportId, PersonId},
   source::comment_report::{CommentReport, CommentReportForm},
   traits::Reportable,
+  ReportCreator,
 };
 use diesel::{dsl::*, result::Error, *};
 
@@ -60,3 +61,37 @@ impl Reportable for CommentReport {
       .execute(conn)
   }
 }
+
+impl CommentReport {
+  /// resolves all open reports of a comment by the given creator, when a remote instance federates
+  /// that it resolved the report. Reports resolved this way have no resolver.
+  ///
+  /// * `conn` - the postgres connection
+  /// * `for_comment_id` - the id of the reported comment
+  /// * `by_creator` - the user or site who created the report
+  pub fn resolve_apub(
+    conn: &PgConnection,
+    for_comment_id: CommentId,
+    by_creator: ReportCreator,
+  ) -> Result<usize, Error> {
+    use crate::schema::comment_report::dsl::*;
+    let (for_creator_id, for_site_id) = by_creator.columns();
+    // comparisons with null are never true, so only the column which is set can match
+    update(
+      comment_report
+        .filter(comment_id.eq(for_comment_id))
+        .filter(
+          creator_id
+            .eq(for_creator_id)
+            .or(forwarded_by_site_id.eq(for_site_id)),
+        )
+        .filter(resolved.eq(false)),
+    )
+    .set((
+      resolved.eq(true),
+      resolver_id.eq(None::<PersonId>),
+      updated.eq(naive_now()),
+    ))
+    .execute(conn)
+  }
+}
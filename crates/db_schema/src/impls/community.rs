@@ -14,6 +14,7 @@ use crate::{
     CommunitySafe,
   },
   traits::{ApubActor, Bannable, Crud, DeleteableOrRemoveable, Followable, Joinable},
+  KEY_ROTATION_GRACE_PERIOD_DAYS,
 };
 use diesel::{
   dsl::*,
@@ -43,6 +44,8 @@ mod safe_type {
     icon,
     banner,
     hidden,
+    moved_to,
+    also_known_as,
   );
 
   impl ToSafe for Community {
@@ -63,6 +66,8 @@ mod safe_type {
         icon,
         banner,
         hidden,
+        moved_to,
+        also_known_as,
       )
     }
   }
@@ -101,6 +106,16 @@ impl Crud for Community {
 }
 
 impl Community {
+  pub fn read_many(
+    conn: &PgConnection,
+    community_ids: Vec<CommunityId>,
+  ) -> Result<Vec<Self>, Error> {
+    use crate::schema::community::dsl::*;
+    community
+      .filter(id.eq_any(community_ids))
+      .load::<Self>(conn)
+  }
+
   pub fn update_deleted(
     conn: &PgConnection,
     community_id: CommunityId,
@@ -123,6 +138,81 @@ impl Community {
       .get_result::<Self>(conn)
   }
 
+  /// Marks the community as moved to another community, usually on a different instance.
+  pub fn set_moved_to(
+    conn: &PgConnection,
+    community_id: CommunityId,
+    new_moved_to: DbUrl,
+  ) -> Result<Community, Error> {
+    use crate::schema::community::dsl::*;
+    diesel::update(community.find(community_id))
+      .set((moved_to.eq(new_moved_to), updated.eq(naive_now())))
+      .get_result::<Self>(conn)
+  }
+
+  /// Replaces the keypair of a local community. The previous public key is kept by a database
+  /// trigger.
+  pub fn update_keypair(
+    conn: &PgConnection,
+    community_id: CommunityId,
+    new_private_key: String,
+    new_public_key: String,
+  ) -> Result<Community, Error> {
+    use crate::schema::community::dsl::*;
+    diesel::update(community.find(community_id))
+      .set((
+        private_key.eq(new_private_key),
+        public_key.eq(new_public_key),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  /// Stops accepting signatures made with the previous key of the community, eg because it was
+  /// compromised.
+  pub fn revoke_previous_key(
+    conn: &PgConnection,
+    community_id: CommunityId,
+  ) -> Result<Community, Error> {
+    use crate::schema::community::dsl::*;
+    diesel::update(community.find(community_id))
+      .set(previous_public_key.eq(None::<String>))
+      .get_result::<Self>(conn)
+  }
+
+  /// Forgets previous keys which were replaced longer ago than the grace period.
+  pub fn expire_previous_keys(conn: &PgConnection) -> Result<usize, Error> {
+    use crate::schema::community::dsl::*;
+    diesel::update(
+      community
+        .filter(previous_public_key.is_not_null())
+        .filter(key_rotated_at.lt(now - KEY_ROTATION_GRACE_PERIOD_DAYS.days())),
+    )
+    .set(previous_public_key.eq(None::<String>))
+    .execute(conn)
+  }
+
+  pub fn list_local(conn: &PgConnection) -> Result<Vec<Community>, Error> {
+    use crate::schema::community::dsl::*;
+    community
+      .filter(local.eq(true))
+      .order_by(id)
+      .load::<Self>(conn)
+  }
+
+  /// Returns the inboxes and shared inboxes of all communities with one of the given inboxes, if
+  /// they advertise a shared inbox
+  pub fn read_shared_inboxes(
+    conn: &PgConnection,
+    inboxes: Vec<DbUrl>,
+  ) -> Result<Vec<(DbUrl, Option<DbUrl>)>, Error> {
+    use crate::schema::community::dsl::*;
+    community
+      .filter(inbox_url.eq_any(inboxes))
+      .filter(shared_inbox_url.is_not_null())
+      .select((inbox_url, shared_inbox_url))
+      .load::<(DbUrl, Option<DbUrl>)>(conn)
+  }
+
   pub fn distinct_federated_communities(conn: &PgConnection) -> Result<Vec<DbUrl>, Error> {
     use crate::schema::community::dsl::*;
     community.select(actor_id).distinct().load::<DbUrl>(conn)
@@ -289,6 +379,84 @@ impl Followable for CommunityFollower {
   }
 }
 
+impl CommunityFollower {
+  /// Inboxes of all remote communities which the person follows
+  pub fn remote_community_inboxes(
+    conn: &PgConnection,
+    for_person_id: PersonId,
+  ) -> Result<Vec<DbUrl>, Error> {
+    use crate::schema::{community, community_follower::dsl::*};
+    community_follower
+      .inner_join(community::table)
+      .filter(person_id.eq(for_person_id))
+      .filter(community::local.eq(false))
+      .select(community::inbox_url)
+      .load::<DbUrl>(conn)
+  }
+
+  /// Moves the follows of local communities from one person to another, when their account
+  /// migrated to a new instance. Returns the number of follows which were moved.
+  pub fn transfer_local_follows(
+    conn: &PgConnection,
+    from_person_id: PersonId,
+    to_person_id: PersonId,
+  ) -> Result<usize, Error> {
+    use crate::schema::{community, community_follower::dsl::*};
+    let community_ids = community_follower
+      .inner_join(community::table)
+      .filter(person_id.eq(from_person_id))
+      .filter(community::local.eq(true))
+      .select(community_id)
+      .load::<CommunityId>(conn)?;
+    for follow_community_id in &community_ids {
+      let form = CommunityFollowerForm {
+        community_id: *follow_community_id,
+        person_id: to_person_id,
+        pending: false,
+      };
+      CommunityFollower::follow(conn, &form)?;
+    }
+    diesel::delete(
+      community_follower
+        .filter(person_id.eq(from_person_id))
+        .filter(community_id.eq_any(&community_ids)),
+    )
+    .execute(conn)
+  }
+
+  /// Moves the follows of local persons from one community to another, when the community migrated
+  /// to a new instance. Returns the ids of the persons whose follow was moved.
+  pub fn transfer_local_followers(
+    conn: &PgConnection,
+    from_community_id: CommunityId,
+    to_community_id: CommunityId,
+    new_pending: bool,
+  ) -> Result<Vec<PersonId>, Error> {
+    use crate::schema::{community_follower::dsl::*, person};
+    let person_ids = community_follower
+      .inner_join(person::table)
+      .filter(community_id.eq(from_community_id))
+      .filter(person::local.eq(true))
+      .select(person_id)
+      .load::<PersonId>(conn)?;
+    for follower_id in &person_ids {
+      let form = CommunityFollowerForm {
+        community_id: to_community_id,
+        person_id: *follower_id,
+        pending: new_pending,
+      };
+      CommunityFollower::follow(conn, &form)?;
+    }
+    diesel::delete(
+      community_follower
+        .filter(community_id.eq(from_community_id))
+        .filter(person_id.eq_any(&person_ids)),
+    )
+    .execute(conn)?;
+    Ok(person_ids)
+  }
+}
+
 impl ApubActor for Community {
   fn read_from_apub_id(conn: &PgConnection, object_id: &DbUrl) -> Result<Option<Self>, Error> {
     use crate::schema::community::dsl::*;
@@ -328,7 +496,9 @@ mod tests {
     establish_unpooled_connection,
     source::{community::*, person::*},
     traits::{Bannable, Crud, Followable, Joinable},
+    KEY_ROTATION_GRACE_PERIOD_DAYS,
   };
+  use diesel::RunQueryDsl;
   use serial_test::serial;
 
   #[test]
@@ -373,6 +543,10 @@ mod tests {
       inbox_url: inserted_community.inbox_url.to_owned(),
       shared_inbox_url: None,
       hidden: false,
+      moved_to: None,
+      also_known_as: vec![],
+      previous_public_key: None,
+      key_rotated_at: None,
     };
 
     let community_follower_form = CommunityFollowerForm {
@@ -445,4 +619,207 @@ mod tests {
     // assert_eq!(2, loaded_count);
     assert_eq!(1, num_deleted);
   }
+
+  #[test]
+  #[serial]
+  fn test_transfer_local_follows() {
+    let conn = establish_unpooled_connection();
+
+    let old_person = Person::create(
+      &conn,
+      &PersonForm {
+        name: "moving_person".into(),
+        ..PersonForm::default()
+      },
+    )
+    .unwrap();
+    let new_person = Person::create(
+      &conn,
+      &PersonForm {
+        name: "moved_person".into(),
+        ..PersonForm::default()
+      },
+    )
+    .unwrap();
+    let inserted_community = Community::create(
+      &conn,
+      &CommunityForm {
+        name: "move_community".to_string(),
+        title: "nada".to_owned(),
+        public_key: "pubkey".to_string(),
+        ..CommunityForm::default()
+      },
+    )
+    .unwrap();
+
+    let form = CommunityFollowerForm {
+      community_id: inserted_community.id,
+      person_id: old_person.id,
+      pending: false,
+    };
+    CommunityFollower::follow(&conn, &form).unwrap();
+
+    let moved =
+      CommunityFollower::transfer_local_follows(&conn, old_person.id, new_person.id).unwrap();
+    assert_eq!(1, moved);
+    // The old follow is gone, so unfollowing it doesnt delete anything
+    assert_eq!(0, CommunityFollower::unfollow(&conn, &form).unwrap());
+    let new_form = CommunityFollowerForm {
+      person_id: new_person.id,
+      ..form
+    };
+    assert_eq!(1, CommunityFollower::unfollow(&conn, &new_form).unwrap());
+
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, old_person.id).unwrap();
+    Person::delete(&conn, new_person.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_transfer_local_followers() {
+    let conn = establish_unpooled_connection();
+
+    let inserted_person = Person::create(
+      &conn,
+      &PersonForm {
+        name: "community_mover".into(),
+        ..PersonForm::default()
+      },
+    )
+    .unwrap();
+    let old_community = Community::create(
+      &conn,
+      &CommunityForm {
+        name: "old_community".to_string(),
+        title: "nada".to_owned(),
+        public_key: "pubkey".to_string(),
+        ..CommunityForm::default()
+      },
+    )
+    .unwrap();
+    let new_community = Community::create(
+      &conn,
+      &CommunityForm {
+        name: "new_community".to_string(),
+        title: "nada".to_owned(),
+        public_key: "pubkey".to_string(),
+        ..CommunityForm::default()
+      },
+    )
+    .unwrap();
+
+    let form = CommunityFollowerForm {
+      community_id: old_community.id,
+      person_id: inserted_person.id,
+      pending: false,
+    };
+    CommunityFollower::follow(&conn, &form).unwrap();
+
+    let moved =
+      CommunityFollower::transfer_local_followers(&conn, old_community.id, new_community.id, false)
+        .unwrap();
+    assert_eq!(vec![inserted_person.id], moved);
+    let moved_community =
+      Community::set_moved_to(&conn, old_community.id, new_community.actor_id.clone()).unwrap();
+    assert_eq!(
+      Some(new_community.actor_id.clone()),
+      moved_community.moved_to
+    );
+
+    // The old follow is gone, so unfollowing it doesnt delete anything
+    assert_eq!(0, CommunityFollower::unfollow(&conn, &form).unwrap());
+    let new_form = CommunityFollowerForm {
+      community_id: new_community.id,
+      ..form
+    };
+    assert_eq!(1, CommunityFollower::unfollow(&conn, &new_form).unwrap());
+
+    Community::delete(&conn, old_community.id).unwrap();
+    Community::delete(&conn, new_community.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+  }
+
+  #[test]
+  #[serial]
+  fn test_update_keypair() {
+    let conn = establish_unpooled_connection();
+
+    let inserted_community = Community::create(
+      &conn,
+      &CommunityForm {
+        name: "rotated_keys".to_string(),
+        title: "nada".to_owned(),
+        public_key: "old_pubkey".to_string(),
+        ..CommunityForm::default()
+      },
+    )
+    .unwrap();
+    assert_eq!(None, inserted_community.previous_public_key);
+
+    let updated_community = Community::update_keypair(
+      &conn,
+      inserted_community.id,
+      "new_privkey".to_string(),
+      "new_pubkey".to_string(),
+    )
+    .unwrap();
+    assert_eq!("new_pubkey", updated_community.public_key);
+    assert_eq!(
+      Some("old_pubkey".to_string()),
+      updated_community.previous_public_key
+    );
+    assert!(updated_community.key_rotated_at.is_some());
+
+    // Updates which keep the same key don't touch the previous key
+    let form = CommunityForm {
+      name: "rotated_keys".to_string(),
+      title: "new title".to_owned(),
+      public_key: "new_pubkey".to_string(),
+      ..CommunityForm::default()
+    };
+    let updated_community = Community::update(&conn, inserted_community.id, &form).unwrap();
+    assert_eq!(
+      Some("old_pubkey".to_string()),
+      updated_community.previous_public_key
+    );
+
+    // The previous key is kept during the grace period
+    Community::expire_previous_keys(&conn).unwrap();
+    let read_community = Community::read(&conn, inserted_community.id).unwrap();
+    assert_eq!(
+      Some("old_pubkey".to_string()),
+      read_community.previous_public_key
+    );
+
+    // and forgotten afterwards, going by the rotation time which the database set
+    diesel::sql_query(format!(
+      "update community set key_rotated_at = key_rotated_at - interval '{} days' where id = {}",
+      KEY_ROTATION_GRACE_PERIOD_DAYS + 1,
+      inserted_community.id
+    ))
+    .execute(&conn)
+    .unwrap();
+    Community::expire_previous_keys(&conn).unwrap();
+    let read_community = Community::read(&conn, inserted_community.id).unwrap();
+    assert_eq!(None, read_community.previous_public_key);
+
+    // Revoking drops the previous key right away
+    let updated_community = Community::update_keypair(
+      &conn,
+      inserted_community.id,
+      "newer_privkey".to_string(),
+      "newer_pubkey".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+      Some("new_pubkey".to_string()),
+      updated_community.previous_public_key
+    );
+    let revoked_community = Community::revoke_previous_key(&conn, inserted_community.id).unwrap();
+    assert_eq!("newer_pubkey", revoked_community.public_key);
+    assert_eq!(None, revoked_community.previous_public_key);
+
+    Community::delete(&conn, inserted_community.id).unwrap();
+  }
 }
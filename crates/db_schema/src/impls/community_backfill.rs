@@ -0,0 +1,98 @@
+use crate::{naive_now, newtypes::CommunityId, source::community_backfill::*};
+use diesel::{result::Error, *};
+
+impl CommunityBackfill {
+  /// Returns None if the community was already backfilled before, so that it only happens once.
+  pub fn start(conn: &PgConnection, for_community_id: CommunityId) -> Result<Option<Self>, Error> {
+    use crate::schema::community_backfill::dsl::*;
+    insert_into(community_backfill)
+      .values(community_id.eq(for_community_id))
+      .on_conflict_do_nothing()
+      .get_result::<Self>(conn)
+      .optional()
+  }
+
+  pub fn update_progress(
+    conn: &PgConnection,
+    backfill_id: i32,
+    form: &CommunityBackfillProgressForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::community_backfill::dsl::*;
+    diesel::update(community_backfill.find(backfill_id))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn finish(
+    conn: &PgConnection,
+    backfill_id: i32,
+    error_message: Option<String>,
+  ) -> Result<Self, Error> {
+    use crate::schema::community_backfill::dsl::*;
+    diesel::update(community_backfill.find(backfill_id))
+      .set((finished.eq(naive_now()), error.eq(error_message)))
+      .get_result::<Self>(conn)
+  }
+
+  /// Backfills which were interrupted by a restart
+  pub fn list_unfinished(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::community_backfill::dsl::*;
+    community_backfill
+      .filter(finished.is_null())
+      .load::<Self>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    source::{
+      community::{Community, CommunityForm},
+      community_backfill::{CommunityBackfill, CommunityBackfillProgressForm},
+    },
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_backfill_only_once() {
+    let conn = establish_unpooled_connection();
+
+    let community_form = CommunityForm {
+      name: "backfill_community".into(),
+      title: "nada".to_owned(),
+      public_key: "pubkey".to_owned(),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &community_form).unwrap();
+
+    let backfill = CommunityBackfill::start(&conn, inserted_community.id)
+      .unwrap()
+      .unwrap();
+    // A second follow doesnt start another backfill
+    assert_eq!(
+      None,
+      CommunityBackfill::start(&conn, inserted_community.id).unwrap()
+    );
+
+    let form = CommunityBackfillProgressForm {
+      pages_fetched: 1,
+      posts_fetched: 20,
+      ..Default::default()
+    };
+    let updated = CommunityBackfill::update_progress(&conn, backfill.id, &form).unwrap();
+    assert_eq!(20, updated.posts_fetched);
+    assert!(CommunityBackfill::list_unfinished(&conn)
+      .unwrap()
+      .iter()
+      .any(|b| b.id == backfill.id));
+
+    let finished = CommunityBackfill::finish(&conn, backfill.id, None).unwrap();
+    assert!(finished.finished.is_some());
+
+    let num_deleted = Community::delete(&conn, inserted_community.id).unwrap();
+    assert_eq!(1, num_deleted);
+  }
+}
@@ -0,0 +1,128 @@
+use crate::source::federation_list::*;
+use diesel::{dsl::*, result::Error, *};
+
+impl FederationListEntry {
+  /// Adds the domain to the allowlist or blocklist. A domain can only be on one of the lists, so
+  /// this replaces any existing entry for it.
+  pub fn upsert(conn: &PgConnection, form: &FederationListEntryForm) -> Result<Self, Error> {
+    use crate::schema::federation_list::dsl::*;
+    insert_into(federation_list)
+      .values(form)
+      .on_conflict(domain)
+      .do_update()
+      .set((form, published.eq(now)))
+      .get_result::<Self>(conn)
+  }
+
+  pub fn remove(conn: &PgConnection, for_domain: &str, for_blocked: bool) -> Result<usize, Error> {
+    use crate::schema::federation_list::dsl::*;
+    diesel::delete(
+      federation_list
+        .filter(domain.eq(for_domain))
+        .filter(blocked.eq(for_blocked)),
+    )
+    .execute(conn)
+  }
+
+  pub fn list(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::federation_list::dsl::*;
+    federation_list
+      .order_by((blocked, domain))
+      .load::<Self>(conn)
+  }
+
+  /// Domains on the blocklist if `for_blocked` is true, otherwise domains on the allowlist
+  pub fn list_domains(conn: &PgConnection, for_blocked: bool) -> Result<Vec<String>, Error> {
+    use crate::schema::federation_list::dsl::*;
+    federation_list
+      .filter(blocked.eq(for_blocked))
+      .select(domain)
+      .order_by(domain)
+      .load::<String>(conn)
+  }
+}
+
+impl FederationLists {
+  pub fn read(conn: &PgConnection) -> Result<Self, Error> {
+    use crate::schema::site::dsl::*;
+    // Before the site is set up, the column default applies
+    let strict_allowlist = site
+      .order_by(id)
+      .select(federation_strict_allowlist)
+      .first::<bool>(conn)
+      .optional()?
+      .unwrap_or(true);
+    Ok(FederationLists {
+      allowed: FederationListEntry::list_domains(conn, false)?,
+      blocked: FederationListEntry::list_domains(conn, true)?,
+      strict_allowlist,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    source::{
+      federation_list::{FederationListEntry, FederationListEntryForm, FederationLists},
+      person::{Person, PersonForm},
+    },
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_federation_list() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "federation_list_admin".into(),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let form = FederationListEntryForm {
+      domain: "federation-list.tld".to_string(),
+      blocked: false,
+      admin_person_id: Some(inserted_person.id),
+      reason: None,
+    };
+    let allowed = FederationListEntry::upsert(&conn, &form).unwrap();
+    assert_eq!(
+      vec!["federation-list.tld".to_string()],
+      FederationListEntry::list_domains(&conn, false).unwrap()
+    );
+
+    // Blocking the same domain moves it from the allowlist to the blocklist
+    let form = FederationListEntryForm {
+      blocked: true,
+      reason: Some("spam".to_string()),
+      ..form
+    };
+    let blocked = FederationListEntry::upsert(&conn, &form).unwrap();
+    assert_eq!(allowed.id, blocked.id);
+    assert!(blocked.blocked);
+    assert_eq!(Some("spam".to_string()), blocked.reason);
+    assert!(FederationListEntry::list_domains(&conn, false)
+      .unwrap()
+      .is_empty());
+    let lists = FederationLists::read(&conn).unwrap();
+    assert!(lists.allowed.is_empty());
+    assert_eq!(vec!["federation-list.tld".to_string()], lists.blocked);
+
+    // Wrong list, nothing is removed
+    assert_eq!(
+      0,
+      FederationListEntry::remove(&conn, "federation-list.tld", false).unwrap()
+    );
+    assert_eq!(
+      1,
+      FederationListEntry::remove(&conn, "federation-list.tld", true).unwrap()
+    );
+    assert!(FederationListEntry::list(&conn).unwrap().is_empty());
+
+    Person::delete(&conn, inserted_person.id).unwrap();
+  }
+}
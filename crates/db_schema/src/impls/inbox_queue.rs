@@ -0,0 +1,223 @@
+use crate::{naive_now, source::inbox_queue::*};
+use diesel::{
+  dsl::*,
+  result::Error,
+  sql_types::{Double, Nullable},
+  *,
+};
+
+impl InboxQueueEntry {
+  pub fn create(conn: &PgConnection, form: &InboxQueueEntryForm) -> Result<Self, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    insert_into(inbox_queue)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  /// Takes the oldest pending activity off the queue. Rows which are locked by another worker are
+  /// skipped, so that each activity is processed only once. Failed activities are skipped until
+  /// their retry is due.
+  pub fn claim_next(conn: &PgConnection) -> Result<Option<Self>, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    conn.transaction(|| {
+      let next = inbox_queue
+        .filter(started.is_null())
+        .filter(retry_at.is_null().or(retry_at.le(now)))
+        .order_by(id.asc())
+        .for_update()
+        .skip_locked()
+        .first::<Self>(conn)
+        .optional()?;
+      match next {
+        Some(entry) => diesel::update(inbox_queue.find(entry.id))
+          .set(started.eq(naive_now()))
+          .get_result::<Self>(conn)
+          .map(Some),
+        None => Ok(None),
+      }
+    })
+  }
+
+  pub fn mark_finished(
+    conn: &PgConnection,
+    entry_id: i32,
+    error_message: Option<String>,
+  ) -> Result<Self, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    diesel::update(inbox_queue.find(entry_id))
+      .set((finished.eq(naive_now()), error.eq(error_message)))
+      .get_result::<Self>(conn)
+  }
+
+  /// Counts a failed attempt to process the activity. It is queued again to be retried after
+  /// `retry_delay`, unless it failed `max_attempts` times already, then it is given up.
+  pub fn mark_failed(
+    conn: &PgConnection,
+    entry_id: i32,
+    error_message: String,
+    max_attempts: i32,
+    retry_delay: chrono::Duration,
+  ) -> Result<Self, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    conn.transaction(|| {
+      let entry = diesel::update(inbox_queue.find(entry_id))
+        .set((attempts.eq(attempts + 1), error.eq(error_message)))
+        .get_result::<Self>(conn)?;
+      if entry.attempts >= max_attempts {
+        return diesel::update(inbox_queue.find(entry_id))
+          .set(finished.eq(naive_now()))
+          .get_result::<Self>(conn);
+      }
+      diesel::update(inbox_queue.find(entry_id))
+        .set((
+          started.eq(None::<chrono::NaiveDateTime>),
+          retry_at.eq(naive_now() + retry_delay),
+        ))
+        .get_result::<Self>(conn)
+    })
+  }
+
+  /// Activities which were being processed when the server stopped are put back into the queue.
+  pub fn release_unfinished(conn: &PgConnection) -> Result<usize, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    diesel::update(
+      inbox_queue
+        .filter(started.is_not_null())
+        .filter(finished.is_null()),
+    )
+    .set(started.eq(None::<chrono::NaiveDateTime>))
+    .execute(conn)
+  }
+
+  /// Processed activities are only kept for the stats
+  pub fn delete_olds(conn: &PgConnection) -> Result<usize, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    diesel::delete(inbox_queue.filter(finished.lt(now - 1.days()))).execute(conn)
+  }
+
+  pub fn stats(conn: &PgConnection) -> Result<InboxQueueStats, Error> {
+    use crate::schema::inbox_queue::dsl::*;
+    let pending = inbox_queue
+      .filter(started.is_null())
+      .select(count(id))
+      .first::<i64>(conn)?;
+    let processing = inbox_queue
+      .filter(started.is_not_null())
+      .filter(finished.is_null())
+      .select(count(id))
+      .first::<i64>(conn)?;
+    let processed_last_hour = inbox_queue
+      .filter(finished.gt(now - 1.hours()))
+      .select(count(id))
+      .first::<i64>(conn)?;
+    let failed_last_hour = inbox_queue
+      .filter(finished.gt(now - 1.hours()))
+      .filter(error.is_not_null())
+      .select(count(id))
+      .first::<i64>(conn)?;
+    let average_latency_seconds = inbox_queue
+      .filter(finished.gt(now - 1.hours()))
+      .select(sql::<Nullable<Double>>(
+        "avg(extract(epoch from (finished - received)))::float8",
+      ))
+      .first::<Option<f64>>(conn)?;
+
+    Ok(InboxQueueStats {
+      pending,
+      processing,
+      processed_last_hour,
+      failed_last_hour,
+      average_latency_seconds,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    naive_now,
+    schema::inbox_queue::{dsl, dsl::inbox_queue},
+    source::inbox_queue::{InboxQueueEntry, InboxQueueEntryForm},
+  };
+  use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+  use serde_json::json;
+  use serial_test::serial;
+  use url::Url;
+
+  #[test]
+  #[serial]
+  fn test_claim_and_finish() {
+    let conn = establish_unpooled_connection();
+
+    let form = InboxQueueEntryForm {
+      ap_id: Url::parse("https://enterprise.lemmy.ml/activities/like/1")
+        .unwrap()
+        .into(),
+      actor: Url::parse("https://enterprise.lemmy.ml/u/riker")
+        .unwrap()
+        .into(),
+      inbox: "Person".into(),
+      data: json!({"type": "Like"}),
+    };
+    let inserted = InboxQueueEntry::create(&conn, &form).unwrap();
+    assert_eq!(None, inserted.started);
+
+    let claimed = InboxQueueEntry::claim_next(&conn).unwrap().unwrap();
+    assert_eq!(inserted.id, claimed.id);
+    assert!(claimed.started.is_some());
+    // Nothing else is pending
+    assert_eq!(None, InboxQueueEntry::claim_next(&conn).unwrap());
+
+    let stats = InboxQueueEntry::stats(&conn).unwrap();
+    assert_eq!(0, stats.pending);
+    assert_eq!(1, stats.processing);
+
+    // A failed activity is queued again, but not claimed before the retry is due
+    let failed = InboxQueueEntry::mark_failed(
+      &conn,
+      claimed.id,
+      "timeout".into(),
+      2,
+      chrono::Duration::hours(1),
+    )
+    .unwrap();
+    assert_eq!(1, failed.attempts);
+    assert_eq!(None, failed.started);
+    assert_eq!(None, failed.finished);
+    assert_eq!(None, InboxQueueEntry::claim_next(&conn).unwrap());
+    diesel::update(inbox_queue.find(inserted.id))
+      .set(dsl::retry_at.eq(naive_now() - chrono::Duration::minutes(1)))
+      .execute(&conn)
+      .unwrap();
+    let retried = InboxQueueEntry::claim_next(&conn).unwrap().unwrap();
+    assert_eq!(inserted.id, retried.id);
+
+    // After the last attempt it is given up
+    let given_up = InboxQueueEntry::mark_failed(
+      &conn,
+      retried.id,
+      "timeout".into(),
+      2,
+      chrono::Duration::hours(1),
+    )
+    .unwrap();
+    assert_eq!(2, given_up.attempts);
+    assert!(given_up.finished.is_some());
+    assert_eq!(None, InboxQueueEntry::claim_next(&conn).unwrap());
+
+    let finished =
+      InboxQueueEntry::mark_finished(&conn, claimed.id, Some("failed".into())).unwrap();
+    assert!(finished.finished.is_some());
+
+    let stats = InboxQueueEntry::stats(&conn).unwrap();
+    assert_eq!(0, stats.processing);
+    assert_eq!(1, stats.failed_last_hour);
+    assert!(stats.average_latency_seconds.is_some());
+
+    let num_deleted = diesel::delete(inbox_queue.find(inserted.id))
+      .execute(&conn)
+      .unwrap();
+    assert_eq!(1, num_deleted);
+  }
+}
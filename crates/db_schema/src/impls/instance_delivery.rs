@@ -0,0 +1,150 @@
+use crate::{naive_now, source::instance_delivery::InstanceDelivery};
+use chrono::{Duration, NaiveDateTime};
+use diesel::{dsl::*, result::Error, *};
+
+impl InstanceDelivery {
+  /// Counts a successful delivery, which also lifts a suspension of the instance
+  pub fn record_success(conn: &PgConnection, for_domain: &str) -> Result<Self, Error> {
+    use crate::schema::instance_delivery::dsl::*;
+    insert_into(instance_delivery)
+      .values((
+        domain.eq(for_domain),
+        success_count.eq(1),
+        last_success.eq(naive_now()),
+      ))
+      .on_conflict(domain)
+      .do_update()
+      .set((
+        success_count.eq(success_count + 1),
+        consecutive_failures.eq(0),
+        last_success.eq(naive_now()),
+        suspended.eq(false),
+        next_probe.eq(None::<NaiveDateTime>),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  /// Counts a failed delivery. Once the instance failed `failure_threshold` times in a row, and
+  /// hasn't been reached for at least `min_downtime_minutes`, it is suspended and only probed again
+  /// after `probe_interval_minutes`. The downtime keeps a short outage, during which many queued
+  /// deliveries fail at once, from suspending an instance.
+  pub fn record_failure(
+    conn: &PgConnection,
+    for_domain: &str,
+    failure_threshold: i32,
+    min_downtime_minutes: i64,
+    probe_interval_minutes: i64,
+  ) -> Result<Self, Error> {
+    use crate::schema::instance_delivery::dsl::*;
+    conn.transaction(|| {
+      let delivery = insert_into(instance_delivery)
+        .values((
+          domain.eq(for_domain),
+          failure_count.eq(1),
+          consecutive_failures.eq(1),
+          last_failure.eq(naive_now()),
+        ))
+        .on_conflict(domain)
+        .do_update()
+        .set((
+          failure_count.eq(failure_count + 1),
+          consecutive_failures.eq(consecutive_failures + 1),
+          last_failure.eq(naive_now()),
+        ))
+        .get_result::<Self>(conn)?;
+
+      // Instances which were never reached count as down since they were first seen
+      let reachable_at = delivery.last_success.unwrap_or(delivery.published);
+      if delivery.consecutive_failures < failure_threshold
+        || reachable_at > naive_now() - Duration::minutes(min_downtime_minutes)
+      {
+        return Ok(delivery);
+      }
+      // Also pushes back the next probe if the instance was already suspended
+      diesel::update(instance_delivery.find(delivery.id))
+        .set((
+          suspended.eq(true),
+          next_probe.eq(naive_now() + Duration::minutes(probe_interval_minutes)),
+        ))
+        .get_result::<Self>(conn)
+    })
+  }
+
+  /// Returns true if the instance is suspended, and not due for another probe yet
+  pub fn is_suspended(conn: &PgConnection, for_domain: &str) -> Result<bool, Error> {
+    use crate::schema::instance_delivery::dsl::*;
+    select(exists(
+      instance_delivery
+        .filter(domain.eq(for_domain))
+        .filter(suspended.eq(true))
+        .filter(next_probe.gt(now)),
+    ))
+    .get_result(conn)
+  }
+
+  /// The domains of all instances which shouldn't receive any activities right now
+  pub fn suspended_domains(conn: &PgConnection) -> Result<Vec<String>, Error> {
+    use crate::schema::instance_delivery::dsl::*;
+    instance_delivery
+      .filter(suspended.eq(true))
+      .filter(next_probe.gt(now))
+      .select(domain)
+      .load::<String>(conn)
+  }
+
+  pub fn list(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::instance_delivery::dsl::*;
+    instance_delivery.order_by(domain).load::<Self>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    schema::instance_delivery::dsl,
+    source::instance_delivery::InstanceDelivery,
+  };
+  use diesel::*;
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_suspend_and_recover() {
+    let conn = establish_unpooled_connection();
+    let domain = "dead.example.com";
+
+    let first = InstanceDelivery::record_failure(&conn, domain, 2, 0, 60).unwrap();
+    assert_eq!(1, first.consecutive_failures);
+    assert!(!first.suspended);
+    assert!(!InstanceDelivery::is_suspended(&conn, domain).unwrap());
+
+    let second = InstanceDelivery::record_failure(&conn, domain, 2, 0, 60).unwrap();
+    assert_eq!(2, second.failure_count);
+    assert!(second.suspended);
+    assert!(InstanceDelivery::is_suspended(&conn, domain).unwrap());
+    assert!(InstanceDelivery::suspended_domains(&conn)
+      .unwrap()
+      .contains(&domain.to_string()));
+
+    let recovered = InstanceDelivery::record_success(&conn, domain).unwrap();
+    assert_eq!(1, recovered.success_count);
+    assert_eq!(0, recovered.consecutive_failures);
+    assert!(recovered.last_success.is_some());
+    assert!(!InstanceDelivery::is_suspended(&conn, domain).unwrap());
+
+    // A burst of failures right after a successful delivery doesn't suspend the instance
+    let burst = (0..3)
+      .map(|_| InstanceDelivery::record_failure(&conn, domain, 2, 60, 60).unwrap())
+      .last()
+      .unwrap();
+    assert_eq!(3, burst.consecutive_failures);
+    assert!(!burst.suspended);
+    assert!(!InstanceDelivery::is_suspended(&conn, domain).unwrap());
+
+    let num_deleted = diesel::delete(dsl::instance_delivery.find(recovered.id))
+      .execute(&conn)
+      .unwrap();
+    assert_eq!(1, num_deleted);
+  }
+}
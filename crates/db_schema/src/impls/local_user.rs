@@ -33,6 +33,8 @@ mod safe_settings_type {
     show_new_post_notifs,
     email_verified,
     accepted_application,
+    default_language,
+    discussion_languages,
   );
 
   impl ToSafeSettings for LocalUser {
@@ -58,6 +60,8 @@ mod safe_settings_type {
         show_new_post_notifs,
         email_verified,
         accepted_application,
+        default_language,
+        discussion_languages,
       )
     }
   }
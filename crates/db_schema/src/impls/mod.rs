@@ -1,15 +1,21 @@
 pub mod activity;
+pub mod activity_queue;
 pub mod comment;
 pub mod comment_report;
 pub mod community;
+pub mod community_backfill;
 pub mod community_block;
 pub mod email_verification;
+pub mod federation_list;
+pub mod inbox_queue;
+pub mod instance_delivery;
 pub mod local_user;
 pub mod moderator;
 pub mod password_reset_request;
 pub mod person;
 pub mod person_block;
 pub mod person_mention;
+pub mod poll;
 pub mod post;
 pub mod post_report;
 pub mod private_message;
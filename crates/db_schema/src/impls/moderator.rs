@@ -192,6 +192,34 @@ impl Crud for ModHideCommunity {
   }
 }
 
+impl Crud for ModFederationList {
+  type Form = ModFederationListForm;
+  type IdType = i32;
+
+  fn read(conn: &PgConnection, from_id: i32) -> Result<Self, Error> {
+    use crate::schema::mod_federation_list::dsl::*;
+    mod_federation_list.find(from_id).first::<Self>(conn)
+  }
+
+  fn create(conn: &PgConnection, form: &ModFederationListForm) -> Result<Self, Error> {
+    use crate::schema::mod_federation_list::dsl::*;
+    insert_into(mod_federation_list)
+      .values(form)
+      .get_result::<Self>(conn)
+  }
+
+  fn update(
+    conn: &PgConnection,
+    from_id: i32,
+    form: &ModFederationListForm,
+  ) -> Result<Self, Error> {
+    use crate::schema::mod_federation_list::dsl::*;
+    diesel::update(mod_federation_list.find(from_id))
+      .set(form)
+      .get_result::<Self>(conn)
+  }
+}
+
 impl Crud for ModAddCommunity {
   type Form = ModAddCommunityForm;
   type IdType = i32;
@@ -3,12 +3,14 @@ use crate::{
   naive_now,
   newtypes::{DbUrl, PersonId},
   schema::person::dsl::*,
-  source::person::{Person, PersonForm, PersonSafe},
+  source::person::{Person, PersonForm, PersonOutboxItem, PersonSafe},
   traits::{ApubActor, Crud},
 };
 use diesel::{
   dsl::*,
   result::Error,
+  sql_query,
+  sql_types::{BigInt, Integer},
   ExpressionMethods,
   PgConnection,
   QueryDsl,
@@ -38,6 +40,8 @@ mod safe_type {
     admin,
     bot_account,
     ban_expires,
+    moved_to,
+    also_known_as,
   );
 
   impl ToSafe for Person {
@@ -62,6 +66,8 @@ mod safe_type {
         admin,
         bot_account,
         ban_expires,
+        moved_to,
+        also_known_as,
       )
     }
   }
@@ -89,6 +95,8 @@ mod safe_type_alias_1 {
     admin,
     bot_account,
     ban_expires,
+    moved_to,
+    also_known_as,
   );
 
   impl ToSafe for PersonAlias1 {
@@ -113,6 +121,8 @@ mod safe_type_alias_1 {
         admin,
         bot_account,
         ban_expires,
+        moved_to,
+        also_known_as,
       )
     }
   }
@@ -140,6 +150,8 @@ mod safe_type_alias_2 {
     admin,
     bot_account,
     ban_expires,
+    moved_to,
+    also_known_as,
   );
 
   impl ToSafe for PersonAlias2 {
@@ -164,6 +176,8 @@ mod safe_type_alias_2 {
         admin,
         bot_account,
         ban_expires,
+        moved_to,
+        also_known_as,
       )
     }
   }
@@ -261,11 +275,77 @@ impl Person {
     is_banned(self.banned, self.ban_expires)
   }
 
+  /// Returns the inboxes and shared inboxes of all persons with one of the given inboxes, if they
+  /// advertise a shared inbox
+  pub fn read_shared_inboxes(
+    conn: &PgConnection,
+    inboxes: Vec<DbUrl>,
+  ) -> Result<Vec<(DbUrl, Option<DbUrl>)>, Error> {
+    person
+      .filter(inbox_url.eq_any(inboxes))
+      .filter(shared_inbox_url.is_not_null())
+      .select((inbox_url, shared_inbox_url))
+      .load::<(DbUrl, Option<DbUrl>)>(conn)
+  }
+
+  /// Marks the account as moved to another account, usually on a different instance.
+  pub fn set_moved_to(
+    conn: &PgConnection,
+    person_id: PersonId,
+    new_moved_to: DbUrl,
+  ) -> Result<Self, Error> {
+    diesel::update(person.find(person_id))
+      .set(moved_to.eq(new_moved_to))
+      .get_result::<Self>(conn)
+  }
+
   pub fn leave_admin(conn: &PgConnection, person_id: PersonId) -> Result<Self, Error> {
     diesel::update(person.find(person_id))
       .set(admin.eq(false))
       .get_result::<Self>(conn)
   }
+
+  /// Counts the items listed by `list_outbox_items`
+  pub fn count_outbox_items(conn: &PgConnection, person_id: PersonId) -> Result<i64, Error> {
+    use crate::schema::{comment, post};
+    let posts = post::table
+      .filter(post::creator_id.eq(person_id))
+      .filter(post::removed.eq(false))
+      .filter(post::scheduled_publish_time.is_null())
+      .select(count(post::id))
+      .first::<i64>(conn)?;
+    let comments = comment::table
+      .filter(comment::creator_id.eq(person_id))
+      .filter(comment::removed.eq(false))
+      .select(count(comment::id))
+      .first::<i64>(conn)?;
+    Ok(posts + comments)
+  }
+
+  /// Lists the published posts and comments of the person, newest first. Deleted ones are
+  /// included, so that other instances learn about the deletion, but not the ones removed by
+  /// mods. Posts and comments are in separate tables, so they are merged with a union, which
+  /// Diesel can't express.
+  pub fn list_outbox_items(
+    conn: &PgConnection,
+    person_id: PersonId,
+    limit: i64,
+    offset: i64,
+  ) -> Result<Vec<PersonOutboxItem>, Error> {
+    sql_query(
+      "select id as post_id, null::int as comment_id, published from post \
+        where creator_id = $1 and not removed and scheduled_publish_time is null \
+      union all \
+      select null::int as post_id, id as comment_id, published from comment \
+        where creator_id = $1 and not removed \
+      order by published desc, post_id, comment_id \
+      limit $2 offset $3",
+    )
+    .bind::<Integer, _>(person_id)
+    .bind::<BigInt, _>(limit)
+    .bind::<BigInt, _>(offset)
+    .load::<PersonOutboxItem>(conn)
+  }
 }
 
 impl PersonSafe {
@@ -354,6 +434,8 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      moved_to: None,
+      also_known_as: vec![],
     };
 
     let read_person = Person::read(&conn, inserted_person.id).unwrap();
@@ -0,0 +1,206 @@
+use crate::{
+  naive_now,
+  newtypes::{PersonId, PollOptionId, PostId},
+  source::poll::*,
+};
+use diesel::{result::Error, *};
+
+impl Poll {
+  /// Creates the poll of a post, or updates it if the post has one already.
+  pub fn upsert(conn: &PgConnection, poll_form: &PollForm) -> Result<Self, Error> {
+    use crate::schema::poll::dsl::*;
+    insert_into(poll)
+      .values(poll_form)
+      .on_conflict(post_id)
+      .do_update()
+      .set(poll_form)
+      .get_result::<Self>(conn)
+  }
+
+  pub fn read_for_post(conn: &PgConnection, for_post_id: PostId) -> Result<Option<Self>, Error> {
+    use crate::schema::poll::dsl::*;
+    poll
+      .filter(post_id.eq(for_post_id))
+      .first::<Self>(conn)
+      .optional()
+  }
+
+  /// Remembers that the results of a local poll changed and need to be sent to other instances.
+  pub fn mark_results_pending(conn: &PgConnection, poll_id: i32) -> Result<usize, Error> {
+    use crate::schema::poll::dsl::*;
+    diesel::update(poll.find(poll_id))
+      .set(results_pending.eq(true))
+      .execute(conn)
+  }
+
+  /// Returns the polls with pending results and resets their flag, so that votes which are cast
+  /// meanwhile mark them again.
+  pub fn take_pending_results(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::poll::dsl::*;
+    diesel::update(poll.filter(results_pending.eq(true)))
+      .set(results_pending.eq(false))
+      .get_results::<Self>(conn)
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.end_time.map(|t| t < naive_now()).unwrap_or(false)
+  }
+}
+
+impl PollOption {
+  pub fn upsert(conn: &PgConnection, poll_option_form: &PollOptionForm) -> Result<Self, Error> {
+    use crate::schema::poll_option::dsl::*;
+    insert_into(poll_option)
+      .values(poll_option_form)
+      .on_conflict((poll_id, name))
+      .do_update()
+      .set(poll_option_form)
+      .get_result::<Self>(conn)
+  }
+
+  /// Options in the order in which they were created
+  pub fn list_for_poll(conn: &PgConnection, for_poll_id: i32) -> Result<Vec<Self>, Error> {
+    use crate::schema::poll_option::dsl::*;
+    poll_option
+      .filter(poll_id.eq(for_poll_id))
+      .order_by(id)
+      .load::<Self>(conn)
+  }
+}
+
+impl PollVote {
+  /// Fails if the person already voted for this option.
+  pub fn vote(conn: &PgConnection, poll_vote_form: &PollVoteForm) -> Result<Self, Error> {
+    use crate::schema::poll_vote::dsl::*;
+    insert_into(poll_vote)
+      .values(poll_vote_form)
+      .get_result::<Self>(conn)
+  }
+
+  /// The options of a poll which the person voted for
+  pub fn list_for_person(
+    conn: &PgConnection,
+    for_poll_id: i32,
+    for_person_id: PersonId,
+  ) -> Result<Vec<PollOptionId>, Error> {
+    use crate::schema::{poll_option, poll_vote};
+    poll_vote::table
+      .inner_join(poll_option::table)
+      .filter(poll_option::poll_id.eq(for_poll_id))
+      .filter(poll_vote::person_id.eq(for_person_id))
+      .select(poll_vote::poll_option_id)
+      .load::<PollOptionId>(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    source::{
+      community::{Community, CommunityForm},
+      person::{Person, PersonForm},
+      poll::*,
+      post::{Post, PostForm},
+    },
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_poll() {
+    let conn = establish_unpooled_connection();
+
+    let inserted_person = Person::create(
+      &conn,
+      &PersonForm {
+        name: "poll_voter".into(),
+        ..PersonForm::default()
+      },
+    )
+    .unwrap();
+    let inserted_community = Community::create(
+      &conn,
+      &CommunityForm {
+        name: "poll_community".to_string(),
+        title: "nada".to_owned(),
+        ..CommunityForm::default()
+      },
+    )
+    .unwrap();
+    let inserted_post = Post::create(
+      &conn,
+      &PostForm {
+        name: "A poll".into(),
+        creator_id: inserted_person.id,
+        community_id: inserted_community.id,
+        ..PostForm::default()
+      },
+    )
+    .unwrap();
+
+    let poll_form = PollForm {
+      post_id: inserted_post.id,
+      multiple_choice: Some(true),
+      ..PollForm::default()
+    };
+    let inserted_poll = Poll::upsert(&conn, &poll_form).unwrap();
+    let yes = PollOption::upsert(
+      &conn,
+      &PollOptionForm {
+        poll_id: inserted_poll.id,
+        name: "yes".into(),
+        vote_count: None,
+      },
+    )
+    .unwrap();
+    let no = PollOption::upsert(
+      &conn,
+      &PollOptionForm {
+        poll_id: inserted_poll.id,
+        name: "no".into(),
+        vote_count: None,
+      },
+    )
+    .unwrap();
+
+    let vote_form = PollVoteForm {
+      poll_option_id: yes.id,
+      person_id: inserted_person.id,
+    };
+    PollVote::vote(&conn, &vote_form).unwrap();
+    // Voting twice for the same option is not possible
+    assert!(PollVote::vote(&conn, &vote_form).is_err());
+
+    let read_poll = Poll::read_for_post(&conn, inserted_post.id).unwrap();
+    let options = PollOption::list_for_poll(&conn, inserted_poll.id).unwrap();
+    let my_votes = PollVote::list_for_person(&conn, inserted_poll.id, inserted_person.id).unwrap();
+
+    // Votes only get sent once for each batch
+    Poll::mark_results_pending(&conn, inserted_poll.id).unwrap();
+    let pending = Poll::take_pending_results(&conn).unwrap();
+    let pending_again = Poll::take_pending_results(&conn).unwrap();
+
+    Post::delete(&conn, inserted_post.id).unwrap();
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+
+    assert_eq!(Some(inserted_poll.clone()), read_poll);
+    assert!(inserted_poll.multiple_choice);
+    assert!(!inserted_poll.is_closed());
+    assert_eq!(
+      vec![(yes.id, 1), (no.id, 0)],
+      options
+        .iter()
+        .map(|o| (o.id, o.vote_count))
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(vec![yes.id], my_votes);
+    assert_eq!(
+      vec![inserted_poll.id],
+      pending.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+    assert!(pending_again.is_empty());
+  }
+}
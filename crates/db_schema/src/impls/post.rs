@@ -6,12 +6,15 @@ use crate::{
     PostForm,
     PostLike,
     PostLikeForm,
+    PostReaction,
+    PostReactionForm,
     PostRead,
     PostReadForm,
+    PostRevision,
     PostSaved,
     PostSavedForm,
   },
-  traits::{Crud, DeleteableOrRemoveable, Likeable, Readable, Saveable},
+  traits::{Crud, DeleteableOrRemoveable, Likeable, Reactable, Readable, Saveable},
 };
 use diesel::{dsl::*, result::Error, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
 use url::Url;
@@ -43,21 +46,45 @@ impl Crud for Post {
 }
 
 impl Post {
+  /// Lists the posts of a community, one page at a time. Pages start at 1.
   pub fn list_for_community(
     conn: &PgConnection,
     the_community_id: CommunityId,
+    page: i64,
+    page_size: i64,
   ) -> Result<Vec<Self>, Error> {
     use crate::schema::post::dsl::*;
+    // Huge page numbers from remote requests give an empty page instead of overflowing
+    let offset = (page.max(1) - 1).saturating_mul(page_size);
     post
       .filter(community_id.eq(the_community_id))
       .filter(deleted.eq(false))
       .filter(removed.eq(false))
+      .filter(scheduled_publish_time.is_null())
       .then_order_by(published.desc())
       .then_order_by(stickied.desc())
-      .limit(20)
+      .limit(page_size)
+      .offset(offset)
       .load::<Self>(conn)
   }
 
+  pub fn read_many(conn: &PgConnection, post_ids: Vec<PostId>) -> Result<Vec<Self>, Error> {
+    use crate::schema::post::dsl::*;
+    post.filter(id.eq_any(post_ids)).load::<Self>(conn)
+  }
+
+  /// Counts the posts of a person which are visible to everyone
+  pub fn count_for_creator(conn: &PgConnection, for_creator_id: PersonId) -> Result<i64, Error> {
+    use crate::schema::post::dsl::*;
+    post
+      .filter(creator_id.eq(for_creator_id))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .filter(scheduled_publish_time.is_null())
+      .select(count(id))
+      .first::<i64>(conn)
+  }
+
   pub fn update_ap_id(conn: &PgConnection, post_id: PostId, apub_id: DbUrl) -> Result<Self, Error> {
     use crate::schema::post::dsl::*;
 
@@ -66,15 +93,48 @@ impl Post {
       .get_result::<Self>(conn)
   }
 
+  /// Scheduled posts whose publish time has passed, but which were not published yet.
+  pub fn list_scheduled_due(conn: &PgConnection) -> Result<Vec<Self>, Error> {
+    use crate::schema::post::dsl::*;
+    post
+      .filter(scheduled_publish_time.le(naive_now()))
+      .filter(deleted.eq(false))
+      .filter(removed.eq(false))
+      .order_by(scheduled_publish_time)
+      .load::<Self>(conn)
+  }
+
+  /// Makes a scheduled post visible, with the actual time of publishing.
+  pub fn mark_published(
+    conn: &PgConnection,
+    post_id: PostId,
+    new_published: chrono::NaiveDateTime,
+  ) -> Result<Self, Error> {
+    use crate::schema::post::dsl::*;
+    diesel::update(post.find(post_id))
+      .set((
+        scheduled_publish_time.eq(None::<chrono::NaiveDateTime>),
+        published.eq(new_published),
+      ))
+      .get_result::<Self>(conn)
+  }
+
   pub fn permadelete_for_creator(
     conn: &PgConnection,
     for_creator_id: PersonId,
   ) -> Result<Vec<Self>, Error> {
-    use crate::schema::post::dsl::*;
+    use crate::schema::{post::dsl::*, post_revision::dsl as revision};
 
     let perma_deleted = "*Permananently Deleted*";
     let perma_deleted_url = "https://deleted.com";
 
+    // Earlier versions of the posts would otherwise remain readable
+    diesel::delete(
+      revision::post_revision
+        .filter(revision::post_id.eq_any(post.filter(creator_id.eq(for_creator_id)).select(id))),
+    )
+    .execute(conn)?;
+
     diesel::update(post.filter(creator_id.eq(for_creator_id)))
       .set((
         name.eq(perma_deleted),
@@ -176,6 +236,18 @@ impl Post {
   }
 }
 
+impl PostRevision {
+  /// Lists the previous versions of a post, newest first.
+  pub fn list_for_post(conn: &PgConnection, for_post_id: PostId) -> Result<Vec<Self>, Error> {
+    use crate::schema::post_revision::dsl::*;
+    post_revision
+      .filter(post_id.eq(for_post_id))
+      .order_by(published.desc())
+      .then_order_by(id.desc())
+      .load::<Self>(conn)
+  }
+}
+
 impl Likeable for PostLike {
   type Form = PostLikeForm;
   type IdType = PostId;
@@ -199,6 +271,41 @@ impl Likeable for PostLike {
   }
 }
 
+impl Reactable for PostReaction {
+  type Form = PostReactionForm;
+  fn react(conn: &PgConnection, post_reaction_form: &PostReactionForm) -> Result<Self, Error> {
+    use crate::schema::post_reaction::dsl::*;
+    insert_into(post_reaction)
+      .values(post_reaction_form)
+      .on_conflict((post_id, person_id, emoji))
+      .do_update()
+      .set(post_reaction_form)
+      .get_result::<Self>(conn)
+  }
+  fn unreact(conn: &PgConnection, post_reaction_form: &PostReactionForm) -> Result<usize, Error> {
+    use crate::schema::post_reaction::dsl::*;
+    diesel::delete(
+      post_reaction
+        .filter(post_id.eq(post_reaction_form.post_id))
+        .filter(person_id.eq(post_reaction_form.person_id))
+        .filter(emoji.eq(&post_reaction_form.emoji)),
+    )
+    .execute(conn)
+  }
+  fn count_other_reactions(
+    conn: &PgConnection,
+    post_reaction_form: &PostReactionForm,
+  ) -> Result<i64, Error> {
+    use crate::schema::post_reaction::dsl::*;
+    post_reaction
+      .filter(post_id.eq(post_reaction_form.post_id))
+      .filter(person_id.eq(post_reaction_form.person_id))
+      .filter(emoji.ne(&post_reaction_form.emoji))
+      .count()
+      .get_result(conn)
+  }
+}
+
 impl Saveable for PostSaved {
   type Form = PostSavedForm;
   fn save(conn: &PgConnection, post_saved_form: &PostSavedForm) -> Result<Self, Error> {
@@ -262,12 +369,13 @@ impl DeleteableOrRemoveable for Post {
 mod tests {
   use crate::{
     establish_unpooled_connection,
+    naive_now,
     source::{
       community::{Community, CommunityForm},
       person::*,
       post::*,
     },
-    traits::{Crud, Likeable, Readable, Saveable},
+    traits::{Crud, Likeable, Reactable, Readable, Saveable},
   };
   use serial_test::serial;
 
@@ -300,6 +408,17 @@ mod tests {
 
     let inserted_post = Post::create(&conn, &new_post).unwrap();
 
+    // A scheduled post, which is due already but not yet published
+    let scheduled_post_form = PostForm {
+      name: "A scheduled post".into(),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      scheduled_publish_time: Some(Some(naive_now() - chrono::Duration::minutes(1))),
+      ..PostForm::default()
+    };
+
+    let inserted_scheduled_post = Post::create(&conn, &scheduled_post_form).unwrap();
+
     let expected_post = Post {
       id: inserted_post.id,
       name: "A test post".into(),
@@ -320,6 +439,9 @@ mod tests {
       thumbnail_url: None,
       ap_id: inserted_post.ap_id.to_owned(),
       local: true,
+      content_warning: None,
+      language: None,
+      scheduled_publish_time: None,
     };
 
     // Post Like
@@ -339,6 +461,37 @@ mod tests {
       score: 1,
     };
 
+    // Post Reaction
+    let post_reaction_form = PostReactionForm {
+      post_id: inserted_post.id,
+      person_id: inserted_person.id,
+      emoji: "🎉".to_string(),
+    };
+
+    let inserted_post_reaction = PostReaction::react(&conn, &post_reaction_form).unwrap();
+    // Reacting twice with the same emoji keeps a single reaction
+    let inserted_post_reaction_again = PostReaction::react(&conn, &post_reaction_form).unwrap();
+    assert_eq!(
+      0,
+      PostReaction::count_other_reactions(&conn, &post_reaction_form).unwrap()
+    );
+    let other_reaction_form = PostReactionForm {
+      emoji: "👍".to_string(),
+      ..post_reaction_form.clone()
+    };
+    assert_eq!(
+      1,
+      PostReaction::count_other_reactions(&conn, &other_reaction_form).unwrap()
+    );
+
+    let expected_post_reaction = PostReaction {
+      id: inserted_post_reaction.id,
+      post_id: inserted_post.id,
+      person_id: inserted_person.id,
+      emoji: "🎉".to_string(),
+      published: inserted_post_reaction.published,
+    };
+
     // Post Save
     let post_saved_form = PostSavedForm {
       post_id: inserted_post.id,
@@ -370,21 +523,72 @@ mod tests {
     };
 
     let read_post = Post::read(&conn, inserted_post.id).unwrap();
+    let outbox_items = Person::list_outbox_items(&conn, inserted_person.id, 20, 0).unwrap();
+    let creator_post_count = Post::count_for_creator(&conn, inserted_person.id).unwrap();
+    let due_posts = Post::list_scheduled_due(&conn).unwrap();
+    let published_post =
+      Post::mark_published(&conn, inserted_scheduled_post.id, naive_now()).unwrap();
+    let due_posts_after_publishing = Post::list_scheduled_due(&conn).unwrap();
     let updated_post = Post::update(&conn, inserted_post.id, &new_post).unwrap();
+    let unchanged_revisions = PostRevision::list_for_post(&conn, inserted_post.id).unwrap();
+    let edited_post_form = PostForm {
+      name: "An edited test post".into(),
+      body: Some(Some("An edited body".into())),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      updated: Some(naive_now()),
+      ..PostForm::default()
+    };
+    let edited_post = Post::update(&conn, inserted_post.id, &edited_post_form).unwrap();
+    let revisions = PostRevision::list_for_post(&conn, inserted_post.id).unwrap();
     let like_removed = PostLike::remove(&conn, inserted_person.id, inserted_post.id).unwrap();
+    let reaction_removed = PostReaction::unreact(&conn, &post_reaction_form).unwrap();
     let saved_removed = PostSaved::unsave(&conn, &post_saved_form).unwrap();
     let read_removed = PostRead::mark_as_unread(&conn, &post_read_form).unwrap();
     let num_deleted = Post::delete(&conn, inserted_post.id).unwrap();
+    Post::delete(&conn, inserted_scheduled_post.id).unwrap();
     Community::delete(&conn, inserted_community.id).unwrap();
     Person::delete(&conn, inserted_person.id).unwrap();
 
     assert_eq!(expected_post, read_post);
     assert_eq!(expected_post, inserted_post);
     assert_eq!(expected_post, updated_post);
+    // Saving a post without changing it doesn't create a revision, an edit keeps the old version
+    assert!(unchanged_revisions.is_empty());
+    assert_eq!(
+      vec![PostRevision {
+        id: revisions[0].id,
+        post_id: inserted_post.id,
+        name: "A test post".into(),
+        url: None,
+        body: None,
+        published: inserted_post.published,
+      }],
+      revisions
+    );
+    assert_eq!(Some("An edited body".to_string()), edited_post.body);
+    // The scheduled post is not in the outbox yet
+    assert_eq!(
+      vec![PersonOutboxItem {
+        post_id: Some(inserted_post.id),
+        comment_id: None,
+      }],
+      outbox_items
+    );
+    assert_eq!(1, creator_post_count);
+    assert_eq!(
+      vec![inserted_scheduled_post.id],
+      due_posts.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+    assert_eq!(None, published_post.scheduled_publish_time);
+    assert!(due_posts_after_publishing.is_empty());
     assert_eq!(expected_post_like, inserted_post_like);
+    assert_eq!(expected_post_reaction, inserted_post_reaction);
+    assert_eq!(inserted_post_reaction.id, inserted_post_reaction_again.id);
     assert_eq!(expected_post_saved, inserted_post_saved);
     assert_eq!(expected_post_read, inserted_post_read);
     assert_eq!(1, like_removed);
+    assert_eq!(1, reaction_removed);
     assert_eq!(1, saved_removed);
     assert_eq!(1, read_removed);
     assert_eq!(1, num_deleted);
@@ -1,8 +1,9 @@
 use crate::{
   naive_now,
-  newtypes::{PersonId, PostReportId},
+  newtypes::{PersonId, PostId, PostReportId},
   source::post_report::*,
   traits::Reportable,
+  ReportCreator,
 };
 use diesel::{dsl::*, result::Error, *};
 
@@ -61,3 +62,153 @@ impl Reportable for PostReport {
       .execute(conn)
   }
 }
+
+impl PostReport {
+  /// resolves all open reports of a post by the given creator, when a remote instance federates
+  /// that it resolved the report. Reports resolved this way have no resolver.
+  ///
+  /// * `conn` - the postgres connection
+  /// * `for_post_id` - the id of the reported post
+  /// * `by_creator` - the user or site who created the report
+  pub fn resolve_apub(
+    conn: &PgConnection,
+    for_post_id: PostId,
+    by_creator: ReportCreator,
+  ) -> Result<usize, Error> {
+    use crate::schema::post_report::dsl::*;
+    let (for_creator_id, for_site_id) = by_creator.columns();
+    // comparisons with null are never true, so only the column which is set can match
+    update(
+      post_report
+        .filter(post_id.eq(for_post_id))
+        .filter(
+          creator_id
+            .eq(for_creator_id)
+            .or(forwarded_by_site_id.eq(for_site_id)),
+        )
+        .filter(resolved.eq(false)),
+    )
+    .set((
+      resolved.eq(true),
+      resolver_id.eq(None::<PersonId>),
+      updated.eq(naive_now()),
+    ))
+    .execute(conn)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    establish_unpooled_connection,
+    newtypes::PostReportId,
+    schema::post_report,
+    source::{
+      community::{Community, CommunityForm},
+      person::{Person, PersonForm},
+      post::{Post, PostForm},
+      post_report::{PostReport, PostReportForm},
+      site::{Site, SiteForm},
+    },
+    traits::{Crud, Reportable},
+    ReportCreator,
+  };
+  use diesel::{PgConnection, QueryDsl, RunQueryDsl};
+  use serial_test::serial;
+
+  fn read_report(conn: &PgConnection, report_id: PostReportId) -> PostReport {
+    post_report::table
+      .find(report_id)
+      .first::<PostReport>(conn)
+      .unwrap()
+  }
+
+  #[test]
+  #[serial]
+  fn test_resolve_apub() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "merle_report".into(),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let site_form = SiteForm {
+      name: "forwarding_site".into(),
+      ..Default::default()
+    };
+    let inserted_site = Site::create(&conn, &site_form).unwrap();
+
+    let new_community = CommunityForm {
+      name: "test_community_report".to_string(),
+      title: "nada".to_owned(),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let new_post = PostForm {
+      name: "A reported post".into(),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      ..PostForm::default()
+    };
+    let inserted_post = Post::create(&conn, &new_post).unwrap();
+
+    let person_report_form = PostReportForm {
+      creator_id: Some(inserted_person.id),
+      post_id: inserted_post.id,
+      original_post_name: inserted_post.name.clone(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "from a person".into(),
+      forwarded_by_site_id: None,
+    };
+    let person_report = PostReport::report(&conn, &person_report_form).unwrap();
+
+    // A forwarded report, as stored by ForwardReport
+    let site_report_form = PostReportForm {
+      creator_id: None,
+      reason: "forwarded by a site".into(),
+      forwarded_by_site_id: Some(inserted_site.id),
+      ..person_report_form
+    };
+    let site_report = PostReport::report(&conn, &site_report_form).unwrap();
+
+    // Resolving the forwarded report, as done by ResolveReport, leaves the other one open
+    let resolved = PostReport::resolve_apub(
+      &conn,
+      inserted_post.id,
+      ReportCreator::Site(inserted_site.id),
+    )
+    .unwrap();
+    assert_eq!(1, resolved);
+    let read_site_report = read_report(&conn, site_report.id);
+    assert!(read_site_report.resolved);
+    assert_eq!(None, read_site_report.resolver_id);
+    assert!(!read_report(&conn, person_report.id).resolved);
+
+    let resolved = PostReport::resolve_apub(
+      &conn,
+      inserted_post.id,
+      ReportCreator::Person(inserted_person.id),
+    )
+    .unwrap();
+    assert_eq!(1, resolved);
+    assert!(read_report(&conn, person_report.id).resolved);
+
+    // Already resolved reports are not changed again
+    let resolved = PostReport::resolve_apub(
+      &conn,
+      inserted_post.id,
+      ReportCreator::Person(inserted_person.id),
+    )
+    .unwrap();
+    assert_eq!(0, resolved);
+
+    Post::delete(&conn, inserted_post.id).unwrap();
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+    Site::delete(&conn, inserted_site.id).unwrap();
+  }
+}
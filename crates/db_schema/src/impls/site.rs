@@ -1,4 +1,4 @@
-use crate::{source::site::*, traits::Crud, DbUrl};
+use crate::{source::site::*, traits::Crud, DbUrl, KEY_ROTATION_GRACE_PERIOD_DAYS};
 use diesel::{dsl::*, result::Error, *};
 use url::Url;
 
@@ -33,6 +33,21 @@ impl Site {
     site.order_by(id).first::<Self>(conn)
   }
 
+  /// Returns the number of updated rows, which is 0 if the site isn't set up yet.
+  pub fn update_federation_strict_allowlist(
+    conn: &PgConnection,
+    strict_allowlist: bool,
+  ) -> Result<usize, Error> {
+    use crate::schema::site::dsl::*;
+    let local_site_id = site.order_by(id).select(id).first::<i32>(conn).optional()?;
+    match local_site_id {
+      Some(local_site_id) => diesel::update(site.find(local_site_id))
+        .set(federation_strict_allowlist.eq(strict_allowlist))
+        .execute(conn),
+      None => Ok(0),
+    }
+  }
+
   pub fn upsert(conn: &PgConnection, site_form: &SiteForm) -> Result<Site, Error> {
     use crate::schema::site::dsl::*;
     insert_into(site)
@@ -55,6 +70,42 @@ impl Site {
     )
   }
 
+  /// Replaces the keypair of the local site. The previous public key is kept by a database trigger.
+  pub fn update_keypair(
+    conn: &PgConnection,
+    site_id: i32,
+    new_private_key: String,
+    new_public_key: String,
+  ) -> Result<Site, Error> {
+    use crate::schema::site::dsl::*;
+    diesel::update(site.find(site_id))
+      .set((
+        private_key.eq(new_private_key),
+        public_key.eq(new_public_key),
+      ))
+      .get_result::<Self>(conn)
+  }
+
+  /// Stops accepting signatures made with the previous key of the site actor.
+  pub fn revoke_previous_key(conn: &PgConnection, site_id: i32) -> Result<Site, Error> {
+    use crate::schema::site::dsl::*;
+    diesel::update(site.find(site_id))
+      .set(previous_public_key.eq(None::<String>))
+      .get_result::<Self>(conn)
+  }
+
+  /// Forgets previous keys which were replaced longer ago than the grace period.
+  pub fn expire_previous_keys(conn: &PgConnection) -> Result<usize, Error> {
+    use crate::schema::site::dsl::*;
+    diesel::update(
+      site
+        .filter(previous_public_key.is_not_null())
+        .filter(key_rotated_at.lt(now - KEY_ROTATION_GRACE_PERIOD_DAYS.days())),
+    )
+    .set(previous_public_key.eq(None::<String>))
+    .execute(conn)
+  }
+
   pub fn read_remote_sites(conn: &PgConnection) -> Result<Vec<Self>, Error> {
     use crate::schema::site::dsl::*;
     site.order_by(id).offset(1).get_results::<Self>(conn)
@@ -10,6 +10,7 @@ extern crate diesel_migrations;
 extern crate strum_macros;
 
 pub mod aggregates;
+pub mod full_text_search;
 pub mod impls;
 pub mod newtypes;
 pub mod schema;
@@ -18,7 +19,7 @@ pub mod traits;
 
 pub type DbPool = diesel::r2d2::Pool<diesel::r2d2::ConnectionManager<diesel::PgConnection>>;
 
-use crate::newtypes::DbUrl;
+use crate::newtypes::{DbUrl, PersonId};
 use chrono::NaiveDateTime;
 use diesel::{Connection, PgConnection};
 use lemmy_utils::LemmyError;
@@ -29,12 +30,13 @@ use std::{env, env::VarError};
 use url::Url;
 
 const FETCH_LIMIT_DEFAULT: i64 = 10;
-const FETCH_LIMIT_MAX: i64 = 50;
+pub const FETCH_LIMIT_MAX: i64 = 50;
 
 pub fn get_database_url_from_env() -> Result<String, VarError> {
   env::var("LEMMY_DATABASE_URL")
 }
 
+/// New sorts are added at the end, because users' default sort is stored as the variant index.
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy)]
 pub enum SortType {
   Active,
@@ -47,6 +49,25 @@ pub enum SortType {
   TopAll,
   MostComments,
   NewComments,
+  TopHour,
+  TopSixHour,
+  TopTwelveHour,
+  /// Many votes, split evenly between up- and downvotes
+  Controversial,
+  /// Many upvotes in the last hours
+  Rising,
+  /// Best matches of a search first, like Hot for other listings
+  Relevance,
+}
+
+/// Sorts for the comments of a post. Replies are always sorted below their parent.
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy)]
+pub enum CommentSortType {
+  Hot,
+  Top,
+  New,
+  Old,
+  Controversial,
 }
 
 #[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy)]
@@ -67,6 +88,24 @@ pub enum SearchType {
   Url,
 }
 
+/// The creator of a report. Reports which are forwarded by another instance dont include the
+/// original reporter, so they are attributed to the site which forwarded them.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReportCreator {
+  Person(PersonId),
+  Site(i32),
+}
+
+impl ReportCreator {
+  /// Values for the `creator_id` and `forwarded_by_site_id` columns of the report tables
+  pub fn columns(self) -> (Option<PersonId>, Option<i32>) {
+    match self {
+      ReportCreator::Person(person_id) => (Some(person_id), None),
+      ReportCreator::Site(site_id) => (None, Some(site_id)),
+    }
+  }
+}
+
 pub fn from_opt_str_to_opt_enum<T: std::str::FromStr>(opt: &Option<String>) -> Option<T> {
   opt.as_ref().and_then(|t| T::from_str(t).ok())
 }
@@ -85,7 +124,7 @@ pub fn limit_and_offset(page: Option<i64>, limit: Option<i64>) -> (i64, i64) {
     Some(l) => std::cmp::min(FETCH_LIMIT_MAX, l),
     None => FETCH_LIMIT_DEFAULT,
   };
-  let offset = limit * (page - 1);
+  let offset = limit.saturating_mul(page - 1);
   (limit, offset)
 }
 
@@ -141,6 +180,11 @@ pub fn naive_now() -> NaiveDateTime {
   chrono::prelude::Utc::now().naive_utc()
 }
 
+/// After an actor changes its keypair, activities which were signed with the old key may still be
+/// waiting in delivery queues, and get retried for a few days. The previous key is accepted for
+/// this long, measured with the database clock like the rotation time which a trigger sets.
+pub const KEY_ROTATION_GRACE_PERIOD_DAYS: i32 = 7;
+
 static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"^[a-zA-Z0-9.!#$%&’*+/=?^_`{|}~-]+@[a-zA-Z0-9-]+(?:\.[a-zA-Z0-9-]+)*$")
     .expect("compile email regex")
@@ -154,6 +198,8 @@ pub mod functions {
   }
 
   sql_function!(fn lower(x: Text) -> Text);
+
+  sql_function!(fn array_length(array: Array<Integer>, dimension: Integer) -> Integer);
 }
 
 #[cfg(test)]
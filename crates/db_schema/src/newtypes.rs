@@ -49,6 +49,9 @@ pub struct CommunityId(pub i32);
 )]
 pub struct LocalUserId(pub i32);
 
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Serialize, Deserialize, DieselNewType)]
+pub struct PollOptionId(pub i32);
+
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Serialize, Deserialize, DieselNewType)]
 pub struct PrivateMessageId(i32);
 
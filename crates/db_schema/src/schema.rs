@@ -24,6 +24,9 @@ table! {
         deleted -> Bool,
         ap_id -> Varchar,
         local -> Bool,
+        content_warning -> Nullable<Text>,
+        language -> Nullable<Text>,
+        path -> Array<Int4>,
     }
 }
 
@@ -35,6 +38,9 @@ table! {
         upvotes -> Int8,
         downvotes -> Int8,
         published -> Timestamp,
+        child_count -> Int4,
+        controversy_rank -> Float8,
+        rising_rank -> Float8,
     }
 }
 
@@ -52,7 +58,7 @@ table! {
 table! {
     comment_report (id) {
         id -> Int4,
-        creator_id -> Int4,
+        creator_id -> Nullable<Int4>,
         comment_id -> Int4,
         original_comment_text -> Text,
         reason -> Text,
@@ -60,6 +66,7 @@ table! {
         resolver_id -> Nullable<Int4>,
         published -> Timestamp,
         updated -> Nullable<Timestamp>,
+        forwarded_by_site_id -> Nullable<Int4>,
     }
 }
 
@@ -94,6 +101,10 @@ table! {
         inbox_url -> Varchar,
         shared_inbox_url -> Nullable<Varchar>,
         hidden -> Bool,
+        moved_to -> Nullable<Varchar>,
+        also_known_as -> Array<Text>,
+        previous_public_key -> Nullable<Text>,
+        key_rotated_at -> Nullable<Timestamp>,
     }
 }
 
@@ -161,6 +172,8 @@ table! {
         show_new_post_notifs -> Bool,
         email_verified -> Bool,
         accepted_application -> Bool,
+        default_language -> Nullable<Text>,
+        discussion_languages -> Array<Text>,
     }
 }
 
@@ -307,6 +320,8 @@ table! {
         admin -> Bool,
         bot_account -> Bool,
         ban_expires -> Nullable<Timestamp>,
+        moved_to -> Nullable<Varchar>,
+        also_known_as -> Array<Text>,
     }
 }
 
@@ -360,6 +375,9 @@ table! {
         thumbnail_url -> Nullable<Text>,
         ap_id -> Varchar,
         local -> Bool,
+        content_warning -> Nullable<Text>,
+        language -> Nullable<Text>,
+        scheduled_publish_time -> Nullable<Timestamp>,
     }
 }
 
@@ -375,6 +393,8 @@ table! {
         published -> Timestamp,
         newest_comment_time_necro -> Timestamp,
         newest_comment_time -> Timestamp,
+        controversy_rank -> Float8,
+        rising_rank -> Float8,
     }
 }
 
@@ -400,7 +420,7 @@ table! {
 table! {
     post_report (id) {
         id -> Int4,
-        creator_id -> Int4,
+        creator_id -> Nullable<Int4>,
         post_id -> Int4,
         original_post_name -> Varchar,
         original_post_url -> Nullable<Text>,
@@ -410,6 +430,7 @@ table! {
         resolver_id -> Nullable<Int4>,
         published -> Timestamp,
         updated -> Nullable<Timestamp>,
+        forwarded_by_site_id -> Nullable<Int4>,
     }
 }
 
@@ -461,6 +482,10 @@ table! {
         private_key -> Nullable<Text>,
         public_key -> Text,
         default_theme -> Text,
+        previous_public_key -> Nullable<Text>,
+        key_rotated_at -> Nullable<Timestamp>,
+        public_edit_history -> Bool,
+        federation_strict_allowlist -> Bool,
     }
 }
 
@@ -512,6 +537,9 @@ table! {
         deleted -> Bool,
         ap_id -> Varchar,
         local -> Bool,
+        content_warning -> Nullable<Text>,
+        language -> Nullable<Text>,
+        path -> Array<Int4>,
     }
 }
 
@@ -538,6 +566,8 @@ table! {
         admin -> Bool,
         bot_account -> Bool,
         ban_expires -> Nullable<Timestamp>,
+        moved_to -> Nullable<Varchar>,
+        also_known_as -> Array<Text>,
     }
 }
 
@@ -564,6 +594,8 @@ table! {
         admin -> Bool,
         bot_account -> Bool,
         ban_expires -> Nullable<Timestamp>,
+        moved_to -> Nullable<Varchar>,
+        also_known_as -> Array<Text>,
     }
 }
 
@@ -606,6 +638,162 @@ table! {
     }
 }
 
+table! {
+    activity_queue_job (id) {
+        id -> Text,
+        queue -> Text,
+        runner_id -> Nullable<Text>,
+        job -> Jsonb,
+        published -> Timestamp,
+        updated -> Timestamp,
+        next_queue -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    activity_queue_stats (id) {
+        id -> Int4,
+        stats -> Jsonb,
+    }
+}
+
+table! {
+    instance_delivery (id) {
+        id -> Int4,
+        domain -> Text,
+        success_count -> Int8,
+        failure_count -> Int8,
+        consecutive_failures -> Int4,
+        last_success -> Nullable<Timestamp>,
+        last_failure -> Nullable<Timestamp>,
+        suspended -> Bool,
+        next_probe -> Nullable<Timestamp>,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    inbox_queue (id) {
+        id -> Int4,
+        ap_id -> Text,
+        actor -> Text,
+        inbox -> Text,
+        data -> Jsonb,
+        received -> Timestamp,
+        started -> Nullable<Timestamp>,
+        finished -> Nullable<Timestamp>,
+        error -> Nullable<Text>,
+        attempts -> Int4,
+        retry_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    community_backfill (id) {
+        id -> Int4,
+        community_id -> Int4,
+        pages_fetched -> Int4,
+        posts_fetched -> Int4,
+        comments_fetched -> Int4,
+        requests_used -> Int4,
+        published -> Timestamp,
+        finished -> Nullable<Timestamp>,
+        error -> Nullable<Text>,
+    }
+}
+
+table! {
+    federation_list (id) {
+        id -> Int4,
+        domain -> Text,
+        blocked -> Bool,
+        admin_person_id -> Nullable<Int4>,
+        reason -> Nullable<Text>,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    mod_federation_list (id) {
+        id -> Int4,
+        mod_person_id -> Int4,
+        domain -> Text,
+        blocked -> Bool,
+        removed -> Bool,
+        reason -> Nullable<Text>,
+        when_ -> Timestamp,
+    }
+}
+
+table! {
+    poll (id) {
+        id -> Int4,
+        post_id -> Int4,
+        multiple_choice -> Bool,
+        end_time -> Nullable<Timestamp>,
+        published -> Timestamp,
+        results_pending -> Bool,
+    }
+}
+
+table! {
+    poll_option (id) {
+        id -> Int4,
+        poll_id -> Int4,
+        name -> Text,
+        vote_count -> Int4,
+    }
+}
+
+table! {
+    poll_vote (id) {
+        id -> Int4,
+        poll_option_id -> Int4,
+        person_id -> Int4,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    post_reaction (id) {
+        id -> Int4,
+        post_id -> Int4,
+        person_id -> Int4,
+        emoji -> Text,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    comment_reaction (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        person_id -> Int4,
+        emoji -> Text,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    post_revision (id) {
+        id -> Int4,
+        post_id -> Int4,
+        name -> Varchar,
+        url -> Nullable<Text>,
+        body -> Nullable<Text>,
+        published -> Timestamp,
+    }
+}
+
+table! {
+    comment_revision (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        content -> Text,
+        published -> Timestamp,
+    }
+}
+
 joinable!(comment_alias_1 -> person_alias_1 (creator_id));
 joinable!(comment -> comment_alias_1 (parent_id));
 joinable!(person_mention -> person_alias_1 (recipient_id));
@@ -671,6 +859,19 @@ joinable!(registration_application -> local_user (local_user_id));
 joinable!(registration_application -> person (admin_id));
 joinable!(mod_hide_community -> person (mod_person_id));
 joinable!(mod_hide_community -> community (community_id));
+joinable!(community_backfill -> community (community_id));
+joinable!(federation_list -> person (admin_person_id));
+joinable!(mod_federation_list -> person (mod_person_id));
+joinable!(post_reaction -> post (post_id));
+joinable!(post_reaction -> person (person_id));
+joinable!(comment_reaction -> comment (comment_id));
+joinable!(comment_reaction -> person (person_id));
+joinable!(poll -> post (post_id));
+joinable!(poll_option -> poll (poll_id));
+joinable!(poll_vote -> poll_option (poll_option_id));
+joinable!(poll_vote -> person (person_id));
+joinable!(post_revision -> post (post_id));
+joinable!(comment_revision -> comment (comment_id));
 
 allow_tables_to_appear_in_same_query!(
   activity,
@@ -716,5 +917,19 @@ allow_tables_to_appear_in_same_query!(
   person_alias_1,
   person_alias_2,
   email_verification,
-  registration_application
+  registration_application,
+  activity_queue_job,
+  activity_queue_stats,
+  instance_delivery,
+  inbox_queue,
+  community_backfill,
+  federation_list,
+  mod_federation_list,
+  post_reaction,
+  comment_reaction,
+  poll,
+  poll_option,
+  poll_vote,
+  post_revision,
+  comment_revision
 );
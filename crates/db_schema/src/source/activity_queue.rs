@@ -0,0 +1,35 @@
+use crate::schema::{activity_queue_job, activity_queue_stats};
+use serde_json::Value;
+use std::fmt::Debug;
+
+/// A serialized background job for delivering an outgoing activity.
+#[derive(Queryable, Identifiable, PartialEq, Debug, Clone)]
+#[table_name = "activity_queue_job"]
+pub struct ActivityQueueJob {
+  pub id: String,
+  pub queue: String,
+  /// Set while a worker is processing the job
+  pub runner_id: Option<String>,
+  pub job: Value,
+  pub published: chrono::NaiveDateTime,
+  pub updated: chrono::NaiveDateTime,
+  /// Failed jobs are only retried once this time has passed
+  pub next_queue: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[table_name = "activity_queue_job"]
+pub struct ActivityQueueJobForm {
+  pub id: String,
+  pub queue: String,
+  pub job: Value,
+  pub updated: chrono::NaiveDateTime,
+  pub next_queue: Option<Option<chrono::NaiveDateTime>>,
+}
+
+#[derive(Queryable, Identifiable, Insertable, AsChangeset, PartialEq, Debug, Clone)]
+#[table_name = "activity_queue_stats"]
+pub struct ActivityQueueStats {
+  pub id: i32,
+  pub stats: Value,
+}
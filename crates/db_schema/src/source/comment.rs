@@ -1,17 +1,17 @@
 use crate::{
   newtypes::{CommentId, DbUrl, PersonId, PostId},
-  schema::{comment, comment_alias_1, comment_like, comment_saved},
+  schema::{
+    comment,
+    comment_alias_1,
+    comment_like,
+    comment_reaction,
+    comment_revision,
+    comment_saved,
+  },
   source::post::Post,
 };
 use serde::{Deserialize, Serialize};
 
-// WITH RECURSIVE MyTree AS (
-//     SELECT * FROM comment WHERE parent_id IS NULL
-//     UNION ALL
-//     SELECT m.* FROM comment AS m JOIN MyTree AS t ON m.parent_id = t.id
-// )
-// SELECT * FROM MyTree;
-
 #[derive(
   Clone, Queryable, Associations, Identifiable, PartialEq, Debug, Serialize, Deserialize,
 )]
@@ -30,6 +30,10 @@ pub struct Comment {
   pub deleted: bool,
   pub ap_id: DbUrl,
   pub local: bool,
+  pub content_warning: Option<String>,
+  pub language: Option<String>,
+  /// Ids from the top level comment down to this comment
+  pub path: Vec<i32>,
 }
 
 #[derive(
@@ -50,6 +54,9 @@ pub struct CommentAlias1 {
   pub deleted: bool,
   pub ap_id: DbUrl,
   pub local: bool,
+  pub content_warning: Option<String>,
+  pub language: Option<String>,
+  pub path: Vec<i32>,
 }
 
 #[derive(Insertable, AsChangeset, Clone, Default)]
@@ -66,6 +73,8 @@ pub struct CommentForm {
   pub deleted: Option<bool>,
   pub ap_id: Option<DbUrl>,
   pub local: Option<bool>,
+  pub content_warning: Option<Option<String>>,
+  pub language: Option<Option<String>>,
 }
 
 #[derive(Identifiable, Queryable, Associations, PartialEq, Debug, Clone)]
@@ -89,6 +98,25 @@ pub struct CommentLikeForm {
   pub score: i16,
 }
 
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Comment)]
+#[table_name = "comment_reaction"]
+pub struct CommentReaction {
+  pub id: i32,
+  pub comment_id: CommentId,
+  pub person_id: PersonId,
+  pub emoji: String,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[table_name = "comment_reaction"]
+pub struct CommentReactionForm {
+  pub comment_id: CommentId,
+  pub person_id: PersonId,
+  pub emoji: String,
+}
+
 #[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
 #[belongs_to(Comment)]
 #[table_name = "comment_saved"]
@@ -105,3 +133,16 @@ pub struct CommentSavedForm {
   pub comment_id: CommentId,
   pub person_id: PersonId,
 }
+
+/// A previous version of a comment, written by the database whenever the comment is edited.
+#[derive(
+  Clone, Queryable, Associations, Identifiable, PartialEq, Debug, Serialize, Deserialize,
+)]
+#[belongs_to(Comment)]
+#[table_name = "comment_revision"]
+pub struct CommentRevision {
+  pub id: i32,
+  pub comment_id: CommentId,
+  pub content: String,
+  pub published: chrono::NaiveDateTime,
+}
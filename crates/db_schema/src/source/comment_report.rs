@@ -12,7 +12,8 @@ use serde::{Deserialize, Serialize};
 #[table_name = "comment_report"]
 pub struct CommentReport {
   pub id: CommentReportId,
-  pub creator_id: PersonId,
+  /// Empty for reports which were forwarded by another instance
+  pub creator_id: Option<PersonId>,
   pub comment_id: CommentId,
   pub original_comment_text: String,
   pub reason: String,
@@ -20,13 +21,15 @@ pub struct CommentReport {
   pub resolver_id: Option<PersonId>,
   pub published: chrono::NaiveDateTime,
   pub updated: Option<chrono::NaiveDateTime>,
+  pub forwarded_by_site_id: Option<i32>,
 }
 
 #[derive(Insertable, AsChangeset, Clone)]
 #[table_name = "comment_report"]
 pub struct CommentReportForm {
-  pub creator_id: PersonId,
+  pub creator_id: Option<PersonId>,
   pub comment_id: CommentId,
   pub original_comment_text: String,
   pub reason: String,
+  pub forwarded_by_site_id: Option<i32>,
 }
@@ -27,6 +27,11 @@ pub struct Community {
   pub inbox_url: DbUrl,
   pub shared_inbox_url: Option<DbUrl>,
   pub hidden: bool,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
+  /// Set when the keypair changes, signatures with the previous key are accepted for a while
+  pub previous_public_key: Option<String>,
+  pub key_rotated_at: Option<chrono::NaiveDateTime>,
 }
 
 /// A safe representation of community, without the sensitive info
@@ -47,6 +52,8 @@ pub struct CommunitySafe {
   pub icon: Option<DbUrl>,
   pub banner: Option<DbUrl>,
   pub hidden: bool,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 #[derive(Insertable, AsChangeset, Debug, Default)]
@@ -71,6 +78,8 @@ pub struct CommunityForm {
   pub inbox_url: Option<DbUrl>,
   pub shared_inbox_url: Option<Option<DbUrl>>,
   pub hidden: Option<bool>,
+  pub moved_to: Option<Option<DbUrl>>,
+  pub also_known_as: Option<Vec<DbUrl>>,
 }
 
 #[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
@@ -0,0 +1,28 @@
+use crate::{newtypes::CommunityId, schema::community_backfill};
+use serde::{Deserialize, Serialize};
+
+/// Progress of fetching the older posts and comments of a remote community, which is started when
+/// the community is followed for the first time.
+#[derive(Queryable, Identifiable, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[table_name = "community_backfill"]
+pub struct CommunityBackfill {
+  pub id: i32,
+  pub community_id: CommunityId,
+  pub pages_fetched: i32,
+  pub posts_fetched: i32,
+  pub comments_fetched: i32,
+  pub requests_used: i32,
+  pub published: chrono::NaiveDateTime,
+  /// Set once the whole outbox was read, or the request budget is used up
+  pub finished: Option<chrono::NaiveDateTime>,
+  pub error: Option<String>,
+}
+
+#[derive(AsChangeset, Clone, Default)]
+#[table_name = "community_backfill"]
+pub struct CommunityBackfillProgressForm {
+  pub pages_fetched: i32,
+  pub posts_fetched: i32,
+  pub comments_fetched: i32,
+  pub requests_used: i32,
+}
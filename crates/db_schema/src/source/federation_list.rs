@@ -0,0 +1,35 @@
+use crate::{newtypes::PersonId, schema::federation_list};
+use serde::{Deserialize, Serialize};
+
+/// An instance on the federation allowlist or blocklist.
+#[derive(Queryable, Identifiable, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[table_name = "federation_list"]
+pub struct FederationListEntry {
+  pub id: i32,
+  pub domain: String,
+  /// True for the blocklist, false for the allowlist
+  pub blocked: bool,
+  /// The admin who added the entry, None if it was imported from the config file
+  pub admin_person_id: Option<PersonId>,
+  pub reason: Option<String>,
+  pub published: chrono::NaiveDateTime,
+}
+
+/// The domains on the federation allowlist and blocklist, together with the strict allowlist
+/// setting of the site.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct FederationLists {
+  /// If empty, the allowlist is not used
+  pub allowed: Vec<String>,
+  pub blocked: Vec<String>,
+  pub strict_allowlist: bool,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[table_name = "federation_list"]
+pub struct FederationListEntryForm {
+  pub domain: String,
+  pub blocked: bool,
+  pub admin_person_id: Option<PersonId>,
+  pub reason: Option<String>,
+}
@@ -0,0 +1,43 @@
+use crate::{newtypes::DbUrl, schema::inbox_queue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An incoming activity, queued for processing after its signature was verified.
+#[derive(Queryable, Identifiable, PartialEq, Debug, Clone)]
+#[table_name = "inbox_queue"]
+pub struct InboxQueueEntry {
+  pub id: i32,
+  pub ap_id: DbUrl,
+  pub actor: DbUrl,
+  /// The kind of inbox which received the activity, determines how it is parsed
+  pub inbox: String,
+  pub data: Value,
+  pub received: chrono::NaiveDateTime,
+  pub started: Option<chrono::NaiveDateTime>,
+  pub finished: Option<chrono::NaiveDateTime>,
+  pub error: Option<String>,
+  /// Number of times processing failed
+  pub attempts: i32,
+  /// A failed activity isn't processed again before this time
+  pub retry_at: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[table_name = "inbox_queue"]
+pub struct InboxQueueEntryForm {
+  pub ap_id: DbUrl,
+  pub actor: DbUrl,
+  pub inbox: String,
+  pub data: Value,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct InboxQueueStats {
+  /// Activities waiting for a worker, including failed ones which are retried later
+  pub pending: i64,
+  pub processing: i64,
+  pub processed_last_hour: i64,
+  pub failed_last_hour: i64,
+  /// Average time from receiving an activity until it was processed, over the last hour
+  pub average_latency_seconds: Option<f64>,
+}
@@ -0,0 +1,19 @@
+use crate::schema::instance_delivery;
+use serde::{Deserialize, Serialize};
+
+/// Delivery statistics for outgoing activities to a single remote instance.
+#[derive(Queryable, Identifiable, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[table_name = "instance_delivery"]
+pub struct InstanceDelivery {
+  pub id: i32,
+  pub domain: String,
+  pub success_count: i64,
+  pub failure_count: i64,
+  pub consecutive_failures: i32,
+  pub last_success: Option<chrono::NaiveDateTime>,
+  pub last_failure: Option<chrono::NaiveDateTime>,
+  /// Set once the instance failed too often in a row. Nothing is sent to it until `next_probe`.
+  pub suspended: bool,
+  pub next_probe: Option<chrono::NaiveDateTime>,
+  pub published: chrono::NaiveDateTime,
+}
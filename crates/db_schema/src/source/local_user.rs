@@ -25,6 +25,8 @@ pub struct LocalUser {
   pub show_new_post_notifs: bool,
   pub email_verified: bool,
   pub accepted_application: bool,
+  pub default_language: Option<String>,
+  pub discussion_languages: Vec<String>,
 }
 
 // TODO redo these, check table defaults
@@ -47,6 +49,8 @@ pub struct LocalUserForm {
   pub show_new_post_notifs: Option<bool>,
   pub email_verified: Option<bool>,
   pub accepted_application: Option<bool>,
+  pub default_language: Option<Option<String>>,
+  pub discussion_languages: Option<Vec<String>>,
 }
 
 /// A local user view that removes password encrypted
@@ -70,4 +74,6 @@ pub struct LocalUserSettings {
   pub show_new_post_notifs: bool,
   pub email_verified: bool,
   pub accepted_application: bool,
+  pub default_language: Option<String>,
+  pub discussion_languages: Vec<String>,
 }
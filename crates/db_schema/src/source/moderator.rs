@@ -5,6 +5,7 @@ use crate::{
     mod_add_community,
     mod_ban,
     mod_ban_from_community,
+    mod_federation_list,
     mod_hide_community,
     mod_lock_post,
     mod_remove_comment,
@@ -236,3 +237,25 @@ pub struct ModAddForm {
   pub other_person_id: PersonId,
   pub removed: Option<bool>,
 }
+
+#[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
+#[table_name = "mod_federation_list"]
+pub struct ModFederationList {
+  pub id: i32,
+  pub mod_person_id: PersonId,
+  pub domain: String,
+  pub blocked: bool,
+  pub removed: bool,
+  pub reason: Option<String>,
+  pub when_: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[table_name = "mod_federation_list"]
+pub struct ModFederationListForm {
+  pub mod_person_id: PersonId,
+  pub domain: String,
+  pub blocked: bool,
+  pub removed: bool,
+  pub reason: Option<String>,
+}
@@ -1,7 +1,8 @@
 use crate::{
-  newtypes::{DbUrl, PersonId},
+  newtypes::{CommentId, DbUrl, PersonId, PostId},
   schema::{person, person_alias_1, person_alias_2},
 };
+use diesel::sql_types::{Integer, Nullable};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
@@ -28,6 +29,8 @@ pub struct Person {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 /// A safe representation of person, without the sensitive info
@@ -52,6 +55,8 @@ pub struct PersonSafe {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 #[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
@@ -78,6 +83,8 @@ pub struct PersonAlias1 {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 #[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
@@ -101,6 +108,8 @@ pub struct PersonSafeAlias1 {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 #[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
@@ -127,6 +136,8 @@ pub struct PersonAlias2 {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 #[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
@@ -150,6 +161,8 @@ pub struct PersonSafeAlias2 {
   pub admin: bool,
   pub bot_account: bool,
   pub ban_expires: Option<chrono::NaiveDateTime>,
+  pub moved_to: Option<DbUrl>,
+  pub also_known_as: Vec<DbUrl>,
 }
 
 #[derive(Insertable, AsChangeset, Clone, Default)]
@@ -175,4 +188,15 @@ pub struct PersonForm {
   pub admin: Option<bool>,
   pub bot_account: Option<bool>,
   pub ban_expires: Option<Option<chrono::NaiveDateTime>>,
+  pub moved_to: Option<Option<DbUrl>>,
+  pub also_known_as: Option<Vec<DbUrl>>,
+}
+
+/// A post or comment in the outbox of a person. Exactly one of the ids is set.
+#[derive(QueryableByName, PartialEq, Debug, Clone)]
+pub struct PersonOutboxItem {
+  #[sql_type = "Nullable<Integer>"]
+  pub post_id: Option<PostId>,
+  #[sql_type = "Nullable<Integer>"]
+  pub comment_id: Option<CommentId>,
 }
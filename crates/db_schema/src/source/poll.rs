@@ -0,0 +1,63 @@
+use crate::{
+  newtypes::{PersonId, PollOptionId, PostId},
+  schema::{poll, poll_option, poll_vote},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
+#[table_name = "poll"]
+pub struct Poll {
+  pub id: i32,
+  pub post_id: PostId,
+  pub multiple_choice: bool,
+  pub end_time: Option<chrono::NaiveDateTime>,
+  pub published: chrono::NaiveDateTime,
+  /// Votes were cast in this local poll since the results were last sent to other instances
+  pub results_pending: bool,
+}
+
+#[derive(Insertable, AsChangeset, Default)]
+#[table_name = "poll"]
+pub struct PollForm {
+  pub post_id: PostId,
+  pub multiple_choice: Option<bool>,
+  pub end_time: Option<Option<chrono::NaiveDateTime>>,
+}
+
+#[derive(
+  Clone, Queryable, Associations, Identifiable, PartialEq, Debug, Serialize, Deserialize,
+)]
+#[belongs_to(Poll)]
+#[table_name = "poll_option"]
+pub struct PollOption {
+  pub id: PollOptionId,
+  pub poll_id: i32,
+  pub name: String,
+  pub vote_count: i32,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[table_name = "poll_option"]
+pub struct PollOptionForm {
+  pub poll_id: i32,
+  pub name: String,
+  /// Only set for remote polls, local counts are updated by the database
+  pub vote_count: Option<i32>,
+}
+
+#[derive(Queryable, Associations, Identifiable, PartialEq, Debug)]
+#[belongs_to(PollOption)]
+#[table_name = "poll_vote"]
+pub struct PollVote {
+  pub id: i32,
+  pub poll_option_id: PollOptionId,
+  pub person_id: PersonId,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[table_name = "poll_vote"]
+pub struct PollVoteForm {
+  pub poll_option_id: PollOptionId,
+  pub person_id: PersonId,
+}
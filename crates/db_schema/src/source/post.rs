@@ -1,6 +1,6 @@
 use crate::{
   newtypes::{CommunityId, DbUrl, PersonId, PostId},
-  schema::{post, post_like, post_read, post_saved},
+  schema::{post, post_like, post_reaction, post_read, post_revision, post_saved},
 };
 use serde::{Deserialize, Serialize};
 #[derive(Clone, Queryable, Identifiable, PartialEq, Debug, Serialize, Deserialize)]
@@ -25,6 +25,9 @@ pub struct Post {
   pub thumbnail_url: Option<DbUrl>,
   pub ap_id: DbUrl,
   pub local: bool,
+  pub content_warning: Option<String>,
+  pub language: Option<String>,
+  pub scheduled_publish_time: Option<chrono::NaiveDateTime>,
 }
 
 #[derive(Insertable, AsChangeset, Default)]
@@ -48,6 +51,9 @@ pub struct PostForm {
   pub thumbnail_url: Option<DbUrl>,
   pub ap_id: Option<DbUrl>,
   pub local: Option<bool>,
+  pub content_warning: Option<Option<String>>,
+  pub language: Option<Option<String>>,
+  pub scheduled_publish_time: Option<Option<chrono::NaiveDateTime>>,
 }
 
 #[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
@@ -69,6 +75,25 @@ pub struct PostLikeForm {
   pub score: i16,
 }
 
+#[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
+#[belongs_to(Post)]
+#[table_name = "post_reaction"]
+pub struct PostReaction {
+  pub id: i32,
+  pub post_id: PostId,
+  pub person_id: PersonId,
+  pub emoji: String,
+  pub published: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable, AsChangeset, Clone)]
+#[table_name = "post_reaction"]
+pub struct PostReactionForm {
+  pub post_id: PostId,
+  pub person_id: PersonId,
+  pub emoji: String,
+}
+
 #[derive(Identifiable, Queryable, Associations, PartialEq, Debug)]
 #[belongs_to(Post)]
 #[table_name = "post_saved"]
@@ -102,3 +127,18 @@ pub struct PostReadForm {
   pub post_id: PostId,
   pub person_id: PersonId,
 }
+
+/// A previous version of a post, written by the database whenever the post is edited.
+#[derive(
+  Clone, Queryable, Associations, Identifiable, PartialEq, Debug, Serialize, Deserialize,
+)]
+#[belongs_to(Post)]
+#[table_name = "post_revision"]
+pub struct PostRevision {
+  pub id: i32,
+  pub post_id: PostId,
+  pub name: String,
+  pub url: Option<DbUrl>,
+  pub body: Option<String>,
+  pub published: chrono::NaiveDateTime,
+}
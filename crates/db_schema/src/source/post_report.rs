@@ -12,7 +12,8 @@ use serde::{Deserialize, Serialize};
 #[table_name = "post_report"]
 pub struct PostReport {
   pub id: PostReportId,
-  pub creator_id: PersonId,
+  /// Empty for reports which were forwarded by another instance
+  pub creator_id: Option<PersonId>,
   pub post_id: PostId,
   pub original_post_name: String,
   pub original_post_url: Option<DbUrl>,
@@ -22,15 +23,17 @@ pub struct PostReport {
   pub resolver_id: Option<PersonId>,
   pub published: chrono::NaiveDateTime,
   pub updated: Option<chrono::NaiveDateTime>,
+  pub forwarded_by_site_id: Option<i32>,
 }
 
 #[derive(Insertable, AsChangeset, Clone)]
 #[table_name = "post_report"]
 pub struct PostReportForm {
-  pub creator_id: PersonId,
+  pub creator_id: Option<PersonId>,
   pub post_id: PostId,
   pub original_post_name: String,
   pub original_post_url: Option<DbUrl>,
   pub original_post_body: Option<String>,
   pub reason: String,
+  pub forwarded_by_site_id: Option<i32>,
 }
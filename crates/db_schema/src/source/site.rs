@@ -26,6 +26,12 @@ pub struct Site {
   pub private_key: Option<String>,
   pub public_key: String,
   pub default_theme: String,
+  pub previous_public_key: Option<String>,
+  pub key_rotated_at: Option<chrono::NaiveDateTime>,
+  pub public_edit_history: bool,
+  /// If true, only federate with instances on the allowlist. If false, the allowlist is only
+  /// used for communities.
+  pub federation_strict_allowlist: bool,
 }
 
 #[derive(Insertable, AsChangeset, Default)]
@@ -52,4 +58,6 @@ pub struct SiteForm {
   pub private_key: Option<Option<String>>,
   pub public_key: Option<String>,
   pub default_theme: Option<String>,
+  pub public_edit_history: Option<bool>,
+  pub federation_strict_allowlist: Option<bool>,
 }
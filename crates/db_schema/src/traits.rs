@@ -77,6 +77,20 @@ pub trait Bannable {
     Self: Sized;
 }
 
+pub trait Reactable {
+  type Form;
+  fn react(conn: &PgConnection, form: &Self::Form) -> Result<Self, Error>
+  where
+    Self: Sized;
+  fn unreact(conn: &PgConnection, form: &Self::Form) -> Result<usize, Error>
+  where
+    Self: Sized;
+  /// Number of reactions which the person of the form made to the same item with other emojis
+  fn count_other_reactions(conn: &PgConnection, form: &Self::Form) -> Result<i64, Error>
+  where
+    Self: Sized;
+}
+
 pub trait Saveable {
   type Form;
   fn save(conn: &PgConnection, form: &Self::Form) -> Result<Self, Error>
@@ -2,7 +2,7 @@ use diesel::{dsl::*, result::Error, *};
 use lemmy_db_schema::{
   aggregates::comment_aggregates::CommentAggregates,
   limit_and_offset,
-  newtypes::{CommentReportId, CommunityId, PersonId},
+  newtypes::{CommentReportId, CommunityId, DbUrl, PersonId},
   schema::{
     comment,
     comment_aggregates,
@@ -15,6 +15,7 @@ use lemmy_db_schema::{
     person_alias_1,
     person_alias_2,
     post,
+    site,
   },
   source::{
     comment::Comment,
@@ -33,12 +34,15 @@ pub struct CommentReportView {
   pub comment: Comment,
   pub post: Post,
   pub community: CommunitySafe,
-  pub creator: PersonSafe,
+  /// Empty for reports which were forwarded by another instance
+  pub creator: Option<PersonSafe>,
   pub comment_creator: PersonSafeAlias1,
   pub counts: CommentAggregates,
   pub creator_banned_from_community: bool, // Left Join to CommunityPersonBan
   pub my_vote: Option<i16>,                // Left join to CommentLike
   pub resolver: Option<PersonSafeAlias2>,
+  /// The instance which forwarded the report, see `creator`
+  pub forwarded_by: Option<DbUrl>,
 }
 
 type CommentReportViewTuple = (
@@ -46,12 +50,13 @@ type CommentReportViewTuple = (
   Comment,
   Post,
   CommunitySafe,
-  PersonSafe,
+  Option<PersonSafe>,
   PersonSafeAlias1,
   CommentAggregates,
   Option<CommunityPersonBan>,
   Option<i16>,
   Option<PersonSafeAlias2>,
+  Option<DbUrl>,
 );
 
 impl CommentReportView {
@@ -74,12 +79,13 @@ impl CommentReportView {
       creator_banned_from_community,
       comment_like,
       resolver,
+      forwarded_by,
     ) = comment_report::table
       .find(report_id)
       .inner_join(comment::table)
       .inner_join(post::table.on(comment::post_id.eq(post::id)))
       .inner_join(community::table.on(post::community_id.eq(community::id)))
-      .inner_join(person::table.on(comment_report::creator_id.eq(person::id)))
+      .left_join(person::table.on(comment_report::creator_id.eq(person::id.nullable())))
       .inner_join(person_alias_1::table.on(comment::creator_id.eq(person_alias_1::id)))
       .inner_join(
         comment_aggregates::table.on(comment_report::comment_id.eq(comment_aggregates::comment_id)),
@@ -106,17 +112,19 @@ impl CommentReportView {
       .left_join(
         person_alias_2::table.on(comment_report::resolver_id.eq(person_alias_2::id.nullable())),
       )
+      .left_join(site::table.on(comment_report::forwarded_by_site_id.eq(site::id.nullable())))
       .select((
         comment_report::all_columns,
         comment::all_columns,
         post::all_columns,
         Community::safe_columns_tuple(),
-        Person::safe_columns_tuple(),
+        Person::safe_columns_tuple().nullable(),
         PersonAlias1::safe_columns_tuple(),
         comment_aggregates::all_columns,
         community_person_ban::all_columns.nullable(),
         comment_like::score.nullable(),
         PersonAlias2::safe_columns_tuple().nullable(),
+        site::actor_id.nullable(),
       ))
       .first::<CommentReportViewTuple>(conn)?;
 
@@ -133,6 +141,7 @@ impl CommentReportView {
       creator_banned_from_community: creator_banned_from_community.is_some(),
       my_vote,
       resolver,
+      forwarded_by,
     })
   }
 
@@ -221,7 +230,7 @@ impl<'a> CommentReportQueryBuilder<'a> {
       .inner_join(comment::table)
       .inner_join(post::table.on(comment::post_id.eq(post::id)))
       .inner_join(community::table.on(post::community_id.eq(community::id)))
-      .inner_join(person::table.on(comment_report::creator_id.eq(person::id)))
+      .left_join(person::table.on(comment_report::creator_id.eq(person::id.nullable())))
       .inner_join(person_alias_1::table.on(comment::creator_id.eq(person_alias_1::id)))
       .inner_join(
         comment_aggregates::table.on(comment_report::comment_id.eq(comment_aggregates::comment_id)),
@@ -248,17 +257,19 @@ impl<'a> CommentReportQueryBuilder<'a> {
       .left_join(
         person_alias_2::table.on(comment_report::resolver_id.eq(person_alias_2::id.nullable())),
       )
+      .left_join(site::table.on(comment_report::forwarded_by_site_id.eq(site::id.nullable())))
       .select((
         comment_report::all_columns,
         comment::all_columns,
         post::all_columns,
         Community::safe_columns_tuple(),
-        Person::safe_columns_tuple(),
+        Person::safe_columns_tuple().nullable(),
         PersonAlias1::safe_columns_tuple(),
         comment_aggregates::all_columns,
         community_person_ban::all_columns.nullable(),
         comment_like::score.nullable(),
         PersonAlias2::safe_columns_tuple().nullable(),
+        site::actor_id.nullable(),
       ))
       .into_boxed();
 
@@ -312,6 +323,7 @@ impl ViewToVec for CommentReportView {
         creator_banned_from_community: a.7.is_some(),
         my_vote: a.8,
         resolver: a.9.to_owned(),
+        forwarded_by: a.10.to_owned(),
       })
       .collect::<Vec<Self>>()
   }
@@ -391,20 +403,22 @@ mod tests {
 
     // sara reports
     let sara_report_form = CommentReportForm {
-      creator_id: inserted_sara.id,
+      creator_id: Some(inserted_sara.id),
       comment_id: inserted_comment.id,
       original_comment_text: "this was it at time of creation".into(),
       reason: "from sara".into(),
+      forwarded_by_site_id: None,
     };
 
     let inserted_sara_report = CommentReport::report(&conn, &sara_report_form).unwrap();
 
     // jessica reports
     let jessica_report_form = CommentReportForm {
-      creator_id: inserted_jessica.id,
+      creator_id: Some(inserted_jessica.id),
       comment_id: inserted_comment.id,
       original_comment_text: "this was it at time of creation".into(),
       reason: "from jessica".into(),
+      forwarded_by_site_id: None,
     };
 
     let inserted_jessica_report = CommentReport::report(&conn, &jessica_report_form).unwrap();
@@ -431,9 +445,11 @@ mod tests {
         updated: None,
         banner: None,
         hidden: false,
+        moved_to: None,
+        also_known_as: vec![],
         published: inserted_community.published,
       },
-      creator: PersonSafe {
+      creator: Some(PersonSafe {
         id: inserted_jessica.id,
         name: inserted_jessica.name,
         display_name: None,
@@ -452,7 +468,9 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
-      },
+        moved_to: None,
+        also_known_as: vec![],
+      }),
       comment_creator: PersonSafeAlias1 {
         id: inserted_timmy.id,
         name: inserted_timmy.name.to_owned(),
@@ -472,6 +490,8 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        moved_to: None,
+        also_known_as: vec![],
       },
       creator_banned_from_community: false,
       counts: CommentAggregates {
@@ -481,16 +501,20 @@ mod tests {
         upvotes: 0,
         downvotes: 0,
         published: agg.published,
+        child_count: 0,
+        controversy_rank: 0.0,
+        rising_rank: 0.0,
       },
       my_vote: None,
       resolver: None,
+      forwarded_by: None,
     };
 
     assert_eq!(read_jessica_report_view, expected_jessica_report_view);
 
     let mut expected_sara_report_view = expected_jessica_report_view.clone();
     expected_sara_report_view.comment_report = inserted_sara_report;
-    expected_sara_report_view.creator = PersonSafe {
+    expected_sara_report_view.creator = Some(PersonSafe {
       id: inserted_sara.id,
       name: inserted_sara.name,
       display_name: None,
@@ -509,7 +533,9 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
-    };
+      moved_to: None,
+      also_known_as: vec![],
+    });
 
     // Do a batch read of timmys reports
     let reports = CommentReportQueryBuilder::create(&conn, inserted_timmy.id, false)
@@ -565,6 +591,8 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      moved_to: None,
+      also_known_as: vec![],
     });
 
     assert_eq!(
@@ -1,8 +1,15 @@
-use diesel::{dsl::*, result::Error, *};
+use crate::reaction_count::{comment_reaction_counts, ReactionCount};
+use diesel::{dsl::*, result::Error, sql_types::Bool, *};
 use lemmy_db_schema::{
   aggregates::comment_aggregates::CommentAggregates,
-  functions::hot_rank,
-  fuzzy_search,
+  full_text_search::{
+    non_blank_search_term,
+    text_search_query,
+    ts_rank_cd,
+    TsVector,
+    TsVectorExtensions,
+  },
+  functions::{array_length, hot_rank},
   limit_and_offset,
   newtypes::{CommentId, CommunityId, DbUrl, PersonId, PostId},
   schema::{
@@ -27,11 +34,20 @@ use lemmy_db_schema::{
     person_block::PersonBlock,
     post::Post,
   },
-  traits::{MaybeOptional, ToSafe, ViewToVec},
+  traits::{Crud, MaybeOptional, ToSafe, ViewToVec},
+  CommentSortType,
   ListingType,
   SortType,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximum number of replies loaded below each comment on the first level of a comment tree. If a
+/// thread has more, clients can tell from `child_count` and load the rest with `parent_id`.
+const TREE_REPLIES_LIMIT: i64 = 300;
+/// Maximum number of replies loaded for all threads of a comment tree together. It is split evenly
+/// between the threads.
+const TREE_TOTAL_REPLIES_LIMIT: i64 = 1000;
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct CommentView {
@@ -46,6 +62,7 @@ pub struct CommentView {
   pub saved: bool,                         // Left join to CommentSaved
   pub creator_blocked: bool,               // Left join to PersonBlock
   pub my_vote: Option<i16>,                // Left join to CommentLike
+  pub reactions: Vec<ReactionCount>,
 }
 
 type CommentViewTuple = (
@@ -157,6 +174,9 @@ impl CommentView {
     } else {
       comment_like
     };
+    let reactions = comment_reaction_counts(conn, vec![comment.id], my_person_id)?
+      .remove(&comment.id)
+      .unwrap_or_default();
 
     Ok(CommentView {
       comment,
@@ -170,6 +190,7 @@ impl CommentView {
       saved: saved.is_some(),
       creator_blocked: creator_blocked.is_some(),
       my_vote,
+      reactions,
     })
   }
 
@@ -223,10 +244,12 @@ impl CommentView {
   }
 }
 
+#[derive(Clone)]
 pub struct CommentQueryBuilder<'a> {
   conn: &'a PgConnection,
   listing_type: Option<ListingType>,
   sort: Option<SortType>,
+  comment_sort: Option<CommentSortType>,
   community_id: Option<CommunityId>,
   community_actor_id: Option<DbUrl>,
   post_id: Option<PostId>,
@@ -234,11 +257,29 @@ pub struct CommentQueryBuilder<'a> {
   recipient_id: Option<PersonId>,
   my_person_id: Option<PersonId>,
   search_term: Option<String>,
+  search_language: Option<String>,
   saved_only: Option<bool>,
   unread_only: Option<bool>,
   show_bot_accounts: Option<bool>,
+  discussion_languages: Option<Vec<String>>,
+  parent_id: Option<CommentId>,
+  max_depth: Option<i32>,
+  children_limit: Option<i64>,
   page: Option<i64>,
   limit: Option<i64>,
+  comment_ids: Option<Vec<CommentId>>,
+  depth_range: Option<(i32, i32)>,
+  reply_threads: Option<ReplyThreads>,
+}
+
+/// The threads of a comment tree whose replies are loaded together, see `list_tree()`
+#[derive(Clone)]
+struct ReplyThreads {
+  /// Position of the top level comments in the path of their replies
+  depth: i32,
+  top_level_ids: Vec<CommentId>,
+  /// Number of replies loaded for each thread
+  limit: i64,
 }
 
 impl<'a> CommentQueryBuilder<'a> {
@@ -247,6 +288,7 @@ impl<'a> CommentQueryBuilder<'a> {
       conn,
       listing_type: None,
       sort: None,
+      comment_sort: None,
       community_id: None,
       community_actor_id: None,
       post_id: None,
@@ -254,11 +296,19 @@ impl<'a> CommentQueryBuilder<'a> {
       recipient_id: None,
       my_person_id: None,
       search_term: None,
+      search_language: None,
       saved_only: None,
       unread_only: None,
       show_bot_accounts: None,
+      discussion_languages: None,
+      parent_id: None,
+      max_depth: None,
+      children_limit: None,
       page: None,
       limit: None,
+      comment_ids: None,
+      depth_range: None,
+      reply_threads: None,
     }
   }
 
@@ -272,6 +322,12 @@ impl<'a> CommentQueryBuilder<'a> {
     self
   }
 
+  /// Sort for comment trees, takes precedence over `sort`
+  pub fn comment_sort<T: MaybeOptional<CommentSortType>>(mut self, comment_sort: T) -> Self {
+    self.comment_sort = comment_sort.get_optional();
+    self
+  }
+
   pub fn post_id<T: MaybeOptional<PostId>>(mut self, post_id: T) -> Self {
     self.post_id = post_id.get_optional();
     self
@@ -303,7 +359,13 @@ impl<'a> CommentQueryBuilder<'a> {
   }
 
   pub fn search_term<T: MaybeOptional<String>>(mut self, search_term: T) -> Self {
-    self.search_term = search_term.get_optional();
+    self.search_term = non_blank_search_term(search_term.get_optional());
+    self
+  }
+
+  /// Language of the search terms, so that other forms of the words are found too
+  pub fn search_language<T: MaybeOptional<String>>(mut self, search_language: T) -> Self {
+    self.search_language = search_language.get_optional();
     self
   }
 
@@ -322,6 +384,40 @@ impl<'a> CommentQueryBuilder<'a> {
     self
   }
 
+  pub fn discussion_languages<T: MaybeOptional<Vec<String>>>(
+    mut self,
+    discussion_languages: T,
+  ) -> Self {
+    self.discussion_languages = discussion_languages.get_optional();
+    self
+  }
+
+  /// Only list the replies below this comment, at any depth
+  pub fn parent_id<T: MaybeOptional<CommentId>>(mut self, parent_id: T) -> Self {
+    self.parent_id = parent_id.get_optional();
+    self
+  }
+
+  /// Fetch a comment tree with this many levels, counted from the top level comments or the
+  /// replies to `parent_id`. Only the first level is paginated.
+  pub fn max_depth<T: MaybeOptional<i32>>(mut self, max_depth: T) -> Self {
+    self.max_depth = max_depth.get_optional();
+    self
+  }
+
+  /// The maximum number of replies listed below each comment of a tree
+  pub fn children_limit<T: MaybeOptional<i64>>(mut self, children_limit: T) -> Self {
+    self.children_limit = children_limit.get_optional();
+    self
+  }
+
+  /// Only list these comments, ordered from the top level down. Used to load all the parents of a
+  /// comment with one query.
+  pub fn comment_ids<T: MaybeOptional<Vec<CommentId>>>(mut self, comment_ids: T) -> Self {
+    self.comment_ids = comment_ids.get_optional();
+    self
+  }
+
   pub fn page<T: MaybeOptional<i64>>(mut self, page: T) -> Self {
     self.page = page.get_optional();
     self
@@ -333,6 +429,91 @@ impl<'a> CommentQueryBuilder<'a> {
   }
 
   pub fn list(self) -> Result<Vec<CommentView>, Error> {
+    match self.max_depth {
+      Some(max_depth) => self.list_tree(max_depth),
+      None => self.list_flat(),
+    }
+  }
+
+  /// Lists the comment tree in depth first order, with the replies of each comment right after
+  /// it. Replies beyond the depth or children limit can be loaded later with `parent_id`, their
+  /// number is in the `child_count` of the parent.
+  fn list_tree(self, max_depth: i32) -> Result<Vec<CommentView>, Error> {
+    let base_depth = match self.parent_id {
+      Some(parent_id) => Comment::read(self.conn, parent_id)?.path.len() as i32,
+      None => 0,
+    };
+
+    let top_level = CommentQueryBuilder {
+      max_depth: None,
+      depth_range: Some((base_depth + 1, base_depth + 1)),
+      ..self.clone()
+    }
+    .list_flat()?;
+    if max_depth <= 1 || top_level.is_empty() {
+      return Ok(top_level);
+    }
+
+    // The replies of all threads are loaded with one query, but limited for each thread
+    // separately, so that a busy thread can't take up the limit for all the others. They are
+    // grouped by parent, and stay in the requested order.
+    let thread_limit = (TREE_TOTAL_REPLIES_LIMIT / top_level.len() as i64).min(TREE_REPLIES_LIMIT);
+    let replies = CommentQueryBuilder {
+      max_depth: None,
+      depth_range: Some((base_depth + 2, base_depth + max_depth)),
+      reply_threads: Some(ReplyThreads {
+        depth: base_depth + 1,
+        top_level_ids: top_level.iter().map(|c| c.comment.id).collect(),
+        limit: thread_limit,
+      }),
+      page: None,
+      limit: None,
+      ..self.clone()
+    }
+    .list_flat()?;
+    let mut children: HashMap<CommentId, Vec<CommentView>> = HashMap::new();
+    for reply in replies {
+      if let Some(parent_id) = reply.comment.parent_id {
+        children.entry(parent_id).or_default().push(reply);
+      }
+    }
+    let children_limit = self
+      .children_limit
+      .map_or(usize::MAX, |l| l.max(0) as usize);
+
+    let mut comments = Vec::new();
+    let mut stack: Vec<CommentView> = top_level.into_iter().rev().collect();
+    while let Some(comment) = stack.pop() {
+      if let Some(mut replies) = children.remove(&comment.comment.id) {
+        replies.truncate(children_limit);
+        stack.extend(replies.into_iter().rev());
+      }
+      comments.push(comment);
+    }
+    Ok(comments)
+  }
+
+  /// Order in which the replies of each thread are ranked for the thread limit. Replies closer to
+  /// the top level come first, so that their parents are less likely to be cut off. The time
+  /// filters of `sort` are applied afterwards, so they may leave fewer replies.
+  fn thread_rank_order(&self) -> &'static str {
+    match (self.comment_sort, self.sort.unwrap_or(SortType::New)) {
+      (Some(CommentSortType::Hot), _) => "hot_rank(ca.score, ca.published) desc",
+      (Some(CommentSortType::Top), _) => "ca.score desc",
+      (Some(CommentSortType::New), _) => "c.published desc",
+      (Some(CommentSortType::Old), _) => "c.published asc",
+      (Some(CommentSortType::Controversial), _) => "ca.controversy_rank desc",
+      (None, SortType::Hot | SortType::Active | SortType::Relevance) => {
+        "hot_rank(ca.score, ca.published) desc"
+      }
+      (None, SortType::New | SortType::MostComments | SortType::NewComments) => "c.published desc",
+      (None, SortType::Controversial) => "ca.controversy_rank desc",
+      (None, SortType::Rising) => "ca.rising_rank desc",
+      (None, _) => "ca.score desc",
+    }
+  }
+
+  fn list_flat(self) -> Result<Vec<CommentView>, Error> {
     use diesel::dsl::*;
 
     // The left join below will return None in this case
@@ -443,8 +624,35 @@ impl<'a> CommentQueryBuilder<'a> {
       query = query.filter(comment::post_id.eq(post_id));
     };
 
-    if let Some(search_term) = self.search_term {
-      query = query.filter(comment::content.ilike(fuzzy_search(&search_term)));
+    if let Some(parent_id) = self.parent_id {
+      query = query
+        .filter(comment::path.contains(vec![parent_id.0]))
+        .filter(comment::id.ne(parent_id));
+    }
+
+    if let Some(comment_ids) = &self.comment_ids {
+      query = query.filter(comment::id.eq_any(comment_ids.to_owned()));
+    }
+
+    if let Some((min_depth, max_depth)) = self.depth_range {
+      query = query.filter(array_length(comment::path, 1).between(min_depth, max_depth));
+    }
+
+    if let Some(threads) = &self.reply_threads {
+      query = query.filter(sql::<Bool>(&thread_rank_filter(
+        threads,
+        self.depth_range.unwrap_or((threads.depth + 1, i32::MAX)),
+        self.thread_rank_order(),
+      )));
+    }
+
+    if let Some(search_term) = &self.search_term {
+      query = query.filter(
+        sql::<TsVector>("comment.search_vector").matches(text_search_query(
+          search_term.to_owned(),
+          self.search_language.to_owned(),
+        )),
+      );
     };
 
     if let Some(listing_type) = self.listing_type {
@@ -478,26 +686,81 @@ impl<'a> CommentQueryBuilder<'a> {
       query = query.filter(person::bot_account.eq(false));
     };
 
-    query = match self.sort.unwrap_or(SortType::New) {
-      SortType::Hot | SortType::Active => query
-        .order_by(hot_rank(comment_aggregates::score, comment_aggregates::published).desc())
-        .then_order_by(comment_aggregates::published.desc()),
-      SortType::New | SortType::MostComments | SortType::NewComments => {
-        query.order_by(comment::published.desc())
+    // An empty list means all languages. Comments without language are always shown.
+    if let Some(languages) = self.discussion_languages.filter(|l| !l.is_empty()) {
+      query = query.filter(
+        comment::language
+          .eq_any(languages)
+          .or(comment::language.is_null()),
+      );
+    }
+
+    query = if self.comment_ids.is_some() {
+      query.order_by(array_length(comment::path, 1).asc())
+    } else if let Some(comment_sort) = self.comment_sort {
+      match comment_sort {
+        CommentSortType::Hot => query
+          .order_by(hot_rank(comment_aggregates::score, comment_aggregates::published).desc())
+          .then_order_by(comment_aggregates::published.desc()),
+        CommentSortType::Top => query
+          .order_by(comment_aggregates::score.desc())
+          .then_order_by(comment_aggregates::published.desc()),
+        CommentSortType::New => query.order_by(comment::published.desc()),
+        CommentSortType::Old => query.order_by(comment::published.asc()),
+        CommentSortType::Controversial => query
+          .order_by(comment_aggregates::controversy_rank.desc())
+          .then_order_by(comment_aggregates::published.desc()),
+      }
+    } else {
+      match self.sort.unwrap_or(SortType::New) {
+        SortType::Relevance if self.search_term.is_some() => query
+          .order_by(
+            ts_rank_cd(
+              sql::<TsVector>("comment.search_vector"),
+              text_search_query(
+                self.search_term.to_owned().unwrap_or_default(),
+                self.search_language.to_owned(),
+              ),
+            )
+            .desc(),
+          )
+          .then_order_by(comment_aggregates::published.desc()),
+        SortType::Hot | SortType::Active | SortType::Relevance => query
+          .order_by(hot_rank(comment_aggregates::score, comment_aggregates::published).desc())
+          .then_order_by(comment_aggregates::published.desc()),
+        SortType::New | SortType::MostComments | SortType::NewComments => {
+          query.order_by(comment::published.desc())
+        }
+        SortType::TopAll => query.order_by(comment_aggregates::score.desc()),
+        SortType::TopYear => query
+          .filter(comment::published.gt(now - 1.years()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::TopMonth => query
+          .filter(comment::published.gt(now - 1.months()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::TopWeek => query
+          .filter(comment::published.gt(now - 1.weeks()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::TopDay => query
+          .filter(comment::published.gt(now - 1.days()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::TopTwelveHour => query
+          .filter(comment_aggregates::published.gt(now - 12.hours()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::TopSixHour => query
+          .filter(comment_aggregates::published.gt(now - 6.hours()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::TopHour => query
+          .filter(comment_aggregates::published.gt(now - 1.hours()))
+          .order_by(comment_aggregates::score.desc()),
+        SortType::Controversial => query
+          .order_by(comment_aggregates::controversy_rank.desc())
+          .then_order_by(comment_aggregates::published.desc()),
+        SortType::Rising => query
+          .filter(comment_aggregates::published.gt(now - 1.days()))
+          .order_by(comment_aggregates::rising_rank.desc())
+          .then_order_by(comment_aggregates::published.desc()),
       }
-      SortType::TopAll => query.order_by(comment_aggregates::score.desc()),
-      SortType::TopYear => query
-        .filter(comment::published.gt(now - 1.years()))
-        .order_by(comment_aggregates::score.desc()),
-      SortType::TopMonth => query
-        .filter(comment::published.gt(now - 1.months()))
-        .order_by(comment_aggregates::score.desc()),
-      SortType::TopWeek => query
-        .filter(comment::published.gt(now - 1.weeks()))
-        .order_by(comment_aggregates::score.desc()),
-      SortType::TopDay => query
-        .filter(comment::published.gt(now - 1.days()))
-        .order_by(comment_aggregates::score.desc()),
     };
 
     // Don't show blocked communities or persons
@@ -506,7 +769,13 @@ impl<'a> CommentQueryBuilder<'a> {
       query = query.filter(person_block::person_id.is_null());
     }
 
-    let (limit, offset) = limit_and_offset(self.page, self.limit);
+    let (limit, offset) = if self.reply_threads.is_some() {
+      (TREE_TOTAL_REPLIES_LIMIT, 0)
+    } else if let Some(comment_ids) = &self.comment_ids {
+      (comment_ids.len() as i64, 0)
+    } else {
+      limit_and_offset(self.page, self.limit)
+    };
 
     // Note: deleted and removed comments are done on the front side
     let res = query
@@ -514,10 +783,43 @@ impl<'a> CommentQueryBuilder<'a> {
       .offset(offset)
       .load::<CommentViewTuple>(self.conn)?;
 
-    Ok(CommentView::from_tuple_to_vec(res))
+    let mut comments = CommentView::from_tuple_to_vec(res);
+    let comment_ids = comments.iter().map(|c| c.comment.id).collect();
+    let mut reactions = comment_reaction_counts(self.conn, comment_ids, self.my_person_id)?;
+    for comment in comments.iter_mut() {
+      comment.reactions = reactions.remove(&comment.comment.id).unwrap_or_default();
+    }
+    Ok(comments)
   }
 }
 
+/// Keeps the first `threads.limit` replies of each thread. Diesel has no window functions, so this
+/// is a subquery in plain sql. It only contains numbers, so there is nothing to escape.
+fn thread_rank_filter(threads: &ReplyThreads, depth_range: (i32, i32), order: &str) -> String {
+  let top_level_ids = threads
+    .top_level_ids
+    .iter()
+    .map(|id| id.0.to_string())
+    .collect::<Vec<_>>()
+    .join(",");
+  format!(
+    "comment.id in (select ranked.id from (\
+      select c.id, row_number() over (\
+        partition by c.path[{depth}] order by array_length(c.path, 1), {order}\
+      ) as thread_rank \
+      from comment c join comment_aggregates ca on ca.comment_id = c.id \
+      where c.path[{depth}] in ({top_level_ids}) \
+      and array_length(c.path, 1) between {min_depth} and {max_depth}\
+    ) ranked where ranked.thread_rank <= {limit})",
+    depth = threads.depth,
+    order = order,
+    top_level_ids = top_level_ids,
+    min_depth = depth_range.0,
+    max_depth = depth_range.1,
+    limit = threads.limit,
+  )
+}
+
 impl ViewToVec for CommentView {
   type DbTuple = CommentViewTuple;
   fn from_tuple_to_vec(items: Vec<Self::DbTuple>) -> Vec<Self> {
@@ -535,6 +837,7 @@ impl ViewToVec for CommentView {
         saved: a.9.is_some(),
         creator_blocked: a.10.is_some(),
         my_vote: a.11,
+        reactions: Vec::new(),
       })
       .collect::<Vec<Self>>()
   }
@@ -548,6 +851,8 @@ mod tests {
     establish_unpooled_connection,
     source::{comment::*, community::*, person::*, person_block::PersonBlockForm, post::*},
     traits::{Blockable, Crud, Likeable},
+    CommentSortType,
+    SortType,
   };
   use serial_test::serial;
 
@@ -636,6 +941,7 @@ mod tests {
     let expected_comment_view_no_person = CommentView {
       creator_banned_from_community: false,
       my_vote: None,
+      reactions: vec![],
       subscribed: false,
       saved: false,
       creator_blocked: false,
@@ -652,6 +958,9 @@ mod tests {
         ap_id: inserted_comment.ap_id,
         updated: None,
         local: true,
+        content_warning: None,
+        language: None,
+        path: vec![inserted_comment.id.0],
       },
       creator: PersonSafe {
         id: inserted_person.id,
@@ -672,6 +981,8 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        moved_to: None,
+        also_known_as: vec![],
       },
       recipient: None,
       post: Post {
@@ -694,6 +1005,9 @@ mod tests {
         thumbnail_url: None,
         ap_id: inserted_post.ap_id.to_owned(),
         local: true,
+        content_warning: None,
+        language: None,
+        scheduled_publish_time: None,
       },
       community: CommunitySafe {
         id: inserted_community.id,
@@ -709,6 +1023,8 @@ mod tests {
         updated: None,
         banner: None,
         hidden: false,
+        moved_to: None,
+        also_known_as: vec![],
         published: inserted_community.published,
       },
       counts: CommentAggregates {
@@ -718,6 +1034,9 @@ mod tests {
         upvotes: 1,
         downvotes: 0,
         published: agg.published,
+        child_count: 1,
+        controversy_rank: 0.0,
+        rising_rank: agg.rising_rank,
       },
     };
 
@@ -738,6 +1057,50 @@ mod tests {
     let read_comment_from_blocked_person =
       CommentView::read(&conn, inserted_comment_2.id, Some(inserted_person.id)).unwrap();
 
+    let read_comment_tree_top_level = CommentQueryBuilder::create(&conn)
+      .post_id(inserted_post.id)
+      .comment_sort(CommentSortType::Hot)
+      .max_depth(1)
+      .list()
+      .unwrap();
+
+    let read_comment_tree = CommentQueryBuilder::create(&conn)
+      .post_id(inserted_post.id)
+      .comment_sort(CommentSortType::Old)
+      .max_depth(2)
+      .list()
+      .unwrap();
+
+    let read_comment_tree_children = CommentQueryBuilder::create(&conn)
+      .parent_id(inserted_comment.id)
+      .max_depth(1)
+      .list()
+      .unwrap();
+
+    let read_comment_tree_no_children = CommentQueryBuilder::create(&conn)
+      .post_id(inserted_post.id)
+      .max_depth(2)
+      .children_limit(0)
+      .list()
+      .unwrap();
+
+    let read_comment_search = CommentQueryBuilder::create(&conn)
+      .post_id(inserted_post.id)
+      .sort(SortType::Relevance)
+      .search_term("Blocked".to_string())
+      .list()
+      .unwrap();
+    let read_comment_search_no_match = CommentQueryBuilder::create(&conn)
+      .post_id(inserted_post.id)
+      .search_term("unicorn".to_string())
+      .list()
+      .unwrap();
+    let read_comment_search_blank = CommentQueryBuilder::create(&conn)
+      .post_id(inserted_post.id)
+      .search_term(" ".to_string())
+      .list()
+      .unwrap();
+
     let like_removed = CommentLike::remove(&conn, inserted_person.id, inserted_comment.id).unwrap();
     let num_deleted = Comment::delete(&conn, inserted_comment.id).unwrap();
     Comment::delete(&conn, inserted_comment_2.id).unwrap();
@@ -761,7 +1124,137 @@ mod tests {
     // Make sure block set the creator blocked
     assert!(read_comment_from_blocked_person.creator_blocked);
 
+    // Comment trees, the reply comes right after its parent
+    assert_eq!(1, read_comment_tree_top_level.len());
+    assert_eq!(
+      inserted_comment.id,
+      read_comment_tree_top_level[0].comment.id
+    );
+    assert_eq!(
+      vec![inserted_comment.id, inserted_comment_2.id],
+      read_comment_tree
+        .iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(1, read_comment_tree_children.len());
+    assert_eq!(
+      inserted_comment_2.id,
+      read_comment_tree_children[0].comment.id
+    );
+    assert_eq!(1, read_comment_tree_no_children.len());
+
+    // The search ignores case, and a blank search finds all comments
+    assert_eq!(
+      vec![inserted_comment_2.id],
+      read_comment_search
+        .iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<_>>()
+    );
+    assert!(read_comment_search_no_match.is_empty());
+    assert_eq!(2, read_comment_search_blank.len());
+
     assert_eq!(1, num_deleted);
     assert_eq!(1, like_removed);
   }
+
+  #[test]
+  #[serial]
+  fn test_comment_context() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "context_person".into(),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "context_community".to_string(),
+      title: "nada".to_owned(),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let new_post = PostForm {
+      name: "A context post".into(),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      ..PostForm::default()
+    };
+    let inserted_post = Post::create(&conn, &new_post).unwrap();
+
+    // A thread four levels deep, with a second reply to the top level comment
+    let mut thread: Vec<Comment> = Vec::new();
+    for i in 0..4 {
+      let comment_form = CommentForm {
+        content: format!("context comment {}", i),
+        creator_id: inserted_person.id,
+        post_id: inserted_post.id,
+        parent_id: thread.last().map(|c| c.id),
+        ..CommentForm::default()
+      };
+      thread.push(Comment::create(&conn, &comment_form).unwrap());
+    }
+    let sibling_form = CommentForm {
+      content: "context sibling".into(),
+      creator_id: inserted_person.id,
+      post_id: inserted_post.id,
+      parent_id: Some(thread[0].id),
+      ..CommentForm::default()
+    };
+    let sibling = Comment::create(&conn, &sibling_form).unwrap();
+
+    let ids = |comments: &[Comment]| comments.iter().map(|c| c.id).collect::<Vec<_>>();
+    let list_ancestors = |comment: &Comment, levels: usize| {
+      CommentQueryBuilder::create(&conn)
+        .comment_ids(comment.ancestor_ids(levels))
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<_>>()
+    };
+
+    let root_ancestors = list_ancestors(&thread[0], 3);
+    let all_ancestors = list_ancestors(&thread[3], 10);
+    let two_ancestors = list_ancestors(&thread[3], 2);
+    let no_ancestors = thread[3].ancestor_ids(0);
+
+    let subtree = CommentQueryBuilder::create(&conn)
+      .parent_id(thread[0].id)
+      .comment_sort(CommentSortType::Old)
+      .max_depth(3)
+      .list()
+      .unwrap();
+    let shallow_subtree = CommentQueryBuilder::create(&conn)
+      .parent_id(thread[0].id)
+      .comment_sort(CommentSortType::Old)
+      .max_depth(1)
+      .list()
+      .unwrap();
+
+    Post::delete(&conn, inserted_post.id).unwrap();
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+
+    assert!(root_ancestors.is_empty());
+    assert_eq!(ids(&thread[..3]), all_ancestors);
+    assert_eq!(ids(&thread[1..3]), two_ancestors);
+    assert!(no_ancestors.is_empty());
+
+    // Depth first, the deeper replies come before the second reply to the top level comment
+    assert_eq!(
+      vec![thread[1].id, thread[2].id, thread[3].id, sibling.id],
+      subtree.iter().map(|c| c.comment.id).collect::<Vec<_>>()
+    );
+    assert_eq!(
+      vec![thread[1].id, sibling.id],
+      shallow_subtree
+        .iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<_>>()
+    );
+  }
 }
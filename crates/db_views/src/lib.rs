@@ -4,8 +4,11 @@ extern crate serial_test;
 pub mod comment_report_view;
 pub mod comment_view;
 pub mod local_user_view;
+pub mod poll_view;
 pub mod post_report_view;
 pub mod post_view;
 pub mod private_message_view;
+pub mod reaction_count;
 pub mod registration_application_view;
+pub mod search_snippet;
 pub mod site_view;
@@ -0,0 +1,70 @@
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  naive_now,
+  newtypes::{PersonId, PollOptionId, PostId},
+  schema::{poll, poll_option, poll_vote},
+  source::poll::{Poll, PollOption},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct PollView {
+  pub multiple_choice: bool,
+  pub end_time: Option<chrono::NaiveDateTime>,
+  /// True once the end time has passed, then no more votes are accepted
+  pub closed: bool,
+  pub options: Vec<PollOption>,
+  /// The options which the person viewing the post voted for
+  pub my_votes: Vec<PollOptionId>,
+}
+
+/// Polls of those posts which have one.
+pub(crate) fn polls_for_posts(
+  conn: &PgConnection,
+  post_ids: Vec<PostId>,
+  my_person_id: Option<PersonId>,
+) -> Result<HashMap<PostId, PollView>, Error> {
+  let polls = poll::table
+    .filter(poll::post_id.eq_any(post_ids))
+    .load::<Poll>(conn)?;
+  if polls.is_empty() {
+    return Ok(HashMap::new());
+  }
+  let poll_ids: Vec<i32> = polls.iter().map(|p| p.id).collect();
+  let options = poll_option::table
+    .filter(poll_option::poll_id.eq_any(poll_ids.clone()))
+    .order_by(poll_option::id)
+    .load::<PollOption>(conn)?;
+  let my_votes = poll_vote::table
+    .inner_join(poll_option::table)
+    .filter(poll_option::poll_id.eq_any(poll_ids))
+    .filter(poll_vote::person_id.eq(my_person_id.unwrap_or(PersonId(-1))))
+    .select((poll_option::poll_id, poll_vote::poll_option_id))
+    .load::<(i32, PollOptionId)>(conn)?;
+
+  let now = naive_now();
+  Ok(
+    polls
+      .into_iter()
+      .map(|poll| {
+        let view = PollView {
+          multiple_choice: poll.multiple_choice,
+          end_time: poll.end_time,
+          closed: poll.end_time.map(|t| t < now).unwrap_or(false),
+          options: options
+            .iter()
+            .filter(|o| o.poll_id == poll.id)
+            .cloned()
+            .collect(),
+          my_votes: my_votes
+            .iter()
+            .filter(|(poll_id, _)| poll_id == &poll.id)
+            .map(|(_, option_id)| *option_id)
+            .collect(),
+        };
+        (poll.post_id, view)
+      })
+      .collect(),
+  )
+}
@@ -2,7 +2,7 @@ use diesel::{dsl::*, result::Error, *};
 use lemmy_db_schema::{
   aggregates::post_aggregates::PostAggregates,
   limit_and_offset,
-  newtypes::{CommunityId, PersonId, PostReportId},
+  newtypes::{CommunityId, DbUrl, PersonId, PostReportId},
   schema::{
     community,
     community_moderator,
@@ -14,6 +14,7 @@ use lemmy_db_schema::{
     post_aggregates,
     post_like,
     post_report,
+    site,
   },
   source::{
     community::{Community, CommunityPersonBan, CommunitySafe},
@@ -30,24 +31,28 @@ pub struct PostReportView {
   pub post_report: PostReport,
   pub post: Post,
   pub community: CommunitySafe,
-  pub creator: PersonSafe,
+  /// Empty for reports which were forwarded by another instance
+  pub creator: Option<PersonSafe>,
   pub post_creator: PersonSafeAlias1,
   pub creator_banned_from_community: bool,
   pub my_vote: Option<i16>,
   pub counts: PostAggregates,
   pub resolver: Option<PersonSafeAlias2>,
+  /// The instance which forwarded the report, see `creator`
+  pub forwarded_by: Option<DbUrl>,
 }
 
 type PostReportViewTuple = (
   PostReport,
   Post,
   CommunitySafe,
-  PersonSafe,
+  Option<PersonSafe>,
   PersonSafeAlias1,
   Option<CommunityPersonBan>,
   Option<i16>,
   PostAggregates,
   Option<PersonSafeAlias2>,
+  Option<DbUrl>,
 );
 
 impl PostReportView {
@@ -69,11 +74,12 @@ impl PostReportView {
       post_like,
       counts,
       resolver,
+      forwarded_by,
     ) = post_report::table
       .find(report_id)
       .inner_join(post::table)
       .inner_join(community::table.on(post::community_id.eq(community::id)))
-      .inner_join(person::table.on(post_report::creator_id.eq(person::id)))
+      .left_join(person::table.on(post_report::creator_id.eq(person::id.nullable())))
       .inner_join(person_alias_1::table.on(post::creator_id.eq(person_alias_1::id)))
       .left_join(
         community_person_ban::table.on(
@@ -98,16 +104,18 @@ impl PostReportView {
       .left_join(
         person_alias_2::table.on(post_report::resolver_id.eq(person_alias_2::id.nullable())),
       )
+      .left_join(site::table.on(post_report::forwarded_by_site_id.eq(site::id.nullable())))
       .select((
         post_report::all_columns,
         post::all_columns,
         Community::safe_columns_tuple(),
-        Person::safe_columns_tuple(),
+        Person::safe_columns_tuple().nullable(),
         PersonAlias1::safe_columns_tuple(),
         community_person_ban::all_columns.nullable(),
         post_like::score.nullable(),
         post_aggregates::all_columns,
         PersonAlias2::safe_columns_tuple().nullable(),
+        site::actor_id.nullable(),
       ))
       .first::<PostReportViewTuple>(conn)?;
 
@@ -123,6 +131,7 @@ impl PostReportView {
       my_vote,
       counts,
       resolver,
+      forwarded_by,
     })
   }
 
@@ -208,7 +217,7 @@ impl<'a> PostReportQueryBuilder<'a> {
     let mut query = post_report::table
       .inner_join(post::table)
       .inner_join(community::table.on(post::community_id.eq(community::id)))
-      .inner_join(person::table.on(post_report::creator_id.eq(person::id)))
+      .left_join(person::table.on(post_report::creator_id.eq(person::id.nullable())))
       .inner_join(person_alias_1::table.on(post::creator_id.eq(person_alias_1::id)))
       .left_join(
         community_person_ban::table.on(
@@ -233,16 +242,18 @@ impl<'a> PostReportQueryBuilder<'a> {
       .left_join(
         person_alias_2::table.on(post_report::resolver_id.eq(person_alias_2::id.nullable())),
       )
+      .left_join(site::table.on(post_report::forwarded_by_site_id.eq(site::id.nullable())))
       .select((
         post_report::all_columns,
         post::all_columns,
         Community::safe_columns_tuple(),
-        Person::safe_columns_tuple(),
+        Person::safe_columns_tuple().nullable(),
         PersonAlias1::safe_columns_tuple(),
         community_person_ban::all_columns.nullable(),
         post_like::score.nullable(),
         post_aggregates::all_columns,
         PersonAlias2::safe_columns_tuple().nullable(),
+        site::actor_id.nullable(),
       ))
       .into_boxed();
 
@@ -295,6 +306,7 @@ impl ViewToVec for PostReportView {
         my_vote: a.6,
         counts: a.7.to_owned(),
         resolver: a.8.to_owned(),
+        forwarded_by: a.9.to_owned(),
       })
       .collect::<Vec<Self>>()
   }
@@ -370,24 +382,26 @@ mod tests {
 
     // sara reports
     let sara_report_form = PostReportForm {
-      creator_id: inserted_sara.id,
+      creator_id: Some(inserted_sara.id),
       post_id: inserted_post.id,
       original_post_name: "Orig post".into(),
       original_post_url: None,
       original_post_body: None,
       reason: "from sara".into(),
+      forwarded_by_site_id: None,
     };
 
     let inserted_sara_report = PostReport::report(&conn, &sara_report_form).unwrap();
 
     // jessica reports
     let jessica_report_form = PostReportForm {
-      creator_id: inserted_jessica.id,
+      creator_id: Some(inserted_jessica.id),
       post_id: inserted_post.id,
       original_post_name: "Orig post".into(),
       original_post_url: None,
       original_post_body: None,
       reason: "from jessica".into(),
+      forwarded_by_site_id: None,
     };
 
     let inserted_jessica_report = PostReport::report(&conn, &jessica_report_form).unwrap();
@@ -413,9 +427,11 @@ mod tests {
         updated: None,
         banner: None,
         hidden: false,
+        moved_to: None,
+        also_known_as: vec![],
         published: inserted_community.published,
       },
-      creator: PersonSafe {
+      creator: Some(PersonSafe {
         id: inserted_jessica.id,
         name: inserted_jessica.name,
         display_name: None,
@@ -434,7 +450,9 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
-      },
+        moved_to: None,
+        also_known_as: vec![],
+      }),
       post_creator: PersonSafeAlias1 {
         id: inserted_timmy.id,
         name: inserted_timmy.name.to_owned(),
@@ -454,6 +472,8 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        moved_to: None,
+        also_known_as: vec![],
       },
       creator_banned_from_community: false,
       my_vote: None,
@@ -468,8 +488,11 @@ mod tests {
         published: agg.published,
         newest_comment_time_necro: inserted_post.published,
         newest_comment_time: inserted_post.published,
+        controversy_rank: 0.0,
+        rising_rank: 0.0,
       },
       resolver: None,
+      forwarded_by: None,
     };
 
     assert_eq!(read_jessica_report_view, expected_jessica_report_view);
@@ -477,7 +500,7 @@ mod tests {
     let mut expected_sara_report_view = expected_jessica_report_view.clone();
     expected_sara_report_view.post_report = inserted_sara_report;
     expected_sara_report_view.my_vote = None;
-    expected_sara_report_view.creator = PersonSafe {
+    expected_sara_report_view.creator = Some(PersonSafe {
       id: inserted_sara.id,
       name: inserted_sara.name,
       display_name: None,
@@ -496,7 +519,9 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
-    };
+      moved_to: None,
+      also_known_as: vec![],
+    });
 
     // Do a batch read of timmys reports
     let reports = PostReportQueryBuilder::create(&conn, inserted_timmy.id, false)
@@ -550,6 +575,8 @@ mod tests {
       shared_inbox_url: None,
       matrix_user_id: None,
       ban_expires: None,
+      moved_to: None,
+      also_known_as: vec![],
     });
 
     assert_eq!(
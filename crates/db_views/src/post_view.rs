@@ -1,8 +1,18 @@
+use crate::{
+  poll_view::{polls_for_posts, PollView},
+  reaction_count::{post_reaction_counts, ReactionCount},
+};
 use diesel::{dsl::*, pg::Pg, result::Error, *};
 use lemmy_db_schema::{
   aggregates::post_aggregates::PostAggregates,
+  full_text_search::{
+    non_blank_search_term,
+    text_search_query,
+    ts_rank_cd,
+    TsVector,
+    TsVectorExtensions,
+  },
   functions::hot_rank,
-  fuzzy_search,
   limit_and_offset,
   newtypes::{CommunityId, DbUrl, PersonId, PostId},
   schema::{
@@ -43,6 +53,8 @@ pub struct PostView {
   pub read: bool,            // Left join to PostRead
   pub creator_blocked: bool, // Left join to PersonBlock
   pub my_vote: Option<i16>,  // Left join to PostLike
+  pub reactions: Vec<ReactionCount>,
+  pub poll: Option<PollView>,
 }
 
 type PostViewTuple = (
@@ -151,6 +163,10 @@ impl PostView {
     } else {
       post_like
     };
+    let reactions = post_reaction_counts(conn, vec![post.id], my_person_id)?
+      .remove(&post.id)
+      .unwrap_or_default();
+    let poll = polls_for_posts(conn, vec![post.id], my_person_id)?.remove(&post.id);
 
     Ok(PostView {
       post,
@@ -163,6 +179,8 @@ impl PostView {
       read: read.is_some(),
       creator_blocked: creator_blocked.is_some(),
       my_vote,
+      reactions,
+      poll,
     })
   }
 }
@@ -176,11 +194,14 @@ pub struct PostQueryBuilder<'a> {
   community_actor_id: Option<DbUrl>,
   my_person_id: Option<PersonId>,
   search_term: Option<String>,
+  search_language: Option<String>,
   url_search: Option<String>,
   show_nsfw: Option<bool>,
   show_bot_accounts: Option<bool>,
   show_read_posts: Option<bool>,
+  discussion_languages: Option<Vec<String>>,
   saved_only: Option<bool>,
+  scheduled_only: Option<bool>,
   page: Option<i64>,
   limit: Option<i64>,
 }
@@ -196,11 +217,14 @@ impl<'a> PostQueryBuilder<'a> {
       community_actor_id: None,
       my_person_id: None,
       search_term: None,
+      search_language: None,
       url_search: None,
       show_nsfw: None,
       show_bot_accounts: None,
       show_read_posts: None,
+      discussion_languages: None,
       saved_only: None,
+      scheduled_only: None,
       page: None,
       limit: None,
     }
@@ -237,7 +261,13 @@ impl<'a> PostQueryBuilder<'a> {
   }
 
   pub fn search_term<T: MaybeOptional<String>>(mut self, search_term: T) -> Self {
-    self.search_term = search_term.get_optional();
+    self.search_term = non_blank_search_term(search_term.get_optional());
+    self
+  }
+
+  /// Language of the search terms, so that other forms of the words are found too
+  pub fn search_language<T: MaybeOptional<String>>(mut self, search_language: T) -> Self {
+    self.search_language = search_language.get_optional();
     self
   }
 
@@ -261,11 +291,25 @@ impl<'a> PostQueryBuilder<'a> {
     self
   }
 
+  pub fn discussion_languages<T: MaybeOptional<Vec<String>>>(
+    mut self,
+    discussion_languages: T,
+  ) -> Self {
+    self.discussion_languages = discussion_languages.get_optional();
+    self
+  }
+
   pub fn saved_only<T: MaybeOptional<bool>>(mut self, saved_only: T) -> Self {
     self.saved_only = saved_only.get_optional();
     self
   }
 
+  /// Only list the scheduled posts, which are hidden otherwise
+  pub fn scheduled_only<T: MaybeOptional<bool>>(mut self, scheduled_only: T) -> Self {
+    self.scheduled_only = scheduled_only.get_optional();
+    self
+  }
+
   pub fn page<T: MaybeOptional<i64>>(mut self, page: T) -> Self {
     self.page = page.get_optional();
     self
@@ -393,12 +437,12 @@ impl<'a> PostQueryBuilder<'a> {
       query = query.filter(post::url.eq(url_search));
     }
 
-    if let Some(search_term) = self.search_term {
-      let searcher = fuzzy_search(&search_term);
+    if let Some(search_term) = &self.search_term {
       query = query.filter(
-        post::name
-          .ilike(searcher.to_owned())
-          .or(post::body.ilike(searcher)),
+        sql::<TsVector>("post.search_vector").matches(text_search_query(
+          search_term.to_owned(),
+          self.search_language.to_owned(),
+        )),
       );
     }
 
@@ -417,6 +461,15 @@ impl<'a> PostQueryBuilder<'a> {
       query = query.filter(person::bot_account.eq(false));
     };
 
+    // An empty list means all languages. Posts without language are always shown.
+    if let Some(languages) = self.discussion_languages.filter(|l| !l.is_empty()) {
+      query = query.filter(
+        post::language
+          .eq_any(languages)
+          .or(post::language.is_null()),
+      );
+    }
+
     if self.saved_only.unwrap_or(false) {
       query = query.filter(post_saved::id.is_not_null());
     }
@@ -426,6 +479,12 @@ impl<'a> PostQueryBuilder<'a> {
       query = query.filter(post_read::id.is_null());
     }
 
+    if self.scheduled_only.unwrap_or(false) {
+      query = query.filter(post::scheduled_publish_time.is_not_null());
+    } else {
+      query = query.filter(post::scheduled_publish_time.is_null());
+    }
+
     // Don't show blocked communities or persons
     if self.my_person_id.is_some() {
       query = query.filter(community_block::person_id.is_null());
@@ -433,6 +492,18 @@ impl<'a> PostQueryBuilder<'a> {
     }
 
     query = match self.sort.unwrap_or(SortType::Hot) {
+      SortType::Relevance if self.search_term.is_some() => query
+        .then_order_by(
+          ts_rank_cd(
+            sql::<TsVector>("post.search_vector"),
+            text_search_query(
+              self.search_term.unwrap_or_default(),
+              self.search_language.to_owned(),
+            ),
+          )
+          .desc(),
+        )
+        .then_order_by(post_aggregates::published.desc()),
       SortType::Active => query
         .then_order_by(
           hot_rank(
@@ -442,7 +513,7 @@ impl<'a> PostQueryBuilder<'a> {
           .desc(),
         )
         .then_order_by(post_aggregates::newest_comment_time_necro.desc()),
-      SortType::Hot => query
+      SortType::Hot | SortType::Relevance => query
         .then_order_by(hot_rank(post_aggregates::score, post_aggregates::published).desc())
         .then_order_by(post_aggregates::published.desc()),
       SortType::New => query.then_order_by(post_aggregates::published.desc()),
@@ -461,6 +532,22 @@ impl<'a> PostQueryBuilder<'a> {
       SortType::TopDay => query
         .filter(post::published.gt(now - 1.days()))
         .then_order_by(post_aggregates::score.desc()),
+      SortType::TopTwelveHour => query
+        .filter(post_aggregates::published.gt(now - 12.hours()))
+        .then_order_by(post_aggregates::score.desc()),
+      SortType::TopSixHour => query
+        .filter(post_aggregates::published.gt(now - 6.hours()))
+        .then_order_by(post_aggregates::score.desc()),
+      SortType::TopHour => query
+        .filter(post_aggregates::published.gt(now - 1.hours()))
+        .then_order_by(post_aggregates::score.desc()),
+      SortType::Controversial => query
+        .then_order_by(post_aggregates::controversy_rank.desc())
+        .then_order_by(post_aggregates::published.desc()),
+      SortType::Rising => query
+        .filter(post_aggregates::published.gt(now - 1.days()))
+        .then_order_by(post_aggregates::rising_rank.desc())
+        .then_order_by(post_aggregates::published.desc()),
     };
 
     let (limit, offset) = limit_and_offset(self.page, self.limit);
@@ -477,7 +564,15 @@ impl<'a> PostQueryBuilder<'a> {
 
     let res = query.load::<PostViewTuple>(self.conn)?;
 
-    Ok(PostView::from_tuple_to_vec(res))
+    let mut posts = PostView::from_tuple_to_vec(res);
+    let post_ids: Vec<PostId> = posts.iter().map(|p| p.post.id).collect();
+    let mut reactions = post_reaction_counts(self.conn, post_ids.clone(), self.my_person_id)?;
+    let mut polls = polls_for_posts(self.conn, post_ids, self.my_person_id)?;
+    for post in posts.iter_mut() {
+      post.reactions = reactions.remove(&post.post.id).unwrap_or_default();
+      post.poll = polls.remove(&post.post.id);
+    }
+    Ok(posts)
   }
 }
 
@@ -497,6 +592,8 @@ impl ViewToVec for PostView {
         read: a.7.is_some(),
         creator_blocked: a.8.is_some(),
         my_vote: a.9,
+        reactions: Vec::new(),
+        poll: None,
       })
       .collect::<Vec<Self>>()
   }
@@ -504,10 +601,14 @@ impl ViewToVec for PostView {
 
 #[cfg(test)]
 mod tests {
-  use crate::post_view::{PostQueryBuilder, PostView};
+  use crate::{
+    post_view::{PostQueryBuilder, PostView},
+    reaction_count::ReactionCount,
+  };
   use lemmy_db_schema::{
     aggregates::post_aggregates::PostAggregates,
     establish_unpooled_connection,
+    naive_now,
     source::{
       community::*,
       community_block::{CommunityBlock, CommunityBlockForm},
@@ -515,7 +616,7 @@ mod tests {
       person_block::{PersonBlock, PersonBlockForm},
       post::*,
     },
-    traits::{Blockable, Crud, Likeable},
+    traits::{Blockable, Crud, Likeable, Reactable},
     ListingType,
     SortType,
   };
@@ -614,6 +715,14 @@ mod tests {
       score: 1,
     };
 
+    // Reactions are listed, but don't change the score
+    let post_reaction_form = PostReactionForm {
+      post_id: inserted_post.id,
+      person_id: inserted_person.id,
+      emoji: "🎉".to_string(),
+    };
+    PostReaction::react(&conn, &post_reaction_form).unwrap();
+
     let read_post_listings_with_person = PostQueryBuilder::create(&conn)
       .listing_type(ListingType::Community)
       .sort(SortType::New)
@@ -658,8 +767,17 @@ mod tests {
         thumbnail_url: None,
         ap_id: inserted_post.ap_id.to_owned(),
         local: true,
+        content_warning: None,
+        language: None,
+        scheduled_publish_time: None,
       },
       my_vote: None,
+      reactions: vec![ReactionCount {
+        emoji: "🎉".to_string(),
+        count: 1,
+        reacted: false,
+      }],
+      poll: None,
       creator: PersonSafe {
         id: inserted_person.id,
         name: person_name,
@@ -679,6 +797,8 @@ mod tests {
         shared_inbox_url: None,
         matrix_user_id: None,
         ban_expires: None,
+        moved_to: None,
+        also_known_as: vec![],
       },
       creator_banned_from_community: false,
       community: CommunitySafe {
@@ -695,6 +815,8 @@ mod tests {
         updated: None,
         banner: None,
         hidden: false,
+        moved_to: None,
+        also_known_as: vec![],
         published: inserted_community.published,
       },
       counts: PostAggregates {
@@ -708,6 +830,8 @@ mod tests {
         published: agg.published,
         newest_comment_time_necro: inserted_post.published,
         newest_comment_time: inserted_post.published,
+        controversy_rank: 0.0,
+        rising_rank: agg.rising_rank,
       },
       subscribed: false,
       read: false,
@@ -715,6 +839,79 @@ mod tests {
       creator_blocked: false,
     };
 
+    // Posts in other languages are hidden, posts without language are always shown
+    let german_post_form = PostForm {
+      name: "Ein Beitrag".to_string(),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      language: Some(Some("de".to_string())),
+      ..PostForm::default()
+    };
+    let german_post = Post::create(&conn, &german_post_form).unwrap();
+    let read_post_listings_french = PostQueryBuilder::create(&conn)
+      .listing_type(ListingType::Community)
+      .sort(SortType::New)
+      .show_bot_accounts(false)
+      .community_id(inserted_community.id)
+      .my_person_id(inserted_person.id)
+      .discussion_languages(vec!["fr".to_string()])
+      .list()
+      .unwrap();
+    Post::delete(&conn, german_post.id).unwrap();
+
+    // Scheduled posts are only listed when asking for them
+    let scheduled_post_form = PostForm {
+      name: "A scheduled post".to_string(),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      scheduled_publish_time: Some(Some(naive_now() + chrono::Duration::days(1))),
+      ..PostForm::default()
+    };
+    let scheduled_post = Post::create(&conn, &scheduled_post_form).unwrap();
+    let read_post_listings_with_scheduled = PostQueryBuilder::create(&conn)
+      .listing_type(ListingType::Community)
+      .sort(SortType::New)
+      .community_id(inserted_community.id)
+      .list()
+      .unwrap();
+    let read_scheduled_posts = PostQueryBuilder::create(&conn)
+      .creator_id(inserted_person.id)
+      .scheduled_only(true)
+      .list()
+      .unwrap();
+    Post::delete(&conn, scheduled_post.id).unwrap();
+
+    // The upvoted post rises above the others, and all of them are from the last hour
+    let read_post_listings_rising = PostQueryBuilder::create(&conn)
+      .listing_type(ListingType::Community)
+      .sort(SortType::Rising)
+      .community_id(inserted_community.id)
+      .list()
+      .unwrap();
+    let read_post_listings_top_hour = PostQueryBuilder::create(&conn)
+      .listing_type(ListingType::Community)
+      .sort(SortType::TopHour)
+      .community_id(inserted_community.id)
+      .list()
+      .unwrap();
+
+    // The english search finds the post by the stemmed word
+    let read_post_listings_search = PostQueryBuilder::create(&conn)
+      .listing_type(ListingType::Community)
+      .sort(SortType::Relevance)
+      .community_id(inserted_community.id)
+      .search_term("posts".to_string())
+      .search_language("en".to_string())
+      .list()
+      .unwrap();
+    let read_post_listings_search_no_match = PostQueryBuilder::create(&conn)
+      .listing_type(ListingType::Community)
+      .sort(SortType::Relevance)
+      .community_id(inserted_community.id)
+      .search_term("unicorn".to_string())
+      .list()
+      .unwrap();
+
     // Test a community block
     let community_block = CommunityBlockForm {
       person_id: inserted_person.id,
@@ -734,6 +931,7 @@ mod tests {
     // TODO More needs to be added here
     let mut expected_post_listing_with_user = expected_post_listing_no_person.to_owned();
     expected_post_listing_with_user.my_vote = Some(1);
+    expected_post_listing_with_user.reactions[0].reacted = true;
 
     let like_removed = PostLike::remove(&conn, inserted_person.id, inserted_post.id).unwrap();
     let num_deleted = Post::delete(&conn, inserted_post.id).unwrap();
@@ -767,6 +965,25 @@ mod tests {
     // Should be 2 posts, with the bot post, and the blocked
     assert_eq!(3, read_post_listings_no_person.len());
 
+    // Only the post without language
+    assert_eq!(1, read_post_listings_french.len());
+    assert_eq!(inserted_post.id, read_post_listings_french[0].post.id);
+    assert_eq!(3, read_post_listings_with_scheduled.len());
+    assert!(!read_post_listings_with_scheduled
+      .iter()
+      .any(|p| p.post.id == scheduled_post.id));
+    assert_eq!(1, read_scheduled_posts.len());
+    assert_eq!(scheduled_post.id, read_scheduled_posts[0].post.id);
+
+    assert_eq!(3, read_post_listings_rising.len());
+    assert_eq!(inserted_post.id, read_post_listings_rising[0].post.id);
+    assert_eq!(3, read_post_listings_top_hour.len());
+    assert_eq!(inserted_post.id, read_post_listings_top_hour[0].post.id);
+    assert!(read_post_listings_search
+      .iter()
+      .any(|p| p.post.id == inserted_post.id));
+    assert!(read_post_listings_search_no_match.is_empty());
+
     // Should be 0 posts after the community block
     assert_eq!(0, read_post_listings_with_person_after_block.len());
 
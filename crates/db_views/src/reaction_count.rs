@@ -0,0 +1,77 @@
+use diesel::{dsl::count_star, result::Error, *};
+use lemmy_db_schema::{
+  newtypes::{CommentId, PersonId, PostId},
+  schema::{comment_reaction, post_reaction},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, hash::Hash};
+
+/// Number of people who reacted with an emoji to a post or comment. Reactions are counted separately
+/// from votes, and don't affect the score.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ReactionCount {
+  pub emoji: String,
+  pub count: i64,
+  /// True if the person viewing the item reacted with this emoji
+  pub reacted: bool,
+}
+
+/// Reaction counts for each of the posts, most used emojis first.
+pub(crate) fn post_reaction_counts(
+  conn: &PgConnection,
+  post_ids: Vec<PostId>,
+  my_person_id: Option<PersonId>,
+) -> Result<HashMap<PostId, Vec<ReactionCount>>, Error> {
+  let counts = post_reaction::table
+    .filter(post_reaction::post_id.eq_any(post_ids.clone()))
+    .group_by((post_reaction::post_id, post_reaction::emoji))
+    .select((post_reaction::post_id, post_reaction::emoji, count_star()))
+    .order_by((count_star().desc(), post_reaction::emoji))
+    .load::<(PostId, String, i64)>(conn)?;
+  let reacted = post_reaction::table
+    .filter(post_reaction::post_id.eq_any(post_ids))
+    .filter(post_reaction::person_id.eq(my_person_id.unwrap_or(PersonId(-1))))
+    .select((post_reaction::post_id, post_reaction::emoji))
+    .load::<(PostId, String)>(conn)?;
+  Ok(group_reaction_counts(counts, reacted))
+}
+
+/// Reaction counts for each of the comments, most used emojis first.
+pub(crate) fn comment_reaction_counts(
+  conn: &PgConnection,
+  comment_ids: Vec<CommentId>,
+  my_person_id: Option<PersonId>,
+) -> Result<HashMap<CommentId, Vec<ReactionCount>>, Error> {
+  let counts = comment_reaction::table
+    .filter(comment_reaction::comment_id.eq_any(comment_ids.clone()))
+    .group_by((comment_reaction::comment_id, comment_reaction::emoji))
+    .select((
+      comment_reaction::comment_id,
+      comment_reaction::emoji,
+      count_star(),
+    ))
+    .order_by((count_star().desc(), comment_reaction::emoji))
+    .load::<(CommentId, String, i64)>(conn)?;
+  let reacted = comment_reaction::table
+    .filter(comment_reaction::comment_id.eq_any(comment_ids))
+    .filter(comment_reaction::person_id.eq(my_person_id.unwrap_or(PersonId(-1))))
+    .select((comment_reaction::comment_id, comment_reaction::emoji))
+    .load::<(CommentId, String)>(conn)?;
+  Ok(group_reaction_counts(counts, reacted))
+}
+
+fn group_reaction_counts<T: Eq + Hash>(
+  counts: Vec<(T, String, i64)>,
+  reacted: Vec<(T, String)>,
+) -> HashMap<T, Vec<ReactionCount>> {
+  let mut grouped: HashMap<T, Vec<ReactionCount>> = HashMap::new();
+  for (item_id, emoji, count) in counts {
+    let reacted = reacted.iter().any(|(i, e)| i == &item_id && e == &emoji);
+    grouped.entry(item_id).or_default().push(ReactionCount {
+      emoji,
+      count,
+      reacted,
+    });
+  }
+  grouped
+}
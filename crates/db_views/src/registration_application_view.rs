@@ -280,6 +280,8 @@ mod tests {
         show_new_post_notifs: inserted_sara_local_user.show_new_post_notifs,
         email_verified: inserted_sara_local_user.email_verified,
         accepted_application: inserted_sara_local_user.accepted_application,
+        default_language: inserted_sara_local_user.default_language,
+        discussion_languages: inserted_sara_local_user.discussion_languages,
       },
       creator: PersonSafe {
         id: inserted_sara_person.id,
@@ -291,6 +293,8 @@ mod tests {
         local: true,
         banned: false,
         ban_expires: None,
+        moved_to: None,
+        also_known_as: vec![],
         deleted: false,
         admin: false,
         bot_account: false,
@@ -360,6 +364,8 @@ mod tests {
       local: true,
       banned: false,
       ban_expires: None,
+      moved_to: None,
+      also_known_as: vec![],
       deleted: false,
       admin: true,
       bot_account: false,
@@ -0,0 +1,170 @@
+use diesel::{dsl::sql, result::Error, *};
+use lemmy_db_schema::{
+  full_text_search::{text_search_headline, text_search_query},
+  newtypes::{CommentId, PostId},
+  schema::{comment, post},
+};
+use serde::{Deserialize, Serialize};
+
+/// The parts of a post which match a search, with the matched words in markdown bold. The title
+/// is searched together with the body, so that matches in either of them are highlighted.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct PostSnippet {
+  pub post_id: PostId,
+  pub snippet: String,
+}
+
+/// The parts of a comment which match a search, with the matched words in markdown bold.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct CommentSnippet {
+  pub comment_id: CommentId,
+  pub snippet: String,
+}
+
+/// Search snippets for each of the posts. The search terms and language need to be the same as
+/// for the search itself.
+pub fn post_search_snippets(
+  conn: &PgConnection,
+  post_ids: Vec<PostId>,
+  search_term: String,
+  search_language: Option<String>,
+) -> Result<Vec<PostSnippet>, Error> {
+  post::table
+    .filter(post::id.eq_any(post_ids))
+    .select((
+      post::id,
+      text_search_headline(
+        sql::<sql_types::Text>("concat_ws(E'\\n\\n', post.name, post.body)"),
+        post::language,
+        text_search_query(search_term, search_language),
+      ),
+    ))
+    .load::<(PostId, String)>(conn)
+    .map(|snippets| {
+      snippets
+        .into_iter()
+        .map(|(post_id, snippet)| PostSnippet { post_id, snippet })
+        .collect()
+    })
+}
+
+/// Search snippets for each of the comments. The search terms and language need to be the same
+/// as for the search itself.
+pub fn comment_search_snippets(
+  conn: &PgConnection,
+  comment_ids: Vec<CommentId>,
+  search_term: String,
+  search_language: Option<String>,
+) -> Result<Vec<CommentSnippet>, Error> {
+  comment::table
+    .filter(comment::id.eq_any(comment_ids))
+    .select((
+      comment::id,
+      text_search_headline(
+        comment::content,
+        comment::language,
+        text_search_query(search_term, search_language),
+      ),
+    ))
+    .load::<(CommentId, String)>(conn)
+    .map(|snippets| {
+      snippets
+        .into_iter()
+        .map(|(comment_id, snippet)| CommentSnippet {
+          comment_id,
+          snippet,
+        })
+        .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::search_snippet::*;
+  use lemmy_db_schema::{
+    establish_unpooled_connection,
+    source::{comment::*, community::*, person::*, post::*},
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_snippets() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "snippet_person".into(),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let new_community = CommunityForm {
+      name: "snippet_community".to_string(),
+      title: "nada".to_owned(),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let title_post_form = PostForm {
+      name: "Unicorn sightings".into(),
+      body: Some(Some("Nothing to see here".into())),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      ..PostForm::default()
+    };
+    let title_post = Post::create(&conn, &title_post_form).unwrap();
+
+    let body_post_form = PostForm {
+      name: "A post about horses".into(),
+      body: Some(Some("Although one of them looked like a unicorn".into())),
+      creator_id: inserted_person.id,
+      community_id: inserted_community.id,
+      ..PostForm::default()
+    };
+    let body_post = Post::create(&conn, &body_post_form).unwrap();
+
+    let comment_form = CommentForm {
+      content: "I saw a unicorn too".into(),
+      creator_id: inserted_person.id,
+      post_id: title_post.id,
+      ..CommentForm::default()
+    };
+    let inserted_comment = Comment::create(&conn, &comment_form).unwrap();
+
+    let mut post_snippets = post_search_snippets(
+      &conn,
+      vec![title_post.id, body_post.id],
+      "unicorn".to_string(),
+      None,
+    )
+    .unwrap();
+    post_snippets.sort_by_key(|s| s.post_id.0);
+    let comment_snippets = comment_search_snippets(
+      &conn,
+      vec![inserted_comment.id],
+      "unicorn".to_string(),
+      None,
+    )
+    .unwrap();
+
+    Post::delete(&conn, title_post.id).unwrap();
+    Post::delete(&conn, body_post.id).unwrap();
+    Community::delete(&conn, inserted_community.id).unwrap();
+    Person::delete(&conn, inserted_person.id).unwrap();
+
+    // A match in the title is highlighted as well as one in the body
+    assert_eq!(2, post_snippets.len());
+    assert_eq!(title_post.id, post_snippets[0].post_id);
+    assert!(post_snippets[0].snippet.contains("**Unicorn**"));
+    assert_eq!(body_post.id, post_snippets[1].post_id);
+    assert!(post_snippets[1].snippet.contains("**unicorn**"));
+    assert_eq!(
+      vec![CommentSnippet {
+        comment_id: inserted_comment.id,
+        snippet: "I saw a **unicorn** too".to_string(),
+      }],
+      comment_snippets
+    );
+  }
+}
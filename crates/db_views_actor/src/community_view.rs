@@ -1,14 +1,29 @@
 use crate::{community_moderator_view::CommunityModeratorView, person_view::PersonViewSafe};
-use diesel::{result::Error, *};
+use diesel::{dsl::sql, result::Error, *};
 use lemmy_db_schema::{
   aggregates::community_aggregates::CommunityAggregates,
+  full_text_search::{
+    non_blank_search_term,
+    text_search_query,
+    ts_rank_cd,
+    TsVector,
+    TsVectorExtensions,
+  },
   functions::hot_rank,
   fuzzy_search,
   limit_and_offset,
   newtypes::{CommunityId, PersonId},
-  schema::{community, community_aggregates, community_block, community_follower, local_user},
+  schema::{
+    community,
+    community_aggregates,
+    community_backfill,
+    community_block,
+    community_follower,
+    local_user,
+  },
   source::{
     community::{Community, CommunityFollower, CommunitySafe},
+    community_backfill::CommunityBackfill,
     community_block::CommunityBlock,
   },
   traits::{MaybeOptional, ToSafe, ViewToVec},
@@ -23,6 +38,8 @@ pub struct CommunityView {
   pub subscribed: bool,
   pub blocked: bool,
   pub counts: CommunityAggregates,
+  /// Only set for remote communities which are being, or have been backfilled
+  pub backfill: Option<CommunityBackfill>,
 }
 
 type CommunityViewTuple = (
@@ -30,6 +47,7 @@ type CommunityViewTuple = (
   CommunityAggregates,
   Option<CommunityFollower>,
   Option<CommunityBlock>,
+  Option<CommunityBackfill>,
 );
 
 impl CommunityView {
@@ -41,7 +59,7 @@ impl CommunityView {
     // The left join below will return None in this case
     let person_id_join = my_person_id.unwrap_or(PersonId(-1));
 
-    let (community, counts, follower, blocked) = community::table
+    let (community, counts, follower, blocked, backfill) = community::table
       .find(community_id)
       .inner_join(community_aggregates::table)
       .left_join(
@@ -58,11 +76,13 @@ impl CommunityView {
             .and(community_block::person_id.eq(person_id_join)),
         ),
       )
+      .left_join(community_backfill::table)
       .select((
         Community::safe_columns_tuple(),
         community_aggregates::all_columns,
         community_follower::all_columns.nullable(),
         community_block::all_columns.nullable(),
+        community_backfill::all_columns.nullable(),
       ))
       .first::<CommunityViewTuple>(conn)?;
 
@@ -71,6 +91,7 @@ impl CommunityView {
       subscribed: follower.is_some(),
       blocked: blocked.is_some(),
       counts,
+      backfill,
     })
   }
 
@@ -142,7 +163,7 @@ impl<'a> CommunityQueryBuilder<'a> {
   }
 
   pub fn search_term<T: MaybeOptional<String>>(mut self, search_term: T) -> Self {
-    self.search_term = search_term.get_optional();
+    self.search_term = non_blank_search_term(search_term.get_optional());
     self
   }
 
@@ -182,23 +203,40 @@ impl<'a> CommunityQueryBuilder<'a> {
             .and(community_block::person_id.eq(person_id_join)),
         ),
       )
+      .left_join(community_backfill::table)
       .select((
         Community::safe_columns_tuple(),
         community_aggregates::all_columns,
         community_follower::all_columns.nullable(),
         community_block::all_columns.nullable(),
+        community_backfill::all_columns.nullable(),
       ))
       .into_boxed();
 
-    if let Some(search_term) = self.search_term {
-      let searcher = fuzzy_search(&search_term);
-      query = query
-        .filter(community::name.ilike(searcher.to_owned()))
-        .or_filter(community::title.ilike(searcher.to_owned()))
-        .or_filter(community::description.ilike(searcher));
+    // Communities don't have a language, so the search terms only match as written. Parts of the
+    // name or title are found too, as people often search for a community by a few letters of it.
+    if let Some(search_term) = &self.search_term {
+      let searcher = fuzzy_search(search_term);
+      query = query.filter(
+        sql::<TsVector>("community.search_vector")
+          .matches(text_search_query(search_term.to_owned(), None::<String>))
+          .or(community::name.ilike(searcher.to_owned()))
+          .or(community::title.ilike(searcher)),
+      );
     };
 
     match self.sort.unwrap_or(SortType::Hot) {
+      SortType::Relevance if self.search_term.is_some() => {
+        query = query
+          .order_by(
+            ts_rank_cd(
+              sql::<TsVector>("community.search_vector"),
+              text_search_query(self.search_term.unwrap_or_default(), None::<String>),
+            )
+            .desc(),
+          )
+          .then_order_by(community_aggregates::subscribers.desc())
+      }
       SortType::New => query = query.order_by(community::published.desc()),
       SortType::TopAll => query = query.order_by(community_aggregates::subscribers.desc()),
       SortType::TopMonth => query = query.order_by(community_aggregates::users_active_month.desc()),
@@ -274,7 +312,57 @@ impl ViewToVec for CommunityView {
         counts: a.1.to_owned(),
         subscribed: a.2.is_some(),
         blocked: a.3.is_some(),
+        backfill: a.4.to_owned(),
       })
       .collect::<Vec<Self>>()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::community_view::*;
+  use lemmy_db_schema::{
+    establish_unpooled_connection,
+    source::community::{Community, CommunityForm},
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_search() {
+    let conn = establish_unpooled_connection();
+
+    let new_community = CommunityForm {
+      name: "searchable_kittens".to_string(),
+      title: "Cute kittens".to_owned(),
+      description: Some("Pictures of cats".to_owned()),
+      ..CommunityForm::default()
+    };
+    let inserted_community = Community::create(&conn, &new_community).unwrap();
+
+    let search = |search_term: &str| {
+      CommunityQueryBuilder::create(&conn)
+        .sort(SortType::Relevance)
+        .search_term(search_term.to_string())
+        .limit(50)
+        .list()
+        .unwrap()
+        .iter()
+        .any(|c| c.community.id == inserted_community.id)
+    };
+    let found_by_title = search("kittens");
+    let found_by_description = search("cats");
+    let found_by_part_of_name = search("able_kit");
+    let found_by_blank_search = search("");
+    let found_by_other_word = search("unicorn");
+
+    Community::delete(&conn, inserted_community.id).unwrap();
+
+    assert!(found_by_title);
+    assert!(found_by_description);
+    assert!(found_by_part_of_name);
+    assert!(found_by_blank_search);
+    assert!(!found_by_other_word);
+  }
+}
@@ -307,7 +307,7 @@ impl<'a> PersonMentionQueryBuilder<'a> {
     }
 
     query = match self.sort.unwrap_or(SortType::Hot) {
-      SortType::Hot | SortType::Active => query
+      SortType::Hot | SortType::Active | SortType::Relevance => query
         .order_by(hot_rank(comment_aggregates::score, comment_aggregates::published).desc())
         .then_order_by(comment_aggregates::published.desc()),
       SortType::New | SortType::MostComments | SortType::NewComments => {
@@ -326,6 +326,22 @@ impl<'a> PersonMentionQueryBuilder<'a> {
       SortType::TopDay => query
         .filter(comment::published.gt(now - 1.days()))
         .order_by(comment_aggregates::score.desc()),
+      SortType::TopTwelveHour => query
+        .filter(comment_aggregates::published.gt(now - 12.hours()))
+        .order_by(comment_aggregates::score.desc()),
+      SortType::TopSixHour => query
+        .filter(comment_aggregates::published.gt(now - 6.hours()))
+        .order_by(comment_aggregates::score.desc()),
+      SortType::TopHour => query
+        .filter(comment_aggregates::published.gt(now - 1.hours()))
+        .order_by(comment_aggregates::score.desc()),
+      SortType::Controversial => query
+        .order_by(comment_aggregates::controversy_rank.desc())
+        .then_order_by(comment_aggregates::published.desc()),
+      SortType::Rising => query
+        .filter(comment_aggregates::published.gt(now - 1.days()))
+        .order_by(comment_aggregates::rising_rank.desc())
+        .then_order_by(comment_aggregates::published.desc()),
     };
 
     let (limit, offset) = limit_and_offset(self.page, self.limit);
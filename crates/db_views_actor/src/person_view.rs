@@ -1,6 +1,13 @@
 use diesel::{dsl::*, result::Error, *};
 use lemmy_db_schema::{
   aggregates::person_aggregates::PersonAggregates,
+  full_text_search::{
+    non_blank_search_term,
+    text_search_query,
+    ts_rank_cd,
+    TsVector,
+    TsVectorExtensions,
+  },
   fuzzy_search,
   limit_and_offset,
   newtypes::PersonId,
@@ -82,7 +89,7 @@ impl<'a> PersonQueryBuilder<'a> {
   }
 
   pub fn search_term<T: MaybeOptional<String>>(mut self, search_term: T) -> Self {
-    self.search_term = search_term.get_optional();
+    self.search_term = non_blank_search_term(search_term.get_optional());
     self
   }
 
@@ -102,15 +109,30 @@ impl<'a> PersonQueryBuilder<'a> {
       .select((Person::safe_columns_tuple(), person_aggregates::all_columns))
       .into_boxed();
 
-    if let Some(search_term) = self.search_term {
-      query = query.filter(person::name.ilike(fuzzy_search(&search_term)));
+    // Users don't have a language, so the search terms only match as written. Parts of the name
+    // are found too, like for mentions which are typed letter by letter.
+    if let Some(search_term) = &self.search_term {
+      query = query.filter(
+        sql::<TsVector>("person.search_vector")
+          .matches(text_search_query(search_term.to_owned(), None::<String>))
+          .or(person::name.ilike(fuzzy_search(search_term))),
+      );
     }
 
     query = match self.sort.unwrap_or(SortType::Hot) {
+      SortType::Relevance if self.search_term.is_some() => query
+        .order_by(
+          ts_rank_cd(
+            sql::<TsVector>("person.search_vector"),
+            text_search_query(self.search_term.unwrap_or_default(), None::<String>),
+          )
+          .desc(),
+        )
+        .then_order_by(person_aggregates::comment_score.desc()),
       SortType::Hot => query
         .order_by(person_aggregates::comment_score.desc())
         .then_order_by(person::published.desc()),
-      SortType::Active => query
+      SortType::Active | SortType::Controversial | SortType::Rising | SortType::Relevance => query
         .order_by(person_aggregates::comment_score.desc())
         .then_order_by(person::published.desc()),
       SortType::New | SortType::MostComments | SortType::NewComments => {
@@ -129,6 +151,15 @@ impl<'a> PersonQueryBuilder<'a> {
       SortType::TopDay => query
         .filter(person::published.gt(now - 1.days()))
         .order_by(person_aggregates::comment_score.desc()),
+      SortType::TopTwelveHour => query
+        .filter(person::published.gt(now - 12.hours()))
+        .order_by(person_aggregates::comment_score.desc()),
+      SortType::TopSixHour => query
+        .filter(person::published.gt(now - 6.hours()))
+        .order_by(person_aggregates::comment_score.desc()),
+      SortType::TopHour => query
+        .filter(person::published.gt(now - 1.hours()))
+        .order_by(person_aggregates::comment_score.desc()),
     };
 
     let (limit, offset) = limit_and_offset(self.page, self.limit);
@@ -152,3 +183,52 @@ impl ViewToVec for PersonViewSafe {
       .collect::<Vec<Self>>()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::person_view::*;
+  use lemmy_db_schema::{
+    establish_unpooled_connection,
+    source::person::{Person, PersonForm},
+    traits::Crud,
+  };
+  use serial_test::serial;
+
+  #[test]
+  #[serial]
+  fn test_search() {
+    let conn = establish_unpooled_connection();
+
+    let new_person = PersonForm {
+      name: "searchable_sam".into(),
+      display_name: Some(Some("Samantha".into())),
+      bio: Some(Some("Likes hiking".into())),
+      ..PersonForm::default()
+    };
+    let inserted_person = Person::create(&conn, &new_person).unwrap();
+
+    let search = |search_term: &str| {
+      PersonQueryBuilder::create(&conn)
+        .sort(SortType::Relevance)
+        .search_term(search_term.to_string())
+        .limit(50)
+        .list()
+        .unwrap()
+        .iter()
+        .any(|p| p.person.id == inserted_person.id)
+    };
+    let found_by_display_name = search("samantha");
+    let found_by_bio = search("hiking");
+    let found_by_part_of_name = search("able_sa");
+    let found_by_blank_search = search(" ");
+    let found_by_other_word = search("unicorn");
+
+    Person::delete(&conn, inserted_person.id).unwrap();
+
+    assert!(found_by_display_name);
+    assert!(found_by_bio);
+    assert!(found_by_part_of_name);
+    assert!(found_by_blank_search);
+    assert!(!found_by_other_word);
+  }
+}
@@ -2,6 +2,7 @@ pub mod mod_add_community_view;
 pub mod mod_add_view;
 pub mod mod_ban_from_community_view;
 pub mod mod_ban_view;
+pub mod mod_federation_list_view;
 pub mod mod_hide_community_view;
 pub mod mod_lock_post_view;
 pub mod mod_remove_comment_view;
@@ -0,0 +1,65 @@
+use diesel::{result::Error, *};
+use lemmy_db_schema::{
+  limit_and_offset,
+  newtypes::PersonId,
+  schema::{mod_federation_list, person},
+  source::{
+    moderator::ModFederationList,
+    person::{Person, PersonSafe},
+  },
+  traits::{ToSafe, ViewToVec},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModFederationListView {
+  pub mod_federation_list: ModFederationList,
+  pub admin: PersonSafe,
+}
+
+type ModFederationListViewTuple = (ModFederationList, PersonSafe);
+
+impl ModFederationListView {
+  // Pass in mod_id as admin_id because only admins can do this action
+  pub fn list(
+    conn: &PgConnection,
+    admin_id: Option<PersonId>,
+    page: Option<i64>,
+    limit: Option<i64>,
+  ) -> Result<Vec<Self>, Error> {
+    let mut query = mod_federation_list::table
+      .inner_join(person::table)
+      .select((
+        mod_federation_list::all_columns,
+        Person::safe_columns_tuple(),
+      ))
+      .into_boxed();
+
+    if let Some(admin_id) = admin_id {
+      query = query.filter(mod_federation_list::mod_person_id.eq(admin_id));
+    };
+
+    let (limit, offset) = limit_and_offset(page, limit);
+
+    let res = query
+      .limit(limit)
+      .offset(offset)
+      .order_by(mod_federation_list::when_.desc())
+      .load::<ModFederationListViewTuple>(conn)?;
+
+    Ok(Self::from_tuple_to_vec(res))
+  }
+}
+
+impl ViewToVec for ModFederationListView {
+  type DbTuple = ModFederationListViewTuple;
+  fn from_tuple_to_vec(items: Vec<Self::DbTuple>) -> Vec<Self> {
+    items
+      .iter()
+      .map(|a| Self {
+        mod_federation_list: a.0.to_owned(),
+        admin: a.1.to_owned(),
+      })
+      .collect::<Vec<Self>>()
+  }
+}
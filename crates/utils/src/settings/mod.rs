@@ -1,30 +1,18 @@
 use crate::{location_info, settings::structs::Settings, LemmyError};
 use anyhow::{anyhow, Context};
 use deser_hjson::from_str;
-use once_cell::sync::Lazy;
 use regex::{Regex, RegexBuilder};
-use std::{env, fs, io::Error, sync::RwLock};
+use std::{env, fs, io::Error};
 
 pub mod structs;
 
 static DEFAULT_CONFIG_FILE: &str = "config/config.hjson";
 
-static SETTINGS: Lazy<RwLock<Settings>> =
-  Lazy::new(|| RwLock::new(Settings::init().expect("Failed to load settings file")));
-static WEBFINGER_REGEX: Lazy<Regex> = Lazy::new(|| {
-  Regex::new(&format!(
-    "^acct:([a-zA-Z0-9_]{{3,}})@{}$",
-    Settings::get().hostname
-  ))
-  .expect("compile webfinger regex")
-});
-
 impl Settings {
   /// Reads config from configuration file.
   ///
   /// Note: The env var `LEMMY_DATABASE_URL` is parsed in
   /// `lemmy_db_schema/src/lib.rs::get_database_url_from_env()`
-  /// Warning: Only call this once.
   pub fn init() -> Result<Self, LemmyError> {
     // Read the config file
     let config = from_str::<Settings>(&Self::read_config_file()?)?;
@@ -36,11 +24,6 @@ impl Settings {
     Ok(config)
   }
 
-  /// Returns the config as a struct.
-  pub fn get() -> Self {
-    SETTINGS.read().expect("read config").to_owned()
-  }
-
   pub fn get_database_url(&self) -> String {
     let conf = &self.database;
     format!(
@@ -91,21 +74,15 @@ impl Settings {
     // check that the config is valid
     from_str::<Settings>(data)?;
 
+    // The running instance picks up the new settings with `LemmyContext::reload_settings`
     fs::write(Settings::get_config_location(), data)?;
 
-    // Reload the new settings
-    // From https://stackoverflow.com/questions/29654927/how-do-i-assign-a-string-to-a-mutable-static-variable/47181804#47181804
-    let mut new_settings = SETTINGS.write().expect("write config");
-    *new_settings = match Settings::init() {
-      Ok(c) => c,
-      Err(e) => panic!("{}", e),
-    };
-
     Ok(Self::read_config_file()?)
   }
 
   pub fn webfinger_regex(&self) -> Regex {
-    WEBFINGER_REGEX.to_owned()
+    Regex::new(&format!("^acct:([a-zA-Z0-9_]{{3,}})@{}$", self.hostname))
+      .expect("compile webfinger regex")
   }
 
   pub fn slur_regex(&self) -> Option<Regex> {
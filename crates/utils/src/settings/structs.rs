@@ -117,24 +117,54 @@ pub struct FederationConfig {
   /// Allows and blocks are described here:
   /// https://join-lemmy.org/docs/en/federation/administration.html///instance-allowlist-and-blocklist
   ///
-  /// list of instances with which federation is allowed
+  /// list of instances with which federation is allowed. Only imported into the database if it
+  /// has no federation list entries yet, afterwards the list is managed through the admin api.
   #[default(None)]
   #[doku(example = "instance1.tld")]
   #[doku(example = "instance2.tld")]
   pub allowed_instances: Option<Vec<String>>,
-  /// Instances which we never federate anything with (but previously federated objects are unaffected)
+  /// Instances which we never federate anything with (but previously federated objects are unaffected).
+  /// Only imported into the database if it has no federation list entries yet, afterwards the list is
+  /// managed through the admin api.
   #[default(None)]
   pub blocked_instances: Option<Vec<String>>,
   /// If true, only federate with instances on the allowlist and block everything else. If false,
   /// use allowlist only for remote communities, and posts/comments in local communities
-  /// (meaning remote communities will show content from arbitrary instances).
+  /// (meaning remote communities will show content from arbitrary instances). Only used as initial
+  /// value for the site, afterwards it is managed through the admin api.
   #[default(true)]
   pub strict_allowlist: bool,
+  /// If true, outgoing fetches are signed with the instance actor, and fetching posts, comments,
+  /// outboxes or other collections from this instance requires a valid signature from an instance
+  /// which isn't blocked. Users and communities can still be fetched without signature, because
+  /// other instances need their public keys to verify signatures.
+  #[default(false)]
+  pub authorized_fetch: bool,
   /// Number of workers for sending outgoing activities. Search logs for "Activity queue stats" to
   /// see information. If "running" number is consistently close to the worker_count, you should
   /// increase it.
   #[default(64)]
   pub worker_count: u64,
+  /// Number of failed deliveries in a row after which a remote instance is considered dead, and
+  /// no more activities are sent to it
+  #[default(50)]
+  pub dead_instance_failure_threshold: i32,
+  /// Minutes since the last successful delivery after which a remote instance may be considered
+  /// dead, so that a short outage doesn't suspend it
+  #[default(60)]
+  pub dead_instance_min_downtime: i64,
+  /// Minutes to wait before trying to deliver to a dead instance again
+  #[default(360)]
+  pub dead_instance_probe_interval: i64,
+  /// Number of workers for processing incoming activities. The queue depth can be checked by
+  /// admins via the api. If many activities are pending, you should increase it.
+  #[default(16)]
+  pub inbox_worker_count: u64,
+  /// Maximum number of requests which are made to backfill a remote community when it is followed
+  /// for the first time. This includes outbox pages and pages of comments, as well as the users
+  /// and parent objects which are fetched along with them.
+  #[default(500)]
+  pub community_backfill_budget: i32,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
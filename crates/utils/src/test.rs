@@ -5,6 +5,7 @@ use crate::{
     is_valid_display_name,
     is_valid_matrix_id,
     is_valid_post_title,
+    is_valid_reaction,
     remove_slurs,
     scrape_text_for_mentions,
     slur_check,
@@ -55,6 +56,29 @@ fn test_valid_post_title() {
   assert!(!is_valid_post_title("\n \n \n \n    		")); // tabs/spaces/newlines
 }
 
+#[test]
+fn test_valid_reaction() {
+  assert!(is_valid_reaction("🎉"));
+  assert!(is_valid_reaction("👍🏽"));
+  assert!(is_valid_reaction("👨‍👩‍👧"));
+  assert!(is_valid_reaction(":blobcat:"));
+  assert!(!is_valid_reaction(""));
+  assert!(!is_valid_reaction("lol"));
+  assert!(!is_valid_reaction("🎉 🎉"));
+  assert!(!is_valid_reaction(":blob cat:"));
+  assert!(is_valid_reaction("❤️"));
+  assert!(is_valid_reaction("🇩🇪"));
+  assert!(is_valid_reaction("1️⃣"));
+  assert!(is_valid_reaction("🏴󠁧󠁢󠁳󠁣󠁴󠁿"));
+  assert!(!is_valid_reaction("🎉🎉"));
+  assert!(!is_valid_reaction("1"));
+  assert!(!is_valid_reaction("?!"));
+  assert!(!is_valid_reaction("中文"));
+  assert!(!is_valid_reaction("ñ"));
+  // Misskey custom emojis from other instances
+  assert!(!is_valid_reaction(":blobcat@misskey.io:"));
+}
+
 #[test]
 fn test_valid_matrix_id() {
   assert!(is_valid_matrix_id("@dess:matrix.org"));
@@ -17,6 +17,20 @@ static VALID_POST_TITLE_REGEX: Lazy<Regex> =
 static VALID_MATRIX_ID_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"^@[A-Za-z0-9._=-]+:[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").expect("compile regex")
 });
+static VALID_LANGUAGE_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^[a-z]{2,3}(-[A-Za-z0-9]{2,8})*$").expect("compile regex"));
+// A single emoji or the shortcode of a custom emoji. Emojis can be made up of several codepoints:
+// a flag, a keycap, or pictographs with skin tone or variation selectors which are joined by ZWJ.
+static VALID_REACTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(concat!(
+    r"^(:[A-Za-z0-9_]{1,60}:",
+    r"|[\x{1F1E6}-\x{1F1FF}]{2}",
+    r"|[0-9#*]\x{FE0F}?\x{20E3}",
+    r"|\p{Extended_Pictographic}[\x{FE0F}\p{Emoji_Modifier}]?[\x{E0020}-\x{E007F}]*",
+    r"(\x{200D}\p{Extended_Pictographic}[\x{FE0F}\p{Emoji_Modifier}]?)*)$"
+  ))
+  .expect("compile regex")
+});
 // taken from https://en.wikipedia.org/wiki/UTM_parameters
 static CLEAN_URL_PARAMS_REGEX: Lazy<Regex> = Lazy::new(|| {
   Regex::new(r"^utm_source|utm_medium|utm_campaign|utm_term|utm_content|gclid|gclsrc|dclid|fbclid$")
@@ -153,6 +167,20 @@ pub fn is_valid_post_title(title: &str) -> bool {
   VALID_POST_TITLE_REGEX.is_match(title) && !has_newline(title)
 }
 
+pub fn is_valid_reaction(emoji: &str) -> bool {
+  VALID_REACTION_REGEX.is_match(emoji)
+}
+
+/// Checks for a language tag like `en` or `pt-BR`
+pub fn is_valid_language(language: &str) -> bool {
+  VALID_LANGUAGE_REGEX.is_match(language)
+}
+
+pub fn is_valid_poll_option(option: &str) -> bool {
+  let len = option.trim().chars().count();
+  (1..=200).contains(&len) && !has_newline(option)
+}
+
 pub fn get_ip(conn_info: &ConnectionInfo) -> IpAddr {
   IpAddr(
     conn_info
@@ -165,6 +193,7 @@ pub fn get_ip(conn_info: &ConnectionInfo) -> IpAddr {
   )
 }
 
+/// Removes tracking parameters from the url, so that the same link always gives the same url.
 pub fn clean_url_params(mut url: Url) -> Url {
   if url.query().is_some() {
     let new_query = url
@@ -172,7 +201,11 @@ pub fn clean_url_params(mut url: Url) -> Url {
       .filter(|q| !CLEAN_URL_PARAMS_REGEX.is_match(&q.0))
       .map(|q| format!("{}={}", q.0, q.1))
       .join("&");
-    url.set_query(Some(&new_query));
+    if new_query.is_empty() {
+      url.set_query(None);
+    } else {
+      url.set_query(Some(&new_query));
+    }
   }
   url
 }
@@ -192,7 +225,12 @@ pub fn clean_optional_text(text: &Option<String>) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-  use crate::utils::{clean_url_params, is_valid_post_title};
+  use crate::utils::{
+    clean_url_params,
+    is_valid_language,
+    is_valid_poll_option,
+    is_valid_post_title,
+  };
   use url::Url;
 
   #[test]
@@ -205,6 +243,10 @@ mod tests {
     let url = Url::parse("https://example.com/path/123").unwrap();
     let cleaned = clean_url_params(url.clone());
     assert_eq!(url.to_string(), cleaned.to_string());
+
+    let url = Url::parse("https://example.com/path/123?utm_source=feed").unwrap();
+    let cleaned = clean_url_params(url);
+    assert_eq!("https://example.com/path/123", cleaned.to_string());
   }
 
   #[test]
@@ -215,4 +257,20 @@ mod tests {
     assert!(!is_valid_post_title("hello there!\n this is a test."));
     assert!(is_valid_post_title("hello there! this is a test."));
   }
+
+  #[test]
+  fn test_valid_language() {
+    assert!(is_valid_language("en"));
+    assert!(is_valid_language("pt-BR"));
+    assert!(!is_valid_language("English"));
+    assert!(!is_valid_language(""));
+  }
+
+  #[test]
+  fn test_valid_poll_option() {
+    assert!(is_valid_poll_option("Yes"));
+    assert!(!is_valid_poll_option("  "));
+    assert!(!is_valid_poll_option("first\nsecond"));
+    assert!(!is_valid_poll_option(&"a".repeat(201)));
+  }
 }
@@ -1,6 +1,7 @@
 use crate::{
   messages::*,
   serialize_websocket_message,
+  InstanceState,
   LemmyContext,
   OperationType,
   UserOperation,
@@ -18,14 +19,7 @@ use lemmy_db_schema::{
   newtypes::{CommunityId, LocalUserId, PostId},
   source::secret::Secret,
 };
-use lemmy_utils::{
-  location_info,
-  rate_limit::RateLimit,
-  settings::structs::Settings,
-  ConnectionId,
-  IpAddr,
-  LemmyError,
-};
+use lemmy_utils::{location_info, rate_limit::RateLimit, ConnectionId, IpAddr, LemmyError};
 use rand::rngs::ThreadRng;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::Serialize;
@@ -74,12 +68,11 @@ pub struct ChatServer {
   /// The DB Pool
   pub(super) pool: Pool<ConnectionManager<PgConnection>>,
 
-  /// The Settings
-  pub(super) settings: Settings,
-
   /// The Secrets
   pub(super) secret: Secret,
 
+  pub(super) state: InstanceState,
+
   /// Rate limiting based on rate type and IP addr
   pub(super) rate_limiter: RateLimit,
 
@@ -112,8 +105,8 @@ impl ChatServer {
     message_handler_crud: MessageHandlerCrudType,
     client: ClientWithMiddleware,
     activity_queue: QueueHandle,
-    settings: Settings,
     secret: Secret,
+    state: InstanceState,
   ) -> ChatServer {
     ChatServer {
       sessions: HashMap::new(),
@@ -129,8 +122,8 @@ impl ChatServer {
       message_handler_crud,
       client,
       activity_queue,
-      settings,
       secret,
+      state,
     }
   }
 
@@ -466,8 +459,8 @@ impl ChatServer {
       chat_server: ctx.address(),
       client: self.client.to_owned(),
       activity_queue: self.activity_queue.to_owned(),
-      settings: self.settings.to_owned(),
       secret: self.secret.to_owned(),
+      state: self.state.to_owned(),
     };
     let message_handler_crud = self.message_handler_crud;
     let message_handler = self.message_handler;
@@ -485,6 +478,7 @@ impl ChatServer {
           UserOperationCrud::GetPost => rate_limiter.message().check(ip),
           UserOperationCrud::Register => rate_limiter.register().check(ip),
           UserOperationCrud::CreatePost => rate_limiter.post().check(ip),
+          UserOperationCrud::CreateCrosspost => rate_limiter.post().check(ip),
           UserOperationCrud::CreateCommunity => rate_limiter.register().check(ip),
           UserOperationCrud::CreateComment => rate_limiter.comment().check(ip),
           _ => true,
@@ -4,10 +4,19 @@ extern crate strum_macros;
 use crate::chat_server::ChatServer;
 use actix::Addr;
 use background_jobs::QueueHandle;
-use lemmy_db_schema::{source::secret::Secret, DbPool};
+use lemmy_apub_lib::{
+  signatures::{FetchSigner, FetchSigning},
+  traits::InstanceSettings,
+};
+use lemmy_db_schema::{
+  source::{federation_list::FederationLists, secret::Secret},
+  DbPool,
+};
 use lemmy_utils::{settings::structs::Settings, LemmyError};
+use parking_lot::RwLock;
 use reqwest_middleware::ClientWithMiddleware;
 use serde::Serialize;
+use std::sync::Arc;
 
 pub mod chat_server;
 pub mod handlers;
@@ -15,13 +24,33 @@ pub mod messages;
 pub mod routes;
 pub mod send;
 
+/// Data of an instance which can change while the server is running. It is shared by all contexts
+/// of the instance, but not between instances, so that several of them can run in one process for
+/// federation tests.
+#[derive(Clone)]
+pub struct InstanceState {
+  settings: Arc<RwLock<Settings>>,
+  federation_lists: Arc<RwLock<FederationLists>>,
+  fetch_signer: FetchSigner,
+}
+
+impl InstanceState {
+  pub fn new(settings: Settings) -> Self {
+    InstanceState {
+      settings: Arc::new(RwLock::new(settings)),
+      federation_lists: Default::default(),
+      fetch_signer: Default::default(),
+    }
+  }
+}
+
 pub struct LemmyContext {
   pool: DbPool,
   chat_server: Addr<ChatServer>,
   client: ClientWithMiddleware,
   activity_queue: QueueHandle,
-  settings: Settings,
   secret: Secret,
+  state: InstanceState,
 }
 
 impl LemmyContext {
@@ -30,16 +59,16 @@ impl LemmyContext {
     chat_server: Addr<ChatServer>,
     client: ClientWithMiddleware,
     activity_queue: QueueHandle,
-    settings: Settings,
     secret: Secret,
+    state: InstanceState,
   ) -> LemmyContext {
     LemmyContext {
       pool,
       chat_server,
       client,
       activity_queue,
-      settings,
       secret,
+      state,
     }
   }
   pub fn pool(&self) -> &DbPool {
@@ -54,13 +83,26 @@ impl LemmyContext {
   pub fn activity_queue(&self) -> &QueueHandle {
     &self.activity_queue
   }
+  /// Settings are stored per instance, so that several instances can run in one process for
+  /// federation tests.
   pub fn settings(&self) -> Settings {
-    // TODO hacky solution to be able to hotload the settings.
-    Settings::get()
+    self.state.settings.read().clone()
+  }
+  /// Reads the config file again, after it was changed through the api
+  pub fn reload_settings(&self) -> Result<(), LemmyError> {
+    *self.state.settings.write() = Settings::init()?;
+    Ok(())
   }
   pub fn secret(&self) -> &Secret {
     &self.secret
   }
+  /// The federation allowlist and blocklist, as last loaded from the database
+  pub fn federation_lists(&self) -> FederationLists {
+    self.state.federation_lists.read().clone()
+  }
+  pub fn set_federation_lists(&self, lists: FederationLists) {
+    *self.state.federation_lists.write() = lists;
+  }
 }
 
 impl Clone for LemmyContext {
@@ -70,12 +112,24 @@ impl Clone for LemmyContext {
       chat_server: self.chat_server.clone(),
       client: self.client.clone(),
       activity_queue: self.activity_queue.clone(),
-      settings: self.settings.clone(),
       secret: self.secret.clone(),
+      state: self.state.clone(),
     }
   }
 }
 
+impl InstanceSettings for LemmyContext {
+  fn instance_settings(&self) -> Settings {
+    self.settings()
+  }
+}
+
+impl FetchSigning for LemmyContext {
+  fn fetch_signer(&self) -> &FetchSigner {
+    &self.state.fetch_signer
+  }
+}
+
 #[derive(Serialize)]
 struct WebsocketResponse<T> {
   op: String,
@@ -104,10 +158,14 @@ pub enum UserOperation {
   MarkCommentAsRead,
   SaveComment,
   CreateCommentLike,
+  CreateCommentReaction,
   CreateCommentReport,
   ResolveCommentReport,
   ListCommentReports,
+  ListCommentRevisions,
   CreatePostLike,
+  CreatePostReaction,
+  CreatePollVote,
   LockPost,
   StickyPost,
   MarkPostAsRead,
@@ -115,6 +173,7 @@ pub enum UserOperation {
   CreatePostReport,
   ResolvePostReport,
   ListPostReports,
+  ListPostRevisions,
   GetReportCount,
   GetUnreadCount,
   VerifyEmail,
@@ -129,13 +188,23 @@ pub enum UserOperation {
   GetUnreadRegistrationApplicationCount,
   ListRegistrationApplications,
   ApproveRegistrationApplication,
+  GetActivityQueueStats,
+  ListInstanceDeliveries,
+  GetInboxQueueStats,
+  GetFederationList,
+  AddFederationListEntry,
+  RemoveFederationListEntry,
+  DebugActivity,
+  RotateKeys,
   BanPerson,
   GetBannedPersons,
   Search,
   ResolveObject,
   MarkAllAsRead,
   SaveUserSettings,
+  MoveAccount,
   TransferCommunity,
+  MoveCommunity,
   LeaveAdmin,
   PasswordReset,
   PasswordChange,
@@ -167,14 +236,18 @@ pub enum UserOperationCrud {
   RemoveCommunity,
   // Post
   CreatePost,
+  CreateCrosspost,
   GetPost,
   GetPosts,
   EditPost,
   DeletePost,
   RemovePost,
+  ListScheduledPosts,
+  CancelScheduledPost,
   // Comment
   CreateComment,
   GetComment,
+  GetCommentContext,
   GetComments,
   EditComment,
   DeleteComment,
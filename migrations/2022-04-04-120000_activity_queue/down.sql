@@ -0,0 +1,2 @@
+drop table activity_queue_job;
+drop table activity_queue_stats;
@@ -0,0 +1,21 @@
+-- Outgoing activities are queued here instead of in memory, so that pending deliveries (and their
+-- retry backoff) survive a restart.
+create table activity_queue_job (
+  id text primary key,
+  queue text not null,
+  runner_id text,
+  job jsonb not null,
+  published timestamp not null default now(),
+  updated timestamp not null default now(),
+  -- Failed jobs are retried no earlier than this
+  next_queue timestamp
+);
+
+create index idx_activity_queue_job_queue on activity_queue_job (queue, next_queue, updated)
+  where runner_id is null;
+
+-- A single row holding the serialized queue statistics
+create table activity_queue_stats (
+  id int primary key,
+  stats jsonb not null
+);
@@ -0,0 +1,13 @@
+-- Delivery statistics per remote instance, used to suspend deliveries to dead instances
+create table instance_delivery (
+  id serial primary key,
+  domain text not null unique,
+  success_count bigint not null default 0,
+  failure_count bigint not null default 0,
+  consecutive_failures int not null default 0,
+  last_success timestamp,
+  last_failure timestamp,
+  suspended boolean not null default false,
+  next_probe timestamp,
+  published timestamp not null default now()
+);
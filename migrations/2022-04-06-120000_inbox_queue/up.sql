@@ -0,0 +1,16 @@
+-- Incoming activities with a valid signature, waiting to be processed by the inbox workers.
+-- Processed entries are kept for a while, to calculate the processing latency.
+create table inbox_queue (
+  id serial primary key,
+  ap_id text not null,
+  actor text not null,
+  inbox text not null,
+  data jsonb not null,
+  received timestamp not null default now(),
+  started timestamp,
+  finished timestamp,
+  error text
+);
+
+create index idx_inbox_queue_pending on inbox_queue (id) where started is null;
+create index idx_inbox_queue_finished on inbox_queue (finished);
@@ -0,0 +1,12 @@
+-- Progress of fetching older posts and comments of a remote community, once it is followed
+create table community_backfill (
+  id serial primary key,
+  community_id int references community on update cascade on delete cascade not null unique,
+  pages_fetched int not null default 0,
+  posts_fetched int not null default 0,
+  comments_fetched int not null default 0,
+  requests_used int not null default 0,
+  published timestamp not null default now(),
+  finished timestamp,
+  error text
+);
@@ -0,0 +1,2 @@
+drop table mod_federation_list;
+drop table federation_list;
@@ -0,0 +1,19 @@
+-- Federation allowlist and blocklist, managed by admins through the api
+create table federation_list (
+  id serial primary key,
+  domain text not null unique,
+  blocked boolean not null,
+  admin_person_id int references person on update cascade on delete set null,
+  reason text,
+  published timestamp not null default now()
+);
+
+create table mod_federation_list (
+  id serial primary key,
+  mod_person_id int references person on update cascade on delete cascade not null,
+  domain text not null,
+  blocked boolean not null,
+  removed boolean not null default false,
+  reason text,
+  when_ timestamp not null default now()
+);
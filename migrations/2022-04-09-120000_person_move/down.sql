@@ -0,0 +1,5 @@
+drop view person_alias_1, person_alias_2;
+alter table person drop column moved_to;
+alter table person drop column also_known_as;
+create view person_alias_1 as select * from person;
+create view person_alias_2 as select * from person;
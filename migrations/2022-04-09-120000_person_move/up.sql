@@ -0,0 +1,6 @@
+-- Account migration: other accounts of the same person, and the account this one moved to
+drop view person_alias_1, person_alias_2;
+alter table person add column moved_to varchar(255);
+alter table person add column also_known_as text[] not null default '{}';
+create view person_alias_1 as select * from person;
+create view person_alias_2 as select * from person;
@@ -0,0 +1,2 @@
+alter table community drop column moved_to;
+alter table community drop column also_known_as;
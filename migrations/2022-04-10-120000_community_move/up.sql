@@ -0,0 +1,3 @@
+-- Community migration: previous actors of the same community, and the community this one moved to
+alter table community add column moved_to varchar(255);
+alter table community add column also_known_as text[] not null default '{}';
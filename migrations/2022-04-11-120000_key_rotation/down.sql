@@ -0,0 +1,8 @@
+drop trigger site_key_rotated on site;
+drop trigger community_key_rotated on community;
+drop function actor_key_rotated;
+
+alter table site drop column previous_public_key;
+alter table site drop column key_rotated_at;
+alter table community drop column previous_public_key;
+alter table community drop column key_rotated_at;
@@ -0,0 +1,27 @@
+-- Keep the previous public key of an actor after its key changes, so that signatures made with it
+-- can still be verified for a while
+alter table site add column previous_public_key text;
+alter table site add column key_rotated_at timestamp;
+alter table community add column previous_public_key text;
+alter table community add column key_rotated_at timestamp;
+
+create or replace function actor_key_rotated()
+returns trigger language plpgsql
+as $$
+begin
+  if new.public_key is distinct from old.public_key then
+    new.previous_public_key = old.public_key;
+    new.key_rotated_at = now();
+  end if;
+  return new;
+end $$;
+
+create trigger site_key_rotated
+before update of public_key on site
+for each row
+execute procedure actor_key_rotated();
+
+create trigger community_key_rotated
+before update of public_key on community
+for each row
+execute procedure actor_key_rotated();
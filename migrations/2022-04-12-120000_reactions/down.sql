@@ -0,0 +1,2 @@
+drop table post_reaction;
+drop table comment_reaction;
@@ -0,0 +1,22 @@
+-- Emoji reactions, which are separate from votes and don't affect score or ranking. A person can
+-- react with several different emojis to the same item.
+create table post_reaction (
+  id serial primary key,
+  post_id int references post on update cascade on delete cascade not null,
+  person_id int references person on update cascade on delete cascade not null,
+  emoji text not null,
+  published timestamp not null default now(),
+  unique(post_id, person_id, emoji)
+);
+
+create table comment_reaction (
+  id serial primary key,
+  comment_id int references comment on update cascade on delete cascade not null,
+  person_id int references person on update cascade on delete cascade not null,
+  emoji text not null,
+  published timestamp not null default now(),
+  unique(comment_id, person_id, emoji)
+);
+
+create index idx_post_reaction_post on post_reaction (post_id);
+create index idx_comment_reaction_comment on comment_reaction (comment_id);
@@ -0,0 +1,5 @@
+drop trigger poll_option_vote_count on poll_vote;
+drop function poll_option_vote_count;
+drop table poll_vote;
+drop table poll_option;
+drop table poll;
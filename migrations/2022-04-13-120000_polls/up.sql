@@ -0,0 +1,43 @@
+-- Posts can have a poll, with answers from local and remote persons
+create table poll (
+  id serial primary key,
+  post_id int references post on update cascade on delete cascade not null unique,
+  multiple_choice boolean default false not null,
+  end_time timestamp,
+  published timestamp not null default now()
+);
+
+create table poll_option (
+  id serial primary key,
+  poll_id int references poll on update cascade on delete cascade not null,
+  name text not null,
+  -- For remote polls this is the count reported by the instance of the poll, plus local votes
+  -- which were cast since the poll was last received
+  vote_count int default 0 not null,
+  unique(poll_id, name)
+);
+
+create table poll_vote (
+  id serial primary key,
+  poll_option_id int references poll_option on update cascade on delete cascade not null,
+  person_id int references person on update cascade on delete cascade not null,
+  published timestamp not null default now(),
+  unique(poll_option_id, person_id)
+);
+
+create function poll_option_vote_count()
+returns trigger language plpgsql
+as $$
+begin
+  IF (TG_OP = 'INSERT') THEN
+    update poll_option set vote_count = vote_count + 1 where id = NEW.poll_option_id;
+  ELSIF (TG_OP = 'DELETE') THEN
+    update poll_option set vote_count = vote_count - 1 where id = OLD.poll_option_id;
+  END IF;
+  return null;
+end $$;
+
+create trigger poll_option_vote_count
+after insert or delete on poll_vote
+for each row
+execute procedure poll_option_vote_count();
@@ -0,0 +1,4 @@
+drop view comment_alias_1;
+alter table post drop column content_warning;
+alter table comment drop column content_warning;
+create view comment_alias_1 as select * from comment;
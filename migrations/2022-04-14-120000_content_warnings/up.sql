@@ -0,0 +1,5 @@
+-- Optional text shown instead of the content until the reader expands it, federated as summary
+drop view comment_alias_1;
+alter table post add column content_warning text;
+alter table comment add column content_warning text;
+create view comment_alias_1 as select * from comment;
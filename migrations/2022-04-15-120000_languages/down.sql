@@ -0,0 +1,6 @@
+drop view comment_alias_1;
+alter table post drop column language;
+alter table comment drop column language;
+create view comment_alias_1 as select * from comment;
+alter table local_user drop column default_language;
+alter table local_user drop column discussion_languages;
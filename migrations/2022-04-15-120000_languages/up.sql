@@ -0,0 +1,10 @@
+-- Language tag of the content, eg "en" or "pt-BR". Null means undetermined.
+drop view comment_alias_1;
+alter table post add column language text;
+alter table comment add column language text;
+create view comment_alias_1 as select * from comment;
+
+-- Language used for new posts and comments if none is given
+alter table local_user add column default_language text;
+-- Only show content in these languages (and content without language). Empty means all languages.
+alter table local_user add column discussion_languages text[] not null default '{}';
@@ -0,0 +1,4 @@
+drop trigger post_aggregates_scheduled_publish on post;
+drop function post_aggregates_scheduled_publish;
+drop index idx_post_scheduled_publish_time;
+alter table post drop column scheduled_publish_time;
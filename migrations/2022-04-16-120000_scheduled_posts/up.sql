@@ -0,0 +1,22 @@
+-- Posts with a publish time in the future are hidden until the scheduled task publishes them
+alter table post add column scheduled_publish_time timestamp;
+create index idx_post_scheduled_publish_time on post (scheduled_publish_time) where scheduled_publish_time is not null;
+
+-- Keep the aggregates in sync when a scheduled post is rescheduled or published
+create or replace function post_aggregates_scheduled_publish()
+returns trigger language plpgsql
+as $$
+begin
+  update post_aggregates pa
+  set published = NEW.published,
+    newest_comment_time = NEW.published,
+    newest_comment_time_necro = NEW.published
+  where pa.post_id = NEW.id;
+  return null;
+end $$;
+
+create trigger post_aggregates_scheduled_publish
+after update of published on post
+for each row
+when (OLD.scheduled_publish_time is not null and OLD.published is distinct from NEW.published)
+execute procedure post_aggregates_scheduled_publish();
@@ -0,0 +1,10 @@
+drop trigger comment_aggregates_child_count on comment;
+drop function comment_aggregates_child_count;
+alter table comment_aggregates drop column child_count;
+
+drop trigger comment_set_path on comment;
+drop function comment_set_path;
+drop view comment_alias_1;
+drop index idx_comment_path;
+alter table comment drop column path;
+create view comment_alias_1 as select * from comment;
@@ -0,0 +1,73 @@
+-- The path of a comment lists the ids from its top level comment down to the comment itself,
+-- so that whole threads or parts of them can be fetched at once.
+drop view comment_alias_1;
+alter table comment add column path integer[];
+
+with recursive comment_tree as (
+  select id, array[id] as path
+  from comment
+  where parent_id is null
+  union all
+  select c.id, t.path || c.id
+  from comment c
+  join comment_tree t on c.parent_id = t.id
+)
+update comment c
+set path = t.path
+from comment_tree t
+where c.id = t.id;
+
+alter table comment alter column path set not null;
+create index idx_comment_path on comment using gin (path);
+create view comment_alias_1 as select * from comment;
+
+create or replace function comment_set_path()
+returns trigger language plpgsql
+as $$
+begin
+  if NEW.parent_id is null then
+    NEW.path := array[NEW.id];
+  else
+    select c.path || NEW.id into NEW.path from comment c where c.id = NEW.parent_id;
+  end if;
+  return NEW;
+end $$;
+
+create trigger comment_set_path
+before insert on comment
+for each row
+execute procedure comment_set_path();
+
+-- Number of all replies below a comment, so that clients know when there are more to load
+alter table comment_aggregates add column child_count integer not null default 0;
+
+update comment_aggregates ca
+set child_count = d.child_count
+from (
+  select c.id, count(r.id) as child_count
+  from comment c
+  join comment r on r.path @> array[c.id] and r.id != c.id
+  group by c.id
+) d
+where ca.comment_id = d.id;
+
+create or replace function comment_aggregates_child_count()
+returns trigger language plpgsql
+as $$
+begin
+  IF (TG_OP = 'INSERT') THEN
+    update comment_aggregates ca
+    set child_count = child_count + 1
+    where ca.comment_id = any(NEW.path) and ca.comment_id != NEW.id;
+  ELSIF (TG_OP = 'DELETE') THEN
+    update comment_aggregates ca
+    set child_count = child_count - 1
+    where ca.comment_id = any(OLD.path) and ca.comment_id != OLD.id;
+  END IF;
+  return null;
+end $$;
+
+create trigger comment_aggregates_child_count
+after insert or delete on comment
+for each row
+execute procedure comment_aggregates_child_count();
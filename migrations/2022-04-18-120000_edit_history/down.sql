@@ -0,0 +1,9 @@
+alter table site drop column public_edit_history;
+
+drop trigger comment_revision_insert on comment;
+drop function comment_revision_insert;
+drop trigger post_revision_insert on post;
+drop function post_revision_insert;
+
+drop table comment_revision;
+drop table post_revision;
@@ -0,0 +1,60 @@
+-- Every edit of a post or comment keeps the previous version as a revision. This is done with
+-- triggers, so that local edits and federated updates are both covered.
+create table post_revision (
+  id serial primary key,
+  post_id int references post on update cascade on delete cascade not null,
+  name varchar(200) not null,
+  url text,
+  body text,
+  published timestamp not null
+);
+
+create index idx_post_revision_post on post_revision (post_id);
+
+create table comment_revision (
+  id serial primary key,
+  comment_id int references comment on update cascade on delete cascade not null,
+  content text not null,
+  published timestamp not null
+);
+
+create index idx_comment_revision_comment on comment_revision (comment_id);
+
+-- The revision is dated with the time at which the old version was written. Updates which mark
+-- the row as deleted (like permanent deletion of an account) are not recorded.
+create or replace function post_revision_insert()
+returns trigger language plpgsql
+as $$
+begin
+  if NEW.deleted = false
+    and (NEW.name is distinct from OLD.name
+      or NEW.url is distinct from OLD.url
+      or NEW.body is distinct from OLD.body) then
+    insert into post_revision (post_id, name, url, body, published)
+    values (OLD.id, OLD.name, OLD.url, OLD.body, coalesce(OLD.updated, OLD.published));
+  end if;
+  return NEW;
+end $$;
+
+create trigger post_revision_insert
+before update on post
+for each row
+execute procedure post_revision_insert();
+
+create or replace function comment_revision_insert()
+returns trigger language plpgsql
+as $$
+begin
+  if NEW.deleted = false and NEW.content is distinct from OLD.content then
+    insert into comment_revision (comment_id, content, published)
+    values (OLD.id, OLD.content, coalesce(OLD.updated, OLD.published));
+  end if;
+  return NEW;
+end $$;
+
+create trigger comment_revision_insert
+before update on comment
+for each row
+execute procedure comment_revision_insert();
+
+alter table site add column public_edit_history boolean default false not null;
@@ -0,0 +1,13 @@
+drop index idx_post_aggregates_published_score;
+drop index idx_comment_aggregates_published_score;
+
+drop trigger post_aggregates_vote_ranks on post_aggregates;
+drop trigger comment_aggregates_vote_ranks on comment_aggregates;
+drop function aggregates_vote_ranks;
+drop function rising_rank;
+drop function controversy_rank;
+
+alter table post_aggregates drop column controversy_rank;
+alter table post_aggregates drop column rising_rank;
+alter table comment_aggregates drop column controversy_rank;
+alter table comment_aggregates drop column rising_rank;
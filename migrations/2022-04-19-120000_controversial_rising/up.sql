@@ -0,0 +1,93 @@
+-- Controversy is high for posts and comments with many votes which are split evenly between up-
+-- and downvotes.
+--
+-- Rising is the rate of recent upvotes, where every upvote counts less the older it gets (halving
+-- about every 40 minutes). Instead of the decaying rate itself, which changes all the time, the
+-- rank stores ln(rate) + epoch / 3600. It only changes when someone votes and orders exactly like
+-- the current rate, so it can be indexed.
+alter table post_aggregates add column controversy_rank double precision not null default 0;
+alter table post_aggregates add column rising_rank double precision not null default 0;
+alter table comment_aggregates add column controversy_rank double precision not null default 0;
+alter table comment_aggregates add column rising_rank double precision not null default 0;
+
+create or replace function controversy_rank(upvotes numeric, downvotes numeric)
+returns double precision as $$
+begin
+  if upvotes <= 0 or downvotes <= 0 then
+    return 0;
+  end if;
+  return power(upvotes + downvotes, least(upvotes, downvotes) / greatest(upvotes, downvotes));
+end; $$
+language plpgsql
+immutable;
+
+create or replace function rising_rank(previous_rank double precision, new_upvotes numeric)
+returns double precision as $$
+declare
+  hours double precision := extract(epoch from timezone('utc', now())) / 3600;
+begin
+  -- The previous rate is clamped so that an old rank doesn't underflow
+  return ln(exp(greatest(previous_rank - hours, -700)) + new_upvotes) + hours;
+end; $$
+language plpgsql;
+
+create or replace function aggregates_vote_ranks()
+returns trigger language plpgsql
+as $$
+begin
+  NEW.controversy_rank := controversy_rank(NEW.upvotes, NEW.downvotes);
+  if NEW.upvotes > OLD.upvotes then
+    NEW.rising_rank := rising_rank(OLD.rising_rank, NEW.upvotes - OLD.upvotes);
+  end if;
+  return NEW;
+end $$;
+
+create trigger post_aggregates_vote_ranks
+before update of upvotes, downvotes on post_aggregates
+for each row
+execute procedure aggregates_vote_ranks();
+
+create trigger comment_aggregates_vote_ranks
+before update of upvotes, downvotes on comment_aggregates
+for each row
+execute procedure aggregates_vote_ranks();
+
+-- Fill in the ranks for existing votes, counting the upvotes of the last day for rising
+update post_aggregates set controversy_rank = controversy_rank(upvotes, downvotes);
+update comment_aggregates set controversy_rank = controversy_rank(upvotes, downvotes);
+
+update post_aggregates pa
+set rising_rank = r.rank
+from (
+  select post_id,
+    ln(sum(exp((extract(epoch from published) - extract(epoch from timezone('utc', now()))) / 3600)))
+      + extract(epoch from timezone('utc', now())) / 3600 as rank
+  from post_like
+  where score = 1 and published > timezone('utc', now()) - interval '1 day'
+  group by post_id
+) r
+where pa.post_id = r.post_id;
+
+update comment_aggregates ca
+set rising_rank = r.rank
+from (
+  select comment_id,
+    ln(sum(exp((extract(epoch from published) - extract(epoch from timezone('utc', now()))) / 3600)))
+      + extract(epoch from timezone('utc', now())) / 3600 as rank
+  from comment_like
+  where score = 1 and published > timezone('utc', now()) - interval '1 day'
+  group by comment_id
+) r
+where ca.comment_id = r.comment_id;
+
+create index idx_post_aggregates_stickied_controversy on post_aggregates (stickied desc, controversy_rank desc);
+create index idx_post_aggregates_controversy on post_aggregates (controversy_rank desc);
+create index idx_post_aggregates_stickied_rising on post_aggregates (stickied desc, rising_rank desc);
+create index idx_post_aggregates_rising on post_aggregates (rising_rank desc);
+
+create index idx_comment_aggregates_controversy on comment_aggregates (controversy_rank desc);
+create index idx_comment_aggregates_rising on comment_aggregates (rising_rank desc);
+
+-- The short top windows filter by time first
+create index idx_post_aggregates_published_score on post_aggregates (published desc, score desc);
+create index idx_comment_aggregates_published_score on comment_aggregates (published desc, score desc);
@@ -0,0 +1,25 @@
+drop trigger person_search_vector on person;
+drop function person_search_vector;
+drop view person_alias_1, person_alias_2;
+alter table person drop column search_vector;
+create view person_alias_1 as select * from person;
+create view person_alias_2 as select * from person;
+
+drop trigger community_search_vector on community;
+drop function community_search_vector;
+alter table community drop column search_vector;
+
+drop trigger comment_search_vector on comment;
+drop function comment_search_vector;
+drop view comment_alias_1;
+alter table comment drop column search_vector;
+create view comment_alias_1 as select * from comment;
+
+drop trigger post_search_vector on post;
+drop function post_search_vector;
+alter table post drop column search_vector;
+
+drop function text_search_headline;
+drop function text_search_query;
+drop function text_search_document;
+drop function text_search_config;
@@ -0,0 +1,156 @@
+-- Full text search over posts, comments, communities and users. Every row keeps a search vector
+-- with its words as written (the 'simple' configuration), and for posts and comments with a known
+-- language also stemmed in that language. Search queries are built the same way, so exact words
+-- always match, and other forms of a word match when the search is in the language of the content.
+
+-- Maps a language tag like "pt-BR" to the text search configuration used for stemming
+create or replace function text_search_config(language text)
+returns regconfig as $$
+begin
+  return case split_part(lower(language), '-', 1)
+    when 'da' then 'danish'
+    when 'de' then 'german'
+    when 'en' then 'english'
+    when 'es' then 'spanish'
+    when 'fi' then 'finnish'
+    when 'fr' then 'french'
+    when 'hu' then 'hungarian'
+    when 'it' then 'italian'
+    when 'nb' then 'norwegian'
+    when 'nl' then 'dutch'
+    when 'nn' then 'norwegian'
+    when 'no' then 'norwegian'
+    when 'pt' then 'portuguese'
+    when 'ro' then 'romanian'
+    when 'ru' then 'russian'
+    when 'sv' then 'swedish'
+    when 'tr' then 'turkish'
+    else 'simple'
+  end::regconfig;
+end; $$
+language plpgsql
+immutable;
+
+-- The title words are weighted higher than the rest of the text
+create or replace function text_search_document(language text, title text, body text)
+returns tsvector as $$
+declare
+  config regconfig := text_search_config(language);
+  document tsvector := setweight(to_tsvector('simple', coalesce(title, '')), 'A')
+    || setweight(to_tsvector('simple', coalesce(body, '')), 'B');
+begin
+  if config <> 'simple'::regconfig then
+    document := document
+      || setweight(to_tsvector(config, coalesce(title, '')), 'A')
+      || setweight(to_tsvector(config, coalesce(body, '')), 'B');
+  end if;
+  return document;
+end; $$
+language plpgsql
+immutable;
+
+-- Supports the web search syntax, like quoted phrases, "or" and "-" to exclude words
+create or replace function text_search_query(search_term text, language text)
+returns tsquery as $$
+declare
+  config regconfig := text_search_config(language);
+  query tsquery := websearch_to_tsquery('simple', search_term);
+begin
+  if config <> 'simple'::regconfig then
+    query := query || websearch_to_tsquery(config, search_term);
+  end if;
+  return query;
+end; $$
+language plpgsql
+immutable;
+
+-- Parts of the text around the matches, with the matched words in markdown bold
+create or replace function text_search_headline(document text, language text, query tsquery)
+returns text as $$
+begin
+  return ts_headline(text_search_config(language), document, query,
+    'StartSel=**, StopSel=**, MinWords=10, MaxWords=30, MaxFragments=2, FragmentDelimiter=" ... "');
+end; $$
+language plpgsql
+immutable;
+
+-- Post
+alter table post add column search_vector tsvector;
+update post set search_vector = text_search_document(language, name, body);
+alter table post alter column search_vector set not null;
+create index idx_post_search_vector on post using gin (search_vector);
+
+create or replace function post_search_vector()
+returns trigger language plpgsql
+as $$
+begin
+  NEW.search_vector := text_search_document(NEW.language, NEW.name, NEW.body);
+  return NEW;
+end $$;
+
+create trigger post_search_vector
+before insert or update of name, body, language on post
+for each row
+execute procedure post_search_vector();
+
+-- Comment
+drop view comment_alias_1;
+alter table comment add column search_vector tsvector;
+update comment set search_vector = text_search_document(language, null, content);
+alter table comment alter column search_vector set not null;
+create index idx_comment_search_vector on comment using gin (search_vector);
+create view comment_alias_1 as select * from comment;
+
+create or replace function comment_search_vector()
+returns trigger language plpgsql
+as $$
+begin
+  NEW.search_vector := text_search_document(NEW.language, null, NEW.content);
+  return NEW;
+end $$;
+
+create trigger comment_search_vector
+before insert or update of content, language on comment
+for each row
+execute procedure comment_search_vector();
+
+-- Community
+alter table community add column search_vector tsvector;
+update community set search_vector = text_search_document(null, name || ' ' || title, description);
+alter table community alter column search_vector set not null;
+create index idx_community_search_vector on community using gin (search_vector);
+
+create or replace function community_search_vector()
+returns trigger language plpgsql
+as $$
+begin
+  NEW.search_vector := text_search_document(null, NEW.name || ' ' || NEW.title, NEW.description);
+  return NEW;
+end $$;
+
+create trigger community_search_vector
+before insert or update of name, title, description on community
+for each row
+execute procedure community_search_vector();
+
+-- Person
+drop view person_alias_1, person_alias_2;
+alter table person add column search_vector tsvector;
+update person set search_vector = text_search_document(null, concat_ws(' ', name, display_name), bio);
+alter table person alter column search_vector set not null;
+create index idx_person_search_vector on person using gin (search_vector);
+create view person_alias_1 as select * from person;
+create view person_alias_2 as select * from person;
+
+create or replace function person_search_vector()
+returns trigger language plpgsql
+as $$
+begin
+  NEW.search_vector := text_search_document(null, concat_ws(' ', NEW.name, NEW.display_name), NEW.bio);
+  return NEW;
+end $$;
+
+create trigger person_search_vector
+before insert or update of name, display_name, bio on person
+for each row
+execute procedure person_search_vector();
@@ -0,0 +1 @@
+alter table site drop column federation_strict_allowlist;
@@ -0,0 +1,3 @@
+-- Stored with the federation allowlist and blocklist, so that admins can change it through the api.
+-- The value from the config file is imported on startup, as long as the lists are empty.
+alter table site add column federation_strict_allowlist boolean default true not null;
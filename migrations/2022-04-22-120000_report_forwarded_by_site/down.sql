@@ -0,0 +1,7 @@
+delete from post_report where creator_id is null;
+alter table post_report drop column forwarded_by_site_id;
+alter table post_report alter column creator_id set not null;
+
+delete from comment_report where creator_id is null;
+alter table comment_report drop column forwarded_by_site_id;
+alter table comment_report alter column creator_id set not null;
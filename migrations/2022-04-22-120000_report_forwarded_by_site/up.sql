@@ -0,0 +1,22 @@
+-- Reports which are forwarded by another instance dont include the original reporter. Instead of
+-- a person representing the instance, they reference the site which forwarded them.
+alter table post_report alter column creator_id drop not null;
+alter table post_report add column forwarded_by_site_id int references site on update cascade on delete cascade;
+alter table post_report add constraint post_report_creator_or_site
+  check (creator_id is not null or forwarded_by_site_id is not null);
+
+alter table comment_report alter column creator_id drop not null;
+alter table comment_report add column forwarded_by_site_id int references site on update cascade on delete cascade;
+alter table comment_report add constraint comment_report_creator_or_site
+  check (creator_id is not null or forwarded_by_site_id is not null);
+
+-- Move existing forwarded reports from the instance persons to their sites, and remove those persons
+update post_report r set creator_id = null, forwarded_by_site_id = s.id
+  from person p, site s where r.creator_id = p.id and p.actor_id = s.actor_id;
+update post_report r set resolver_id = null
+  from person p, site s where r.resolver_id = p.id and p.actor_id = s.actor_id;
+update comment_report r set creator_id = null, forwarded_by_site_id = s.id
+  from person p, site s where r.creator_id = p.id and p.actor_id = s.actor_id;
+update comment_report r set resolver_id = null
+  from person p, site s where r.resolver_id = p.id and p.actor_id = s.actor_id;
+delete from person p using site s where p.actor_id = s.actor_id;
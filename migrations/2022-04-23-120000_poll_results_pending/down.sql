@@ -0,0 +1 @@
+alter table poll drop column results_pending;
@@ -0,0 +1,3 @@
+-- Set when votes were cast in a local poll, so that the results are sent to other instances in
+-- batches instead of after every vote
+alter table poll add column results_pending boolean not null default false;
@@ -0,0 +1 @@
+drop index idx_post_url;
@@ -0,0 +1,2 @@
+-- Crossposts of a post are found by their url
+create index idx_post_url on post (url);
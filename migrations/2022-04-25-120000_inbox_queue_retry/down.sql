@@ -0,0 +1,2 @@
+alter table inbox_queue drop column attempts;
+alter table inbox_queue drop column retry_at;
@@ -0,0 +1,4 @@
+-- Activities which failed to process are retried a few times with increasing delays, as the
+-- sender can't retry them anymore once the inbox accepted them
+alter table inbox_queue add column attempts int not null default 0;
+alter table inbox_queue add column retry_at timestamp;
@@ -63,6 +63,7 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
             web::post().to(route_post_crud::<RemoveCommunity>),
           )
           .route("/transfer", web::post().to(route_post::<TransferCommunity>))
+          .route("/move", web::post().to(route_post::<MoveCommunity>))
           .route("/ban_user", web::post().to(route_post::<BanFromCommunity>))
           .route("/mod", web::post().to(route_post::<AddModToCommunity>))
           .route("/join", web::post().to(route_post::<CommunityJoin>))
@@ -76,6 +77,12 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.post())
           .route(web::post().to(route_post_crud::<CreatePost>)),
       )
+      .service(
+        web::resource("/post/crosspost")
+          .guard(guard::Post())
+          .wrap(rate_limit.post())
+          .route(web::post().to(route_post_crud::<CreateCrosspost>)),
+      )
       .service(
         web::scope("/post")
           .wrap(rate_limit.message())
@@ -89,8 +96,19 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           )
           .route("/lock", web::post().to(route_post::<LockPost>))
           .route("/sticky", web::post().to(route_post::<StickyPost>))
+          .route("/revisions", web::get().to(route_get::<ListPostRevisions>))
           .route("/list", web::get().to(route_get_crud::<GetPosts>))
+          .route(
+            "/scheduled",
+            web::get().to(route_get_crud::<ListScheduledPosts>),
+          )
+          .route(
+            "/scheduled/cancel",
+            web::post().to(route_post_crud::<CancelScheduledPost>),
+          )
           .route("/like", web::post().to(route_post::<CreatePostLike>))
+          .route("/react", web::post().to(route_post::<CreatePostReaction>))
+          .route("/poll/vote", web::post().to(route_post::<CreatePollVote>))
           .route("/save", web::put().to(route_post::<SavePost>))
           .route("/join", web::post().to(route_post::<PostJoin>))
           .route("/report", web::post().to(route_post::<CreatePostReport>))
@@ -116,6 +134,10 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
         web::scope("/comment")
           .wrap(rate_limit.message())
           .route("", web::get().to(route_get_crud::<GetComment>))
+          .route(
+            "/context",
+            web::get().to(route_get_crud::<GetCommentContext>),
+          )
           .route("", web::put().to(route_post_crud::<EditComment>))
           .route("/delete", web::post().to(route_post_crud::<DeleteComment>))
           .route("/remove", web::post().to(route_post_crud::<RemoveComment>))
@@ -124,7 +146,15 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
             web::post().to(route_post::<MarkCommentAsRead>),
           )
           .route("/like", web::post().to(route_post::<CreateCommentLike>))
+          .route(
+            "/react",
+            web::post().to(route_post::<CreateCommentReaction>),
+          )
           .route("/save", web::put().to(route_post::<SaveComment>))
+          .route(
+            "/revisions",
+            web::get().to(route_get::<ListCommentRevisions>),
+          )
           .route("/list", web::get().to(route_get_crud::<GetComments>))
           .route("/report", web::post().to(route_post::<CreateCommentReport>))
           .route(
@@ -206,6 +236,7 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
             "/save_user_settings",
             web::put().to(route_post::<SaveUserSettings>),
           )
+          .route("/move", web::post().to(route_post::<MoveAccount>))
           .route(
             "/change_password",
             web::put().to(route_post::<ChangePassword>),
@@ -231,7 +262,36 @@ pub fn config(cfg: &mut web::ServiceConfig, rate_limit: &RateLimit) {
           .route(
             "/registration_application/approve",
             web::put().to(route_post::<ApproveRegistrationApplication>),
-          ),
+          )
+          .route(
+            "/activity_queue",
+            web::get().to(route_get::<GetActivityQueueStats>),
+          )
+          .route(
+            "/instance_delivery/list",
+            web::get().to(route_get::<ListInstanceDeliveries>),
+          )
+          .route(
+            "/inbox_queue",
+            web::get().to(route_get::<GetInboxQueueStats>),
+          )
+          .route(
+            "/federation_list",
+            web::get().to(route_get::<GetFederationList>),
+          )
+          .route(
+            "/federation_list",
+            web::post().to(route_post::<AddFederationListEntry>),
+          )
+          .route(
+            "/federation_list/remove",
+            web::post().to(route_post::<RemoveFederationListEntry>),
+          )
+          .route(
+            "/debug_activity",
+            web::post().to(route_post::<DebugActivity>),
+          )
+          .route("/rotate_keys", web::post().to(route_post::<RotateKeys>)),
       ),
   );
 }
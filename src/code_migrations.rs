@@ -123,6 +123,8 @@ fn community_updates_2020_04_02(
       followers_url: None,
       inbox_url: None,
       shared_inbox_url: None,
+      moved_to: None,
+      also_known_as: None,
     };
 
     Community::update(conn, ccommunity.id, &form)?;
@@ -11,6 +11,13 @@ use doku::json::{AutoComments, Formatting};
 use lemmy_api::match_websocket_operation;
 use lemmy_api_common::{blocking, check_private_instance_and_federation_enabled};
 use lemmy_api_crud::match_websocket_operation_crud;
+use lemmy_apub::{
+  activity_queue::{ActivityQueueStorage, InstanceHealthTracker},
+  federation_list::init_federation_lists,
+  fetcher::community_backfill::resume_community_backfills,
+  http::inbox_queue::start_inbox_workers,
+  objects::instance::init_authorized_fetch,
+};
 use lemmy_apub_lib::activity_queue::create_activity_queue;
 use lemmy_db_schema::{get_database_url_from_env, source::secret::Secret};
 use lemmy_routes::{feeds, images, nodeinfo, webfinger};
@@ -28,7 +35,7 @@ use lemmy_utils::{
   LemmyError,
   REQWEST_TIMEOUT,
 };
-use lemmy_websocket::{chat_server::ChatServer, LemmyContext};
+use lemmy_websocket::{chat_server::ChatServer, InstanceState, LemmyContext};
 use parking_lot::Mutex;
 use reqwest::Client;
 use reqwest_middleware::ClientBuilder;
@@ -102,12 +109,20 @@ async fn main() -> Result<(), LemmyError> {
 
   let client = ClientBuilder::new(client).with(TracingMiddleware).build();
 
-  let queue_manager = create_activity_queue(client.clone(), settings.federation.worker_count);
+  let queue_storage = ActivityQueueStorage::new(pool.clone()).await?;
+  let instance_health = InstanceHealthTracker::new(pool.clone(), &settings);
+  let queue_manager = create_activity_queue(
+    queue_storage,
+    Arc::new(instance_health),
+    client.clone(),
+    settings.federation.worker_count,
+  );
 
   let activity_queue = queue_manager.queue_handle().clone();
 
   check_private_instance_and_federation_enabled(&pool, &settings).await?;
 
+  let state = InstanceState::new(settings.clone());
   let chat_server = ChatServer::startup(
     pool.clone(),
     rate_limiter.clone(),
@@ -115,22 +130,34 @@ async fn main() -> Result<(), LemmyError> {
     |c, i, o, d| Box::pin(match_websocket_operation_crud(c, i, o, d)),
     client.clone(),
     activity_queue.clone(),
-    settings.clone(),
     secret.clone(),
+    state.clone(),
   )
   .start();
 
+  let context = LemmyContext::create(
+    pool.clone(),
+    chat_server.clone(),
+    client.clone(),
+    activity_queue.clone(),
+    secret.clone(),
+    state.clone(),
+  );
+  init_federation_lists(&context).await?;
+  init_authorized_fetch(&context).await?;
+  scheduled_tasks::start_scheduled_post_publisher(context.clone());
+  scheduled_tasks::start_poll_results_sender(context.clone());
+  scheduled_tasks::start_federation_lists_refresher(context.clone());
+
+  if settings.federation.enabled {
+    start_inbox_workers(context.clone()).await?;
+    resume_community_backfills(&context).await?;
+  }
+
   // Create Http server with websocket support
   let settings_bind = settings.clone();
   HttpServer::new(move || {
-    let context = LemmyContext::create(
-      pool.clone(),
-      chat_server.to_owned(),
-      client.clone(),
-      activity_queue.to_owned(),
-      settings.to_owned(),
-      secret.to_owned(),
-    );
+    let context = context.clone();
     let rate_limiter = rate_limiter.clone();
     App::new()
       .wrap(actix_web::middleware::Logger::default())
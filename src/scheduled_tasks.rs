@@ -1,11 +1,39 @@
 // Scheduler, and trait for .seconds(), .minutes(), etc.
 use clokwerk::{Scheduler, TimeUnits};
 // Import week days and WeekDay
+use actix_rt::time::sleep;
 use diesel::{sql_query, PgConnection, RunQueryDsl};
-use lemmy_db_schema::{source::activity::Activity, DbPool};
+use lemmy_api_common::blocking;
+use lemmy_apub::{
+  activities::poll::send_pending_poll_results,
+  federation_list::refresh_federation_lists,
+  objects::person::ApubPerson,
+  protocol::activities::{create_or_update::post::CreateOrUpdatePost, CreateOrUpdateType},
+};
+use lemmy_db_schema::{
+  naive_now,
+  source::{
+    activity::Activity,
+    community::Community,
+    inbox_queue::InboxQueueEntry,
+    person::Person,
+    post::Post,
+    site::Site,
+  },
+  traits::Crud,
+  DbPool,
+};
 use lemmy_utils::LemmyError;
+use lemmy_websocket::{send::send_post_ws_message, LemmyContext, UserOperationCrud};
 use std::{thread, time::Duration};
-use tracing::info;
+use tracing::{info, warn};
+
+/// How often to check for scheduled posts which are due
+const SCHEDULED_POSTS_INTERVAL: Duration = Duration::from_secs(60);
+/// How often to send the results of local polls which got new votes
+const POLL_RESULTS_INTERVAL: Duration = Duration::from_secs(60);
+/// How often to reload the federation lists, to pick up changes made through another process
+const FEDERATION_LISTS_INTERVAL: Duration = Duration::from_secs(60);
 
 /// Schedules various cleanup tasks for lemmy in a background thread
 pub fn setup(pool: DbPool) -> Result<(), LemmyError> {
@@ -22,6 +50,8 @@ pub fn setup(pool: DbPool) -> Result<(), LemmyError> {
     active_counts(&conn);
     update_banned_when_expired(&conn);
     reindex_aggregates_tables(&conn, true);
+    clear_old_inbox_queue(&conn);
+    expire_previous_keys(&conn);
   });
 
   let conn = pool.get()?;
@@ -37,6 +67,86 @@ pub fn setup(pool: DbPool) -> Result<(), LemmyError> {
   }
 }
 
+/// Publishes scheduled posts once their time has come. Unlike the cleanup tasks above, this needs
+/// to federate and send websocket messages, so it runs on the async runtime.
+pub fn start_scheduled_post_publisher(context: LemmyContext) {
+  actix_rt::spawn(async move {
+    loop {
+      if let Err(e) = publish_scheduled_posts(&context).await {
+        warn!("Failed to publish scheduled posts: {}", e);
+      }
+      sleep(SCHEDULED_POSTS_INTERVAL).await;
+    }
+  });
+}
+
+/// Sends the results of local polls to other instances, at most once per interval for each poll.
+pub fn start_poll_results_sender(context: LemmyContext) {
+  actix_rt::spawn(async move {
+    loop {
+      sleep(POLL_RESULTS_INTERVAL).await;
+      if let Err(e) = send_pending_poll_results(&context).await {
+        warn!("Failed to send poll results: {}", e);
+      }
+    }
+  });
+}
+
+/// The federation lists are kept in memory, and only reloaded right away in the process which
+/// handled the change. Other processes using the same database pick it up here.
+pub fn start_federation_lists_refresher(context: LemmyContext) {
+  actix_rt::spawn(async move {
+    loop {
+      sleep(FEDERATION_LISTS_INTERVAL).await;
+      if let Err(e) = refresh_federation_lists(&context).await {
+        warn!("Failed to reload federation lists: {}", e);
+      }
+    }
+  });
+}
+
+async fn publish_scheduled_posts(context: &LemmyContext) -> Result<(), LemmyError> {
+  let posts = blocking(context.pool(), Post::list_scheduled_due).await??;
+  for post in posts {
+    let post_id = post.id;
+    // A failed post stays scheduled and is retried next time, so one shouldnt hold back the others
+    if let Err(e) = publish_scheduled_post(post, context).await {
+      warn!("Failed to publish scheduled post {}: {}", post_id, e);
+    }
+  }
+  Ok(())
+}
+
+/// Sends the same apub activity and websocket message as for a newly created post. The post is
+/// only marked as published once the activity is queued, so that it can't become visible locally
+/// without being federated. If marking it fails, the activity is sent again on the next run, which
+/// other instances treat as an update.
+async fn publish_scheduled_post(mut post: Post, context: &LemmyContext) -> Result<(), LemmyError> {
+  let creator_id = post.creator_id;
+  let creator: ApubPerson = blocking(context.pool(), move |conn| Person::read(conn, creator_id))
+    .await??
+    .into();
+  let post_id = post.id;
+  let published = naive_now();
+  post.published = published;
+  post.scheduled_publish_time = None;
+  CreateOrUpdatePost::send(post.into(), &creator, CreateOrUpdateType::Create, context).await?;
+
+  blocking(context.pool(), move |conn| {
+    Post::mark_published(conn, post_id, published)
+  })
+  .await??;
+  send_post_ws_message(
+    post_id,
+    UserOperationCrud::CreatePost,
+    None,
+    Some(creator_id),
+    context,
+  )
+  .await?;
+  Ok(())
+}
+
 /// Reindex the aggregates tables every one hour
 /// This is necessary because hot_rank is actually a mutable function:
 /// https://dba.stackexchange.com/questions/284052/how-to-create-an-index-based-on-a-time-based-function-in-postgres?noredirect=1#comment555727_284052
@@ -65,6 +175,21 @@ fn clear_old_activities(conn: &PgConnection) {
   info!("Done.");
 }
 
+/// Clear processed incoming activities, they are only kept for the queue stats
+fn clear_old_inbox_queue(conn: &PgConnection) {
+  info!("Clearing old inbox queue entries...");
+  InboxQueueEntry::delete_olds(conn).expect("clear old inbox queue entries");
+  info!("Done.");
+}
+
+/// Stop accepting the previous keys of actors once the grace period after a key rotation is over
+fn expire_previous_keys(conn: &PgConnection) {
+  info!("Expiring previous actor keys...");
+  Site::expire_previous_keys(conn).expect("expire previous site keys");
+  Community::expire_previous_keys(conn).expect("expire previous community keys");
+  info!("Done.");
+}
+
 /// Re-calculate the site and community active counts every 12 hours
 fn active_counts(conn: &PgConnection) {
   info!("Updating active site and community aggregates ...");
@@ -0,0 +1,239 @@
+//! Starts several Lemmy instances in one process, each with its own database and http server on
+//! localhost. Activities are sent and received synchronously, same as with
+//! `APUB_TESTING_SEND_SYNC` in the docker based api tests, so that the result of an action can be
+//! checked on other instances right after the api call returns.
+//!
+//! The databases are created next to the one from `LEMMY_DATABASE_URL`, with the instance name as
+//! suffix, and are recreated every time the instances are started.
+//!
+//! Settings, federation lists and other state which can change at runtime are kept per instance,
+//! see `InstanceState`.
+
+use actix::Actor;
+use actix_web::{dev::ServerHandle, web::Data, App, HttpServer};
+use background_jobs::Manager;
+use diesel::{
+  r2d2::{ConnectionManager, Pool},
+  Connection,
+  PgConnection,
+  RunQueryDsl,
+};
+use lemmy_api::{match_websocket_operation, Perform};
+use lemmy_api_common::{blocking, person::Login, site::GetSite};
+use lemmy_api_crud::{match_websocket_operation_crud, PerformCrud};
+use lemmy_apub::{
+  activity_queue::{ActivityQueueStorage, InstanceHealthTracker},
+  federation_list::init_federation_lists,
+};
+use lemmy_apub_lib::activity_queue::create_activity_queue;
+use lemmy_db_schema::{get_database_url_from_env, source::secret::Secret, DbPool};
+use lemmy_routes::{nodeinfo, webfinger};
+use lemmy_server::{api_routes, code_migrations::run_advanced_migrations};
+use lemmy_utils::{
+  rate_limit::{rate_limiter::RateLimiter, RateLimit},
+  settings::structs::{FederationConfig, Settings, SetupConfig},
+  LemmyError,
+  Sensitive,
+  REQWEST_TIMEOUT,
+};
+use lemmy_websocket::{chat_server::ChatServer, InstanceState, LemmyContext};
+use parking_lot::Mutex;
+use reqwest::Client;
+use reqwest_middleware::ClientBuilder;
+use std::{
+  env,
+  net::{IpAddr, Ipv4Addr, SocketAddr},
+  sync::Arc,
+};
+use url::Url;
+
+embed_migrations!();
+
+/// Ports are chosen so that they don't conflict with the docker based federation tests.
+const FIRST_PORT: u16 = 8561;
+const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+const ADMIN_PASSWORD: &str = "lemmylemmy";
+
+pub struct TestInstance {
+  pub context: Data<LemmyContext>,
+  /// Login token for the admin, who is created together with the site
+  pub admin_jwt: Sensitive<String>,
+  server: ServerHandle,
+  _queue_manager: Manager,
+}
+
+impl TestInstance {
+  /// Starts one instance for each of the names, with hostnames like `lemmy-alpha:8561`. Has to be
+  /// called from within an actix system, eg in an `#[actix_web::test]`.
+  pub async fn start_all(names: &[&str]) -> Result<Vec<TestInstance>, LemmyError> {
+    env::set_var("APUB_TESTING_SEND_SYNC", "1");
+
+    let hosts: Vec<(String, u16)> = names
+      .iter()
+      .zip(FIRST_PORT..)
+      .map(|(name, port)| (format!("lemmy-{}", name), port))
+      .collect();
+    // Other instances are reached through localhost, without any changes to the system resolver
+    let mut client = Client::builder().timeout(REQWEST_TIMEOUT);
+    for (host, port) in &hosts {
+      client = client.resolve(host, SocketAddr::new(LOCALHOST, *port));
+    }
+    let client = ClientBuilder::new(client.build()?).build();
+
+    let mut instances = vec![];
+    for (name, (host, port)) in names.iter().zip(hosts) {
+      let settings = Settings {
+        hostname: format!("{}:{}", host, port),
+        bind: LOCALHOST,
+        port,
+        tls_enabled: false,
+        federation: FederationConfig {
+          enabled: true,
+          ..Default::default()
+        },
+        setup: Some(SetupConfig {
+          admin_username: format!("lemmy_{}", name),
+          admin_password: ADMIN_PASSWORD.to_string(),
+          site_name: format!("lemmy-{}", name),
+          open_registration: Some(true),
+          ..Default::default()
+        }),
+        ..Default::default()
+      };
+      instances.push(TestInstance::start(name, settings, client.clone()).await?);
+    }
+    Ok(instances)
+  }
+
+  async fn start(
+    name: &str,
+    settings: Settings,
+    client: reqwest_middleware::ClientWithMiddleware,
+  ) -> Result<TestInstance, LemmyError> {
+    let pool = create_database(name)?;
+    let protocol_and_hostname = settings.get_protocol_and_hostname();
+    let secret = blocking(&pool, move |conn| {
+      embedded_migrations::run(conn)?;
+      run_advanced_migrations(conn, &protocol_and_hostname)?;
+      Ok(Secret::init(conn)?) as Result<Secret, LemmyError>
+    })
+    .await??;
+
+    let rate_limiter = RateLimit {
+      rate_limiter: Arc::new(Mutex::new(RateLimiter::default())),
+      rate_limit_config: settings.rate_limit.to_owned().unwrap_or_default(),
+    };
+    let queue_storage = ActivityQueueStorage::new(pool.clone()).await?;
+    let instance_health = InstanceHealthTracker::new(pool.clone(), &settings);
+    let queue_manager = create_activity_queue(
+      queue_storage,
+      Arc::new(instance_health),
+      client.clone(),
+      settings.federation.worker_count,
+    );
+    let activity_queue = queue_manager.queue_handle().clone();
+
+    let state = InstanceState::new(settings.clone());
+    let chat_server = ChatServer::startup(
+      pool.clone(),
+      rate_limiter.clone(),
+      |c, i, o, d| Box::pin(match_websocket_operation(c, i, o, d)),
+      |c, i, o, d| Box::pin(match_websocket_operation_crud(c, i, o, d)),
+      client.clone(),
+      activity_queue.clone(),
+      secret.clone(),
+      state.clone(),
+    )
+    .start();
+    let context = LemmyContext::create(pool, chat_server, client, activity_queue, secret, state);
+    init_federation_lists(&context).await?;
+
+    let server_context = context.clone();
+    let server_settings = settings.clone();
+    let server = HttpServer::new(move || {
+      let rate_limiter = rate_limiter.clone();
+      App::new()
+        .app_data(Data::new(server_context.clone()))
+        .app_data(Data::new(rate_limiter.clone()))
+        .configure(|cfg| api_routes::config(cfg, &rate_limiter))
+        .configure(|cfg| lemmy_apub::http::routes::config(cfg, &server_settings))
+        .configure(nodeinfo::config)
+        .configure(|cfg| webfinger::config(cfg, &server_settings))
+    })
+    .workers(2)
+    .bind((settings.bind, settings.port))?
+    .run();
+    let server_handle = server.handle();
+    actix_web::rt::spawn(server);
+
+    let context = Data::new(context);
+    // Creates the admin and the site from the setup config
+    GetSite { auth: None }.perform(&context, None).await?;
+    let admin_jwt = login(&context, &format!("lemmy_{}", name), ADMIN_PASSWORD).await?;
+
+    Ok(TestInstance {
+      context,
+      admin_jwt,
+      server: server_handle,
+      _queue_manager: queue_manager,
+    })
+  }
+
+  pub fn pool(&self) -> &DbPool {
+    self.context.pool()
+  }
+
+  /// Calls an api method as if it was received over http.
+  pub async fn api<T: Perform>(&self, data: T) -> Result<T::Response, LemmyError> {
+    data.perform(&self.context, None).await
+  }
+
+  /// Same as `api`, for the crud methods.
+  pub async fn crud<T: PerformCrud>(&self, data: T) -> Result<T::Response, LemmyError> {
+    data.perform(&self.context, None).await
+  }
+
+  pub async fn stop(self) {
+    self.server.stop(true).await;
+  }
+}
+
+async fn login(
+  context: &Data<LemmyContext>,
+  username: &str,
+  password: &str,
+) -> Result<Sensitive<String>, LemmyError> {
+  let login = Login {
+    username_or_email: username.to_string().into(),
+    password: password.to_string().into(),
+  };
+  let jwt = login
+    .perform(context, None)
+    .await?
+    .jwt
+    .expect("login returns jwt");
+  Ok(jwt)
+}
+
+/// Drops and recreates the database for the instance, and returns a connection pool for it.
+fn create_database(name: &str) -> Result<DbPool, LemmyError> {
+  let base_url =
+    get_database_url_from_env().expect("LEMMY_DATABASE_URL needs to be set for federation tests");
+  let mut db_url = Url::parse(&base_url)?;
+  let db_name = format!("{}_{}", db_url.path().trim_start_matches('/'), name);
+  db_url.set_path(&db_name);
+
+  let conn = PgConnection::establish(&base_url)?;
+  // Connections from a previous test run may still be open
+  diesel::sql_query(format!(
+    "select pg_terminate_backend(pid) from pg_stat_activity where datname = '{}' and pid <> pg_backend_pid()",
+    db_name
+  ))
+  .execute(&conn)?;
+  diesel::sql_query(format!("drop database if exists {}", db_name)).execute(&conn)?;
+  diesel::sql_query(format!("create database {}", db_name)).execute(&conn)?;
+
+  let manager = ConnectionManager::<PgConnection>::new(db_url.as_str());
+  Ok(Pool::builder().max_size(5).build(manager)?)
+}
@@ -0,0 +1,519 @@
+//! Federation tests which run several instances in one process, see `harness.rs`. They need a
+//! running postgres, configured with `LEMMY_DATABASE_URL`, same as the database tests.
+
+#[macro_use]
+extern crate diesel_migrations;
+
+mod harness;
+
+use harness::TestInstance;
+use lemmy_api_common::{
+  blocking,
+  community::{BanFromCommunity, CreateCommunity, EditCommunity, FollowCommunity, MoveCommunity},
+  person::{BanPerson, Register},
+  post::{CreateCrosspost, CreatePost, CreatePostLike, DeletePost, GetPost},
+  site::ResolveObject,
+};
+use lemmy_apub_lib::signatures::sign_and_send;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PersonId, PostId},
+  source::{community::Community, person::Person, post::Post},
+  traits::{ApubActor, Crud},
+  DbPool,
+};
+use lemmy_db_views::post_view::PostView;
+use lemmy_db_views_actor::community_view::CommunityView;
+use lemmy_utils::{LemmyError, Sensitive};
+use serial_test::serial;
+use url::Url;
+
+/// Creates a community on alpha, which the admin of beta follows. Returns the id of the community
+/// on both instances.
+async fn follow_community(alpha: &TestInstance, beta: &TestInstance) -> (CommunityId, CommunityId) {
+  let form = CreateCommunity {
+    name: "main".to_string(),
+    title: "Main".to_string(),
+    description: None,
+    icon: None,
+    banner: None,
+    nsfw: None,
+    auth: alpha.admin_jwt.clone(),
+  };
+  let alpha_community = alpha.crud(form).await.unwrap().community_view.community;
+
+  let resolve = ResolveObject {
+    q: alpha_community.actor_id.to_string(),
+    auth: Some(beta.admin_jwt.clone()),
+  };
+  let beta_community = beta
+    .api(resolve)
+    .await
+    .unwrap()
+    .community
+    .unwrap()
+    .community;
+  let follow = FollowCommunity {
+    community_id: beta_community.id,
+    follow: true,
+    auth: beta.admin_jwt.clone(),
+  };
+  beta.api(follow).await.unwrap();
+
+  (alpha_community.id, beta_community.id)
+}
+
+async fn create_post(instance: &TestInstance, community_id: CommunityId) -> Post {
+  create_post_as(instance, community_id, instance.admin_jwt.clone())
+    .await
+    .unwrap()
+}
+
+async fn create_post_as(
+  instance: &TestInstance,
+  community_id: CommunityId,
+  auth: Sensitive<String>,
+) -> Result<Post, LemmyError> {
+  let form = CreatePost {
+    name: "A federated post".to_string(),
+    community_id,
+    url: None,
+    body: None,
+    honeypot: None,
+    nsfw: None,
+    content_warning: None,
+    language: None,
+    poll: None,
+    scheduled_publish_time: None,
+    auth,
+  };
+  Ok(instance.crud(form).await?.post_view.post)
+}
+
+async fn read_person(pool: &DbPool, person_id: PersonId) -> Person {
+  blocking(pool, move |conn| Person::read(conn, person_id))
+    .await
+    .unwrap()
+    .unwrap()
+}
+
+async fn read_community(pool: &DbPool, community_id: CommunityId) -> Community {
+  blocking(pool, move |conn| Community::read(conn, community_id))
+    .await
+    .unwrap()
+    .unwrap()
+}
+
+async fn read_post(pool: &DbPool, ap_id: Url) -> Option<Post> {
+  blocking(pool, move |conn| Post::read_from_apub_id(conn, ap_id))
+    .await
+    .unwrap()
+    .unwrap()
+}
+
+#[actix_web::test]
+#[serial]
+async fn test_follow_post_and_vote() {
+  let mut instances = TestInstance::start_all(&["alpha", "beta"]).await.unwrap();
+  let beta = instances.pop().unwrap();
+  let alpha = instances.pop().unwrap();
+
+  let (alpha_community_id, beta_community_id) = follow_community(&alpha, &beta).await;
+  let community = blocking(alpha.pool(), move |conn| {
+    CommunityView::read(conn, alpha_community_id, None)
+  })
+  .await
+  .unwrap()
+  .unwrap();
+  assert_eq!(2, community.counts.subscribers);
+
+  // A post in the community is sent to the follower on beta
+  let alpha_post = create_post(&alpha, alpha_community_id).await;
+  let beta_post = read_post(beta.pool(), alpha_post.ap_id.clone().into())
+    .await
+    .unwrap();
+  assert_eq!(alpha_post.name, beta_post.name);
+
+  // The vote from beta is counted on alpha
+  let like = CreatePostLike {
+    post_id: beta_post.id,
+    score: 1,
+    auth: beta.admin_jwt.clone(),
+  };
+  beta.api(like).await.unwrap();
+  let alpha_post_id = alpha_post.id;
+  let alpha_post_view = blocking(alpha.pool(), move |conn| {
+    PostView::read(conn, alpha_post_id, None)
+  })
+  .await
+  .unwrap()
+  .unwrap();
+  assert_eq!(2, alpha_post_view.counts.score);
+
+  // A post from beta in the remote community arrives on alpha
+  let beta_post = create_post(&beta, beta_community_id).await;
+  let alpha_post = read_post(alpha.pool(), beta_post.ap_id.into()).await;
+  assert!(alpha_post.is_some());
+
+  alpha.stop().await;
+  beta.stop().await;
+}
+
+#[actix_web::test]
+#[serial]
+async fn test_delete_post() {
+  let mut instances = TestInstance::start_all(&["alpha", "beta"]).await.unwrap();
+  let beta = instances.pop().unwrap();
+  let alpha = instances.pop().unwrap();
+
+  let (alpha_community_id, _) = follow_community(&alpha, &beta).await;
+  let alpha_post = create_post(&alpha, alpha_community_id).await;
+  let delete = DeletePost {
+    post_id: alpha_post.id,
+    deleted: true,
+    auth: alpha.admin_jwt.clone(),
+  };
+  alpha.crud(delete).await.unwrap();
+
+  let beta_post = read_post(beta.pool(), alpha_post.ap_id.into())
+    .await
+    .unwrap();
+  assert!(beta_post.deleted);
+
+  alpha.stop().await;
+  beta.stop().await;
+}
+
+#[actix_web::test]
+#[serial]
+async fn test_ban_from_community() {
+  let mut instances = TestInstance::start_all(&["alpha", "beta"]).await.unwrap();
+  let beta = instances.pop().unwrap();
+  let alpha = instances.pop().unwrap();
+
+  let (alpha_community_id, beta_community_id) = follow_community(&alpha, &beta).await;
+  let beta_post = create_post(&beta, beta_community_id).await;
+
+  // Ban the author of the post, as seen from alpha
+  let alpha_post = read_post(alpha.pool(), beta_post.ap_id.into())
+    .await
+    .unwrap();
+  let ban = BanFromCommunity {
+    community_id: alpha_community_id,
+    person_id: alpha_post.creator_id,
+    ban: true,
+    remove_data: None,
+    reason: None,
+    expires: None,
+    auth: alpha.admin_jwt.clone(),
+  };
+  alpha.api(ban).await.unwrap();
+
+  // The ban is applied on beta as well, so the user can't post in the community anymore
+  let form = CreatePost {
+    name: "Post after ban".to_string(),
+    community_id: beta_community_id,
+    url: None,
+    body: None,
+    honeypot: None,
+    nsfw: None,
+    content_warning: None,
+    language: None,
+    poll: None,
+    scheduled_publish_time: None,
+    auth: beta.admin_jwt.clone(),
+  };
+  assert!(beta.crud(form).await.is_err());
+
+  alpha.stop().await;
+  beta.stop().await;
+}
+
+#[actix_web::test]
+#[serial]
+async fn test_site_ban() {
+  let mut instances = TestInstance::start_all(&["alpha", "beta"]).await.unwrap();
+  let beta = instances.pop().unwrap();
+  let alpha = instances.pop().unwrap();
+
+  let (_, beta_community_id) = follow_community(&alpha, &beta).await;
+
+  // A user of beta posts in the community, so that alpha knows them
+  let register = Register {
+    username: "beta_user".to_string(),
+    password: "lemmylemmy".to_string().into(),
+    password_verify: "lemmylemmy".to_string().into(),
+    show_nsfw: false,
+    email: None,
+    captcha_uuid: None,
+    captcha_answer: None,
+    honeypot: None,
+    answer: None,
+  };
+  let user_jwt = beta.crud(register).await.unwrap().jwt.unwrap();
+  let beta_post = create_post_as(&beta, beta_community_id, user_jwt.clone())
+    .await
+    .unwrap();
+  let alpha_post = read_post(alpha.pool(), beta_post.ap_id.into())
+    .await
+    .unwrap();
+
+  // The admin of beta bans the user, which is applied on alpha as well
+  let ban = BanPerson {
+    person_id: beta_post.creator_id,
+    ban: true,
+    remove_data: None,
+    reason: None,
+    expires: None,
+    auth: beta.admin_jwt.clone(),
+  };
+  beta.api(ban).await.unwrap();
+  assert!(
+    read_person(alpha.pool(), alpha_post.creator_id)
+      .await
+      .banned
+  );
+  assert!(create_post_as(&beta, beta_community_id, user_jwt.clone())
+    .await
+    .is_err());
+
+  // Unbanning is federated the same way
+  let unban = BanPerson {
+    person_id: beta_post.creator_id,
+    ban: false,
+    remove_data: None,
+    reason: None,
+    expires: None,
+    auth: beta.admin_jwt.clone(),
+  };
+  beta.api(unban).await.unwrap();
+  assert!(
+    !read_person(alpha.pool(), alpha_post.creator_id)
+      .await
+      .banned
+  );
+
+  // A ban by the admin of another instance only applies there, not on the home instance
+  let remote_ban = BanPerson {
+    person_id: alpha_post.creator_id,
+    ban: true,
+    remove_data: None,
+    reason: None,
+    expires: None,
+    auth: alpha.admin_jwt.clone(),
+  };
+  alpha.api(remote_ban).await.unwrap();
+  assert!(
+    read_person(alpha.pool(), alpha_post.creator_id)
+      .await
+      .banned
+  );
+  assert!(!read_person(beta.pool(), beta_post.creator_id).await.banned);
+  assert!(create_post_as(&beta, beta_community_id, user_jwt)
+    .await
+    .is_ok());
+
+  alpha.stop().await;
+  beta.stop().await;
+}
+
+#[actix_web::test]
+#[serial]
+async fn test_crosspost() {
+  let mut instances = TestInstance::start_all(&["alpha"]).await.unwrap();
+  let alpha = instances.pop().unwrap();
+
+  let mut community_ids = vec![];
+  for name in ["main", "other"] {
+    let form = CreateCommunity {
+      name: name.to_string(),
+      title: name.to_string(),
+      description: None,
+      icon: None,
+      banner: None,
+      nsfw: None,
+      auth: alpha.admin_jwt.clone(),
+    };
+    community_ids.push(alpha.crud(form).await.unwrap().community_view.community.id);
+  }
+  let (main_id, other_id) = (community_ids[0], community_ids[1]);
+
+  // The link points at the instance itself, so that no outside requests are made
+  let url = Url::parse(&format!(
+    "{}/linked_article",
+    alpha.context.settings().get_protocol_and_hostname()
+  ))
+  .unwrap();
+  let form = CreatePost {
+    name: "A link".to_string(),
+    community_id: main_id,
+    url: Some(url.clone()),
+    body: Some("Worth reading".to_string()),
+    honeypot: None,
+    nsfw: None,
+    content_warning: None,
+    language: None,
+    poll: None,
+    scheduled_publish_time: None,
+    auth: alpha.admin_jwt.clone(),
+  };
+  let post = alpha.crud(form).await.unwrap().post_view.post;
+
+  // Crossposting into the same community is pointless
+  let crosspost = CreateCrosspost {
+    post_id: post.id,
+    community_id: main_id,
+    auth: alpha.admin_jwt.clone(),
+  };
+  assert!(alpha.crud(crosspost).await.is_err());
+
+  // The crosspost keeps the link, and refers to the original post. More crossposts than fit on
+  // a default page of 10 are created, to check that all of them are listed.
+  let mut crosspost_ids = vec![];
+  for _ in 0..11 {
+    let crosspost = CreateCrosspost {
+      post_id: post.id,
+      community_id: other_id,
+      auth: alpha.admin_jwt.clone(),
+    };
+    let crosspost = alpha.crud(crosspost).await.unwrap().post_view.post;
+    assert_eq!(other_id, crosspost.community_id);
+    assert_eq!(post.name, crosspost.name);
+    let crosspost_url: Option<Url> = crosspost.url.map(Into::into);
+    assert_eq!(Some(url.clone()), crosspost_url);
+    assert_eq!(
+      Some(format!(
+        "cross-posted from: {}\n\nWorth reading",
+        post.ap_id
+      )),
+      crosspost.body
+    );
+    crosspost_ids.push(crosspost.id);
+  }
+
+  // The original post lists the crossposts, but not itself
+  let get_post = GetPost {
+    id: post.id,
+    comment_sort: None,
+    auth: Some(alpha.admin_jwt.clone()),
+  };
+  let mut cross_post_ids: Vec<PostId> = alpha
+    .crud(get_post)
+    .await
+    .unwrap()
+    .cross_posts
+    .iter()
+    .map(|pv| pv.post.id)
+    .collect();
+  cross_post_ids.sort_by_key(|id| id.0);
+  assert_eq!(crosspost_ids, cross_post_ids);
+
+  // and each crosspost lists the original as well as the other crossposts
+  let get_post = GetPost {
+    id: crosspost_ids[0],
+    comment_sort: None,
+    auth: None,
+  };
+  let cross_posts = alpha.crud(get_post).await.unwrap().cross_posts;
+  assert_eq!(11, cross_posts.len());
+  assert!(cross_posts.iter().any(|pv| pv.post.id == post.id));
+  assert!(!cross_posts.iter().any(|pv| pv.post.id == crosspost_ids[0]));
+
+  alpha.stop().await;
+}
+
+#[actix_web::test]
+#[serial]
+async fn test_move_community() {
+  let mut instances = TestInstance::start_all(&["alpha", "beta"]).await.unwrap();
+  let beta = instances.pop().unwrap();
+  let alpha = instances.pop().unwrap();
+
+  let (alpha_community_id, beta_community_id) = follow_community(&alpha, &beta).await;
+  let alpha_community = read_community(alpha.pool(), alpha_community_id).await;
+
+  // The new community on beta agrees to the move
+  let form = CreateCommunity {
+    name: "target".to_string(),
+    title: "Target".to_string(),
+    description: None,
+    icon: None,
+    banner: None,
+    nsfw: None,
+    auth: beta.admin_jwt.clone(),
+  };
+  let target = beta.crud(form).await.unwrap().community_view.community;
+  let form = EditCommunity {
+    community_id: target.id,
+    title: None,
+    description: None,
+    icon: None,
+    banner: None,
+    nsfw: None,
+    also_known_as: Some(vec![alpha_community.actor_id.to_string()]),
+    auth: beta.admin_jwt.clone(),
+  };
+  beta.crud(form).await.unwrap();
+
+  // A user of alpha who isn't a mod sends a move directly to beta, which can't check mod status
+  // of a remote community and needs to reject it
+  let register = Register {
+    username: "alpha_user".to_string(),
+    password: "lemmylemmy".to_string().into(),
+    password_verify: "lemmylemmy".to_string().into(),
+    show_nsfw: false,
+    email: None,
+    captcha_uuid: None,
+    captcha_answer: None,
+    honeypot: None,
+    answer: None,
+  };
+  alpha.crud(register).await.unwrap();
+  let user = blocking(alpha.pool(), |conn| {
+    Person::read_from_name(conn, "alpha_user")
+  })
+  .await
+  .unwrap()
+  .unwrap();
+  let alpha_url = alpha.context.settings().get_protocol_and_hostname();
+  let beta_url = beta.context.settings().get_protocol_and_hostname();
+  let forged_move = serde_json::json!({
+    "@context": "https://www.w3.org/ns/activitystreams",
+    "actor": user.actor_id,
+    "to": ["https://www.w3.org/ns/activitystreams#Public"],
+    "object": alpha_community.actor_id,
+    "target": target.actor_id,
+    "cc": [alpha_community.actor_id],
+    "type": "Move",
+    "id": format!("{}/activities/move/forged", alpha_url),
+  });
+  sign_and_send(
+    alpha.context.client(),
+    &Url::parse(&format!("{}/inbox", beta_url)).unwrap(),
+    forged_move.to_string(),
+    &user.actor_id.into(),
+    user.private_key.unwrap(),
+  )
+  .await
+  .unwrap();
+  assert!(read_community(beta.pool(), beta_community_id)
+    .await
+    .moved_to
+    .is_none());
+
+  // The move by the mod is announced by the community, and accepted
+  let form = MoveCommunity {
+    community_id: alpha_community_id,
+    target: target.actor_id.to_string(),
+    auth: alpha.admin_jwt.clone(),
+  };
+  alpha.api(form).await.unwrap();
+  assert_eq!(
+    Some(target.actor_id),
+    read_community(beta.pool(), beta_community_id)
+      .await
+      .moved_to
+  );
+
+  alpha.stop().await;
+  beta.stop().await;
+}